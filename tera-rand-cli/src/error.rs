@@ -7,4 +7,35 @@ pub(crate) enum TeraRandCliError {
          It is an error to include only one of the two."
     )]
     InvalidBatchArguments,
+    #[error(
+        "`rate` cannot be combined with `batch_size`, `batch_interval`, `burst_size` or \
+         `burst_gap`"
+    )]
+    RateWithBatchArguments,
+    #[error(
+        "Exactly one of `file` and `template` should be included. \
+         It is an error to include both or neither."
+    )]
+    InvalidTemplateArguments,
+    #[error("`{0}` is not a valid timestamp format")]
+    InvalidTimestampFormat(String),
+    #[error("`{0}` is not a valid anomaly rate; it should be between 0 and 1")]
+    InvalidAnomalyRate(f64),
+    #[error("`{0}` is not a valid shard; it should look like `i/n`, where `i` is less than `n`")]
+    InvalidShard(String),
+    #[error(
+        "`{0}` is not a valid delimiter; it should be a single character other than a line \
+         break, or `\\t` for a tab"
+    )]
+    InvalidDelimiter(String),
+    #[error("`csv_header` and `delimiter` can only be used when `format` is `csv`")]
+    CsvOptionsWithoutCsvFormat,
+    #[error("Standard input can be read for either the template or `values_stdin`, but not both")]
+    StdinReadTwice,
+    #[error("Rendering a record exceeded the render timeout of {0:?}")]
+    RenderTimedOut(std::time::Duration),
+    #[error("The thread rendering records stopped unexpectedly")]
+    RenderWorkerStopped,
+    #[error("Found {0} issue(s) in the template")]
+    LintIssuesFound(usize),
 }
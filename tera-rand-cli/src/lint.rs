@@ -0,0 +1,58 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tera::{Tera, Value};
+use tera_rand::{function_parameters, TeraRandFunction};
+
+/// Register each of `functions` on `tera`, both as a function and as a filter, replacing any of the
+/// same name, such that every call is checked against the function's known parameters, and errors
+/// are recorded instead of failing the render. A function which returns an error renders as null, so that rendering can
+/// continue past it and find more issues.
+///
+/// Returns the list of issues found so far, which grows as the template is rendered. Each issue is
+/// recorded once, even if it is found on every iteration of a loop.
+pub(crate) fn register_linting_functions(
+    tera: &mut Tera,
+    functions: &[(&'static str, TeraRandFunction)],
+) -> Arc<Mutex<Vec<String>>> {
+    let issues: Arc<Mutex<Vec<String>>> = Arc::new(Mutex::new(Vec::new()));
+
+    for (name, function) in functions.iter().copied() {
+        let known_parameters_opt: Option<&'static [&'static str]> = function_parameters(name);
+        let function_issues: Arc<Mutex<Vec<String>>> = issues.clone();
+
+        let lint_call = move |args: &HashMap<String, Value>| {
+            let mut call_issues: Vec<String> = Vec::new();
+            if let Some(known_parameters) = known_parameters_opt {
+                let mut unknown_parameters: Vec<&String> = args
+                    .keys()
+                    .filter(|parameter| !known_parameters.contains(&parameter.as_str()))
+                    .collect();
+                unknown_parameters.sort();
+                for parameter in unknown_parameters {
+                    call_issues.push(format!("`{name}` has no parameter `{parameter}`"));
+                }
+            }
+
+            let result: tera::Result<Value> = function(args).or_else(|e| {
+                call_issues.push(format!("`{name}` returned an error: {e}"));
+                Ok(Value::Null)
+            });
+
+            let mut issues = function_issues
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            for issue in call_issues {
+                if !issues.contains(&issue) {
+                    issues.push(issue);
+                }
+            }
+            result
+        };
+        tera.register_function(name, lint_call.clone());
+        tera.register_filter(
+            name,
+            move |_value: &Value, args: &HashMap<String, Value>| lint_call(args),
+        );
+    }
+    issues
+}
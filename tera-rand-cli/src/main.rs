@@ -1,26 +1,59 @@
 #![warn(missing_debug_implementations)]
 
 mod error;
+mod lint;
+mod summary;
 
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, BufWriter, Read, StdoutLock, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
 use std::time::Instant;
 
 use crate::error::TeraRandCliError;
-use clap::Parser;
+use crate::lint::register_linting_functions;
+use crate::summary::{print_choice_summary, register_tallying_random_choice, ChoiceTally};
+use chrono::format::{Item, StrftimeItems};
+use chrono::Local;
+use clap::{Args, CommandFactory, Parser, Subcommand, ValueEnum};
 use iso8601::Duration;
-use tera::{Context, Tera};
+use tera::{Context, Tera, Value};
 use tera_rand::{
-    random_bool, random_char, random_float32, random_float64, random_from_file, random_int32,
-    random_int64, random_ipv4, random_ipv4_cidr, random_ipv6, random_ipv6_cidr, random_string,
-    random_uint32, random_uint64, random_uuid,
+    clear_once_values, random_float64, random_subset, register_all, reset_choice_coverage,
+    set_entropy_fallback_seed, set_file_cache_max_bytes, set_file_cache_ttl, set_seed,
+    set_value_pool, GeneratorFilter, TeraRandFunction, ALL_FUNCTIONS,
 };
 
 #[derive(Debug, Parser)]
 #[command(author, version, about, long_about = None)]
 struct CliArgs {
-    /// filepath of the Tera template to render.
-    #[arg(short, long)]
-    file: PathBuf,
+    #[command(subcommand)]
+    command: CliCommand,
+}
+
+#[derive(Debug, Subcommand)]
+enum CliCommand {
+    /// render the template repeatedly and output each render as a record (the default).
+    ///
+    /// This is the default subcommand, so `tera-rand-cli -f template.json` is the same as
+    /// `tera-rand-cli feed -f template.json`.
+    Feed(Box<FeedArgs>),
+    /// output the lines of a file once each, in a random order.
+    Shuffle(ShuffleArgs),
+    /// render the template once without outputting it, to check that it renders without errors.
+    Check(CheckArgs),
+}
+
+#[derive(Debug, Args)]
+struct FeedArgs {
+    #[command(flatten)]
+    template_args: TemplateArgs,
     /// number of times to render and output the template per `batch_interval`. This is optional,
     /// but if an argument is provided for `batch_size`, then an argument should be provided for
     /// `batch_interval`, as well.
@@ -31,6 +64,26 @@ struct CliArgs {
     /// `batch_interval`, as well.
     #[arg(long)]
     batch_interval: Option<Duration>,
+    /// in burst mode, the number of records to render and output as fast as possible before
+    /// pausing for `burst_gap`. This approximates on/off traffic, unlike the steady pace of
+    /// `batch_size` and `batch_interval`, which it cannot be combined with. Requires `burst_gap`,
+    /// and must be at least 1.
+    #[arg(
+        long,
+        requires = "burst_gap",
+        conflicts_with_all = ["batch_size", "batch_interval"],
+        value_parser = clap::value_parser!(u32).range(1..)
+    )]
+    burst_size: Option<u32>,
+    /// in burst mode, the ISO 8601 duration to pause for after each burst of `burst_size`
+    /// records. Requires `burst_size`.
+    #[arg(long, requires = "burst_size")]
+    burst_gap: Option<Duration>,
+    /// the number of records to render and output per second, spaced evenly, such as `100`. This
+    /// is a simpler way to express a steady pace than `batch_size` and `batch_interval`, which it
+    /// cannot be combined with, and neither can `burst_size` and `burst_gap`.
+    #[arg(long, value_name = "N", value_parser = clap::value_parser!(u32).range(1..))]
+    rate: Option<u32>,
     /// the maximum ISO 8601 duration for which this program should render and output the template.
     /// The program exits when it reaches either `time_limit` or `record_limit`, whichever comes
     /// first.
@@ -41,41 +94,881 @@ struct CliArgs {
     /// first.
     #[arg(short, long)]
     record_limit: Option<u32>,
+    /// render each record into an in-memory buffer instead of outputting it, and print the number
+    /// of records rendered per second to standard error when the program exits. This measures the
+    /// cost of generating records apart from the cost of writing them, which helps when tuning
+    /// `batch_size` and `batch_interval`. Use with `record_limit` or `time_limit` so that the
+    /// program exits and prints its summary.
+    #[arg(long)]
+    measure_only: bool,
+    /// print the number of records written and the rate at which they were written to standard
+    /// error when the program exits, including when it is interrupted with Ctrl-C.
+    #[arg(long)]
+    stats: bool,
+    /// prepend the current local time to each record, followed by a space, formatted with the
+    /// given strftime-like format, such as `%Y-%m-%dT%H:%M:%S%.3f%:z`. See
+    /// <https://docs.rs/chrono/latest/chrono/format/strftime/index.html> for the supported
+    /// specifiers.
+    #[arg(long, value_name = "FORMAT")]
+    timestamp_prefix: Option<String>,
+    /// the probability, from 0 to 1, that each record is rendered from the template at
+    /// `anomaly_file` instead of the main template. This injects malformed or edge-case records
+    /// into the feed for resilience testing. Requires `anomaly_file`.
+    #[arg(long, requires = "anomaly_file")]
+    anomaly_rate: Option<f64>,
+    /// filepath of the Tera template to render in place of the main template for a fraction of
+    /// records, as given by `anomaly_rate`. Requires `anomaly_rate`.
+    #[arg(long, requires = "anomaly_rate")]
+    anomaly_file: Option<PathBuf>,
+    /// count each value produced by `random_choice` calls with a `name`, and print the frequency
+    /// of each value, per name, to standard error when the program exits. This verifies that the
+    /// realized distribution matches the intended one. Use with `record_limit` or `time_limit` so
+    /// that the program exits and prints its summary.
+    #[arg(long)]
+    summary: bool,
+    /// the maximum ISO 8601 duration, such as `PT0.5S`, which rendering a single record may take.
+    /// Each record is then rendered on a separate thread, and if a render exceeds the timeout, the
+    /// program exits with an error instead of hanging on a pathological template, such as one with
+    /// a huge loop.
+    #[arg(long)]
+    render_timeout: Option<Duration>,
+    /// output only the records whose zero-based index modulo `n` equals `i`, given as `i/n`, such
+    /// as `0/2`. Every record is still rendered, so that instances run with the same `seed` and a
+    /// different `i` produce disjoint shards of the same feed, which together make up the output
+    /// of an instance run without `shard`. `record_limit` counts every record, including those
+    /// left to other shards.
+    #[arg(long, value_name = "i/n")]
+    shard: Option<Shard>,
+    /// how records are formatted. With `csv`, the template should render one line per record, with
+    /// `{{ delimiter }}` between columns.
+    #[arg(long, value_enum, default_value_t = OutputFormat::Raw)]
+    format: OutputFormat,
+    /// in `csv` format, a comma-separated list of column names to output as a header row once,
+    /// before any records.
+    #[arg(long, value_name = "COLUMNS", value_delimiter = ',')]
+    csv_header: Option<Vec<String>>,
+    /// in `csv` format, the character which separates columns, such as `;`. Pass `\t` for a tab.
+    /// Templates can refer to it as `{{ delimiter }}`. If not provided, it defaults to a comma.
+    #[arg(long, value_parser = parse_delimiter)]
+    delimiter: Option<char>,
+    /// filepath to write records to instead of standard output. The file is created if it does not
+    /// exist, and truncated if it does, unless `append` is provided.
+    #[arg(short, long, conflicts_with = "measure_only")]
+    output: Option<PathBuf>,
+    /// append records to the end of the file at `output` instead of truncating it. Requires
+    /// `output`.
+    #[arg(long, requires = "output")]
+    append: bool,
+    /// buffer records written to the output, and flush them after every `N` records. If
+    /// `flush_interval` is also provided, records are flushed on whichever comes first. If neither
+    /// is provided, every record is flushed as soon as it is written.
+    #[arg(long, value_name = "N", value_parser = clap::value_parser!(u32).range(1..))]
+    flush_every: Option<u32>,
+    /// buffer records written to the output, and flush them once the given ISO 8601
+    /// duration has passed since the last flush, even while waiting between batches or bursts. If
+    /// `flush_every` is also provided, records are flushed on whichever comes first.
+    #[arg(long)]
+    flush_interval: Option<Duration>,
+    #[command(flatten)]
+    common_args: CommonArgs,
+}
+
+/// How records are formatted, as given to `--format`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum OutputFormat {
+    /// each record is output exactly as the template renders it.
+    Raw,
+    /// each record is a line of delimiter-separated values, optionally preceded by a header row.
+    Csv,
+}
+
+/// Parse the argument to `--delimiter`, which is a single character or `\t` for a tab.
+fn parse_delimiter(delimiter: &str) -> Result<char, TeraRandCliError> {
+    let mut chars = delimiter.chars();
+    match (delimiter, chars.next(), chars.next()) {
+        ("\\t", _, _) => Ok('\t'),
+        (_, Some(delimiter_char), None) if delimiter_char != '\n' && delimiter_char != '\r' => {
+            Ok(delimiter_char)
+        }
+        _ => Err(TeraRandCliError::InvalidDelimiter(String::from(delimiter))),
+    }
+}
+
+/// A shard of the feed, as given to `--shard`: the records whose index modulo `count` equals
+/// `index`.
+#[derive(Clone, Copy, Debug)]
+struct Shard {
+    index: u64,
+    count: u64,
+}
+
+impl Shard {
+    fn contains(&self, record_index: u64) -> bool {
+        record_index % self.count == self.index
+    }
+}
+
+impl FromStr for Shard {
+    type Err = TeraRandCliError;
+
+    fn from_str(shard: &str) -> Result<Self, Self::Err> {
+        let invalid_shard = || TeraRandCliError::InvalidShard(String::from(shard));
+        let (index, count) = shard.split_once('/').ok_or_else(invalid_shard)?;
+        let index: u64 = index.trim().parse().map_err(|_| invalid_shard())?;
+        let count: u64 = count.trim().parse().map_err(|_| invalid_shard())?;
+        if index >= count {
+            return Err(invalid_shard());
+        }
+        Ok(Self { index, count })
+    }
+}
+
+#[derive(Debug, Args)]
+struct ShuffleArgs {
+    /// filepath of a line-delimited file whose lines should be shuffled.
+    #[arg(short, long)]
+    file: PathBuf,
+    /// seed for the random number generator. Shuffling the same file with the same seed produces
+    /// the same order. If not provided, the `TERA_RAND_SEED` environment variable is used; if
+    /// neither is set, the order is not reproducible.
+    #[arg(long, env = "TERA_RAND_SEED")]
+    seed: Option<u64>,
+}
+
+#[derive(Debug, Args)]
+struct CheckArgs {
+    #[command(flatten)]
+    template_args: TemplateArgs,
+    /// instead of stopping at the first error, report every issue found while rendering: calls
+    /// which pass a parameter the function does not have, and calls which return an error. Both
+    /// function calls and filter calls, such as `"" | random_hex(length=8)`, are checked.
+    #[arg(long)]
+    lint: bool,
+    #[command(flatten)]
+    common_args: CommonArgs,
+}
+
+/// Where the template comes from, shared by the subcommands which render templates. Exactly one of
+/// `file` and `template` should be provided.
+#[derive(Debug, Args)]
+struct TemplateArgs {
+    /// filepath of the Tera template, or `-` to read the template from standard input.
+    #[arg(short, long)]
+    file: Option<PathBuf>,
+    /// the Tera template itself, such as `'{"x": {{ random_uint32() }}}'`, instead of a filepath.
+    #[arg(long)]
+    template: Option<String>,
+}
+
+/// The template given by `TemplateArgs`.
+#[derive(Debug)]
+enum TemplateSource<'a> {
+    File(&'a Path),
+    Inline(&'a str),
+}
+
+impl TemplateArgs {
+    fn source(&self) -> Result<TemplateSource<'_>, TeraRandCliError> {
+        match (&self.file, &self.template) {
+            (Some(file), None) => Ok(TemplateSource::File(file)),
+            (None, Some(template)) => Ok(TemplateSource::Inline(template)),
+            _ => Err(TeraRandCliError::InvalidTemplateArguments),
+        }
+    }
+}
+
+impl TemplateSource<'_> {
+    fn is_stdin(&self) -> bool {
+        matches!(self, TemplateSource::File(file) if file.as_os_str() == "-")
+    }
+
+    /// How to refer to the template in messages to the user.
+    fn display_name(&self) -> String {
+        match self {
+            TemplateSource::File(file) => file.display().to_string(),
+            TemplateSource::Inline(_) => String::from("template"),
+        }
+    }
+}
+
+/// Options which affect how templates are rendered, shared by the subcommands which render them.
+#[derive(Debug, Args)]
+struct CommonArgs {
+    /// seed for the random number generator. Rendering the same template with the same seed
+    /// produces the same output. If not provided, the `TERA_RAND_SEED` environment variable is
+    /// used; if neither is set, output is not reproducible.
+    #[arg(long, env = "TERA_RAND_SEED")]
+    seed: Option<u64>,
+    /// read a pool of values from standard input, one value per line, and register it under the
+    /// given name. Templates can then sample from it with `random_choice(name="<NAME>")`. Standard
+    /// input is read once, before rendering starts.
+    #[arg(long, value_name = "NAME")]
+    values_stdin: Option<String>,
+    /// the maximum number of bytes of file contents to keep cached for functions such as
+    /// `random_from_file`. When exceeded, the least recently used files are evicted and read again
+    /// on their next use. If not provided, the cache is unbounded.
+    #[arg(long)]
+    file_cache_bytes: Option<usize>,
+    /// the number of seconds to keep the contents of a file cached for functions such as
+    /// `random_from_file` before reading the file again, which picks up changes to files during a
+    /// long run. If not provided, cached files are never read again unless they are evicted.
+    #[arg(long, value_name = "SECS")]
+    file_cache_ttl_secs: Option<u64>,
+    /// seed to fall back to if the operating system cannot provide entropy, as in some locked-down
+    /// sandboxes. Without it, the program panics in that case. If not provided, the
+    /// `TERA_RAND_FALLBACK_SEED` environment variable is used. This has no effect when `seed` is
+    /// provided.
+    #[arg(long, env = "TERA_RAND_FALLBACK_SEED")]
+    entropy_fallback_seed: Option<u64>,
 }
 
 fn main() {
-    let cli_args: CliArgs = CliArgs::parse();
-    let mut tera: Tera = Tera::default();
+    let cli_args: CliArgs = CliArgs::parse_from(args_with_default_subcommand());
 
-    register_tera_rand_functions(&mut tera);
-    render_template(&mut tera, cli_args).unwrap_or_else(|e| {
+    let result: anyhow::Result<()> = match cli_args.command {
+        CliCommand::Feed(feed_args) => feed(*feed_args),
+        CliCommand::Shuffle(shuffle_args) => shuffle(shuffle_args),
+        CliCommand::Check(check_args) => check(check_args),
+    };
+    result.unwrap_or_else(|e| {
         eprintln!("Encountered a fatal error: {e:?}");
         std::process::exit(1)
     });
 }
 
+/// Return the command line arguments, inserting the `feed` subcommand if no subcommand was given,
+/// so that invocations from before subcommands existed, like `tera-rand-cli -f template.json`,
+/// keep working. Top-level flags like `--help` and `--version` are left alone.
+fn args_with_default_subcommand() -> Vec<OsString> {
+    let mut args: Vec<OsString> = std::env::args_os().collect();
+    let first_arg: Option<&str> = args.get(1).and_then(|arg| arg.to_str());
+
+    let needs_default_subcommand: bool = match first_arg {
+        Some("-h" | "--help" | "-V" | "--version" | "help") => false,
+        Some(first_arg) => CliArgs::command().find_subcommand(first_arg).is_none(),
+        None => true,
+    };
+    if needs_default_subcommand {
+        args.insert(1, OsString::from("feed"));
+    }
+    args
+}
+
+/// Apply the options shared by the subcommands which render templates. This must happen before
+/// rendering starts.
+fn apply_common_args(
+    common_args: &CommonArgs,
+    template_source: &TemplateSource,
+) -> anyhow::Result<()> {
+    // standard input can only be read once, so it can hold either the template or a value pool
+    if common_args.values_stdin.is_some() && template_source.is_stdin() {
+        return Err(TeraRandCliError::StdinReadTwice.into());
+    }
+    if let Some(seed) = common_args.seed {
+        set_seed(seed);
+    }
+    set_file_cache_max_bytes(common_args.file_cache_bytes);
+    set_file_cache_ttl(
+        common_args
+            .file_cache_ttl_secs
+            .map(std::time::Duration::from_secs),
+    );
+    set_entropy_fallback_seed(common_args.entropy_fallback_seed);
+    // each run starts a new round of `ensure_all=true` coverage
+    reset_choice_coverage();
+    if let Some(name) = &common_args.values_stdin {
+        read_value_pool_from_stdin(name)?;
+    }
+    Ok(())
+}
+
+fn feed(feed_args: FeedArgs) -> anyhow::Result<()> {
+    let template_source: TemplateSource = feed_args.template_args.source()?;
+    apply_common_args(&feed_args.common_args, &template_source)?;
+    ctrlc::set_handler(|| SHUTDOWN_REQUESTED.store(true, Ordering::SeqCst))?;
+
+    let mut tera: Tera = Tera::default();
+    register_tera_rand_functions(&mut tera);
+    let choice_tally_opt: Option<ChoiceTally> = if feed_args.summary {
+        Some(register_tallying_random_choice(&mut tera))
+    } else {
+        None
+    };
+
+    let measure_only: bool = feed_args.measure_only;
+    let stats: bool = feed_args.stats;
+    let destination: RecordDestination = if measure_only {
+        RecordDestination::InMemory(Vec::new())
+    } else if let Some(output_path) = &feed_args.output {
+        let file: File = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .append(feed_args.append)
+            .truncate(!feed_args.append)
+            .open(output_path)?;
+        RecordDestination::File(BufWriter::new(file))
+    } else {
+        RecordDestination::Stdout(BufWriter::new(std::io::stdout().lock()))
+    };
+    if let Some(timestamp_format) = &feed_args.timestamp_prefix {
+        if StrftimeItems::new(timestamp_format).any(|item| item == Item::Error) {
+            return Err(TeraRandCliError::InvalidTimestampFormat(timestamp_format.clone()).into());
+        }
+    }
+    if let Some(anomaly_rate) = feed_args.anomaly_rate {
+        if !(0.0..=1.0).contains(&anomaly_rate) {
+            return Err(TeraRandCliError::InvalidAnomalyRate(anomaly_rate).into());
+        }
+    }
+    if feed_args.format != OutputFormat::Csv
+        && (feed_args.csv_header.is_some() || feed_args.delimiter.is_some())
+    {
+        return Err(TeraRandCliError::CsvOptionsWithoutCsvFormat.into());
+    }
+    let mut context: Context = Context::new();
+    let csv_opt: Option<CsvFormat> = match feed_args.format {
+        OutputFormat::Raw => None,
+        OutputFormat::Csv => {
+            let delimiter: char = feed_args.delimiter.unwrap_or(',');
+            context.insert("delimiter", &delimiter);
+            Some(CsvFormat {
+                delimiter,
+                header: feed_args.csv_header.clone(),
+            })
+        }
+    };
+    add_template(&mut tera, &template_source, "template")?;
+    if let Some(anomaly_file) = &feed_args.anomaly_file {
+        tera.add_template_file(anomaly_file, Some("anomaly"))?;
+    }
+    let render_worker_opt: Option<RenderWorker> = feed_args.render_timeout.map(|render_timeout| {
+        RenderWorker::spawn(
+            tera.clone(),
+            context.clone(),
+            feed_args.common_args.seed,
+            render_timeout.into(),
+        )
+    });
+    let mut output: RecordOutput = RecordOutput::new(
+        destination,
+        feed_args.timestamp_prefix.clone(),
+        feed_args.anomaly_rate,
+        render_worker_opt,
+        feed_args.shard,
+        csv_opt,
+        FlushCadence {
+            every: feed_args.flush_every,
+            interval: feed_args.flush_interval.map(Into::into),
+        },
+    );
+    let start_time: Instant = Instant::now();
+    render_template(&tera, &context, feed_args, &mut output)?;
+    output.flush()?;
+
+    let elapsed_secs: f64 = start_time.elapsed().as_secs_f64();
+    let records_per_sec: f64 = if elapsed_secs > 0.0 {
+        output.records_written as f64 / elapsed_secs
+    } else {
+        0.0
+    };
+    if measure_only {
+        eprintln!(
+            "rendered {} records in {:.3}s ({:.1} records/sec)",
+            output.records_written, elapsed_secs, records_per_sec
+        );
+    }
+    if stats {
+        eprintln!(
+            "generated {} records in {:.2}s ({:.0}/s)",
+            output.records_written, elapsed_secs, records_per_sec
+        );
+    }
+    if shutdown_requested() && !measure_only {
+        eprintln!(
+            "interrupted after writing {} records",
+            output.records_written
+        );
+    }
+    if let Some(choice_tally) = &choice_tally_opt {
+        print_choice_summary(choice_tally);
+    }
+    Ok(())
+}
+
+// Set when the program receives SIGINT, such as from Ctrl-C, so that `feed` can stop rendering,
+// flush the records written so far, and exit cleanly.
+static SHUTDOWN_REQUESTED: AtomicBool = AtomicBool::new(false);
+
+fn shutdown_requested() -> bool {
+    SHUTDOWN_REQUESTED.load(Ordering::SeqCst)
+}
+
+/// Sleep for `duration`, waking up early if a shutdown is requested, so that a long pause between
+/// batches does not hold up the exit.
+fn sleep_unless_shutdown(duration: core::time::Duration) {
+    const MAX_SLEEP_SLICE: core::time::Duration = core::time::Duration::from_millis(50);
+
+    let deadline: Instant = Instant::now() + duration;
+    while !shutdown_requested() {
+        match deadline.checked_duration_since(Instant::now()) {
+            Some(time_remaining) if !time_remaining.is_zero() => {
+                thread::sleep(time_remaining.min(MAX_SLEEP_SLICE));
+            }
+            _ => break,
+        }
+    }
+}
+
+fn shuffle(shuffle_args: ShuffleArgs) -> anyhow::Result<()> {
+    if let Some(seed) = shuffle_args.seed {
+        set_seed(seed);
+    }
+
+    let buf_reader: BufReader<File> = BufReader::new(File::open(&shuffle_args.file)?);
+    let lines: Vec<Value> = buf_reader
+        .lines()
+        .map(|line_result| line_result.map(Value::String))
+        .collect::<std::io::Result<Vec<Value>>>()?;
+
+    // a subset of every line is a shuffle of the lines, drawn from the same seedable generator as
+    // the template functions
+    let line_count: usize = lines.len();
+    let subset_args: HashMap<String, Value> = HashMap::from([
+        (String::from("values"), Value::Array(lines)),
+        (String::from("count"), Value::from(line_count)),
+    ]);
+    let shuffled_lines: Value = random_subset(&subset_args)?;
+
+    let mut stdout = std::io::stdout().lock();
+    for line in shuffled_lines.as_array().into_iter().flatten() {
+        writeln!(stdout, "{}", line.as_str().unwrap_or_default())?;
+    }
+    Ok(())
+}
+
+fn check(check_args: CheckArgs) -> anyhow::Result<()> {
+    let template_source: TemplateSource = check_args.template_args.source()?;
+    apply_common_args(&check_args.common_args, &template_source)?;
+
+    let mut tera: Tera = Tera::default();
+    register_tera_rand_functions(&mut tera);
+    add_template(&mut tera, &template_source, "template")?;
+    if !check_args.lint {
+        tera.render("template", &Context::new())?;
+        println!("{} rendered successfully", template_source.display_name());
+        return Ok(());
+    }
+
+    let issues: Arc<Mutex<Vec<String>>> =
+        register_linting_functions(&mut tera, &tera_rand_functions());
+    let render_result: tera::Result<String> = tera.render("template", &Context::new());
+    let mut issues: Vec<String> = std::mem::take(
+        &mut *issues
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()),
+    );
+    if let Err(e) = render_result {
+        issues.push(format!("rendering stopped early: {e:?}"));
+    }
+    if issues.is_empty() {
+        println!("{} rendered successfully", template_source.display_name());
+        return Ok(());
+    }
+
+    for issue in &issues {
+        eprintln!("{}: {issue}", template_source.display_name());
+    }
+    Err(TeraRandCliError::LintIssuesFound(issues.len()).into())
+}
+
+// The stubs of the functions which `register_all` leaves out because their feature is disabled.
+// Registering them anyway means that a template which calls one fails with an error naming the
+// feature to enable, rather than with an unknown function error.
+const DISABLED_FEATURE_FUNCTIONS: &[(&str, TeraRandFunction)] = &[
+    #[cfg(not(feature = "time"))]
+    ("random_datetime", tera_rand::random_datetime),
+    #[cfg(not(feature = "time"))]
+    ("random_duration", tera_rand::random_duration),
+    #[cfg(not(feature = "distributions"))]
+    ("random_exponential", tera_rand::random_exponential),
+    #[cfg(not(feature = "distributions"))]
+    ("random_file_size", tera_rand::random_file_size),
+    #[cfg(not(feature = "schema"))]
+    ("random_from_schema", tera_rand::random_from_schema),
+    #[cfg(not(feature = "sqlite"))]
+    ("random_from_sqlite", tera_rand::random_from_sqlite),
+    #[cfg(not(feature = "distributions"))]
+    ("random_lognormal", tera_rand::random_lognormal),
+    #[cfg(not(feature = "distributions"))]
+    ("random_mixture", tera_rand::random_mixture),
+    #[cfg(not(feature = "distributions"))]
+    ("random_normal", tera_rand::random_normal),
+    #[cfg(not(feature = "distributions"))]
+    ("random_poisson", tera_rand::random_poisson),
+    #[cfg(not(feature = "ulid"))]
+    ("random_ulid", tera_rand::random_ulid),
+    #[cfg(not(feature = "uuid"))]
+    ("random_uuid", tera_rand::random_uuid),
+    #[cfg(not(feature = "uuid"))]
+    ("random_uuid_v5", tera_rand::random_uuid_v5),
+    #[cfg(not(feature = "uuid"))]
+    ("random_uuid_v7", tera_rand::random_uuid_v7),
+];
+
 fn register_tera_rand_functions(tera: &mut Tera) {
-    tera.register_function("random_bool", random_bool);
-    tera.register_function("random_char", random_char);
-    tera.register_function("random_float32", random_float32);
-    tera.register_function("random_float64", random_float64);
-    tera.register_function("random_from_file", random_from_file);
-    tera.register_function("random_int32", random_int32);
-    tera.register_function("random_int64", random_int64);
-    tera.register_function("random_ipv4", random_ipv4);
-    tera.register_function("random_ipv4_cidr", random_ipv4_cidr);
-    tera.register_function("random_ipv6", random_ipv6);
-    tera.register_function("random_ipv6_cidr", random_ipv6_cidr);
-    tera.register_function("random_string", random_string);
-    tera.register_function("random_uint32", random_uint32);
-    tera.register_function("random_uint64", random_uint64);
-    tera.register_function("random_uuid", random_uuid);
+    register_all(tera);
+    for (name, function) in DISABLED_FEATURE_FUNCTIONS.iter().copied() {
+        tera.register_function(name, function);
+        tera.register_filter(name, GeneratorFilter::new(function));
+    }
+}
+
+// Every tera-rand function registered by `register_tera_rand_functions`.
+fn tera_rand_functions() -> Vec<(&'static str, TeraRandFunction)> {
+    ALL_FUNCTIONS
+        .iter()
+        .chain(DISABLED_FEATURE_FUNCTIONS)
+        .copied()
+        .collect()
+}
+
+/// Add the template from `template_source` to `tera` under `name`, reading it from standard input
+/// if its filepath is `-`.
+fn add_template(
+    tera: &mut Tera,
+    template_source: &TemplateSource,
+    name: &str,
+) -> anyhow::Result<()> {
+    match template_source {
+        _ if template_source.is_stdin() => {
+            let mut template: String = String::new();
+            std::io::stdin().lock().read_to_string(&mut template)?;
+            tera.add_raw_template(name, &template)?;
+        }
+        TemplateSource::File(file) => tera.add_template_file(file, Some(name))?,
+        TemplateSource::Inline(template) => tera.add_raw_template(name, template)?,
+    }
+    Ok(())
+}
+
+/// Read standard input line by line and register the lines as a tera-rand value pool under `name`.
+/// Empty lines are skipped.
+fn read_value_pool_from_stdin(name: &str) -> anyhow::Result<()> {
+    let mut values: Vec<Value> = Vec::new();
+    for line_result in std::io::stdin().lock().lines() {
+        let line: String = line_result?;
+        if !line.is_empty() {
+            values.push(Value::String(line));
+        }
+    }
+    set_value_pool(name, values);
+    Ok(())
+}
+
+/// Where rendered records go.
+#[derive(Debug)]
+enum RecordDestination {
+    /// Standard output, buffered until the records are flushed according to the `FlushCadence`.
+    Stdout(BufWriter<StdoutLock<'static>>),
+    /// A file given by `--output`, buffered in the same way as standard output.
+    File(BufWriter<File>),
+    /// An in-memory buffer which is cleared after each record, so that it never grows beyond the
+    /// size of the largest record.
+    InMemory(Vec<u8>),
+}
+
+/// Writes rendered records to their destination and counts how many have been rendered and
+/// written.
+#[derive(Debug)]
+struct RecordOutput {
+    destination: RecordDestination,
+    // a strftime-like format for a timestamp to write before each record
+    timestamp_format: Option<String>,
+    // the probability of rendering the "anomaly" template instead of the main one
+    anomaly_rate: Option<f64>,
+    // renders each record on another thread with a timeout, if a render timeout is set
+    render_worker_opt: Option<RenderWorker>,
+    // if set, only the records in this shard are written, and the rest are rendered and discarded
+    shard_opt: Option<Shard>,
+    // if set, records are CSV lines, which may be preceded by a header row
+    csv_opt: Option<CsvFormat>,
+    flush_cadence: FlushCadence,
+    records_rendered: u64,
+    records_written: u64,
+    // the number of records written since the last flush, and when that flush happened
+    records_unflushed: u32,
+    last_flush_time: Instant,
+}
+
+/// The options of the `csv` output format.
+#[derive(Clone, Debug)]
+struct CsvFormat {
+    delimiter: char,
+    header: Option<Vec<String>>,
+}
+
+impl CsvFormat {
+    /// Join the header's column names with the delimiter, quoting any name which contains the
+    /// delimiter, a quotation mark, or a line break, as described in RFC 4180.
+    fn header_row(&self) -> Option<String> {
+        let header: &Vec<String> = self.header.as_ref()?;
+        let columns: Vec<String> = header
+            .iter()
+            .map(|column| {
+                if column.contains([self.delimiter, '"', '\n', '\r']) {
+                    format!("\"{}\"", column.replace('"', "\"\""))
+                } else {
+                    column.clone()
+                }
+            })
+            .collect();
+        Some(columns.join(&self.delimiter.to_string()))
+    }
+}
+
+/// When buffered records are flushed to their destination: after `every` records or once `interval`
+/// has passed since the last flush, whichever comes first. If neither is set, every record is
+/// flushed as soon as it is written.
+#[derive(Clone, Copy, Debug)]
+struct FlushCadence {
+    every: Option<u32>,
+    interval: Option<core::time::Duration>,
+}
+
+impl RecordOutput {
+    fn new(
+        destination: RecordDestination,
+        timestamp_format: Option<String>,
+        anomaly_rate: Option<f64>,
+        render_worker_opt: Option<RenderWorker>,
+        shard_opt: Option<Shard>,
+        csv_opt: Option<CsvFormat>,
+        flush_cadence: FlushCadence,
+    ) -> Self {
+        Self {
+            destination,
+            timestamp_format,
+            anomaly_rate,
+            render_worker_opt,
+            shard_opt,
+            csv_opt,
+            flush_cadence,
+            records_rendered: 0,
+            records_written: 0,
+            records_unflushed: 0,
+            last_flush_time: Instant::now(),
+        }
+    }
+
+    /// Write the CSV header row, if there is one. This should happen once, before any records.
+    fn write_csv_header(&mut self) -> anyhow::Result<()> {
+        let Some(header_row) = self.csv_opt.as_ref().and_then(CsvFormat::header_row) else {
+            return Ok(());
+        };
+        match &mut self.destination {
+            RecordDestination::Stdout(stdout) => writeln!(stdout, "{header_row}")?,
+            RecordDestination::File(file) => writeln!(file, "{header_row}")?,
+            // measuring the rate of rendering records has no use for a header
+            RecordDestination::InMemory(_) => return Ok(()),
+        }
+        self.flush()?;
+        Ok(())
+    }
+
+    fn write_record(&mut self, tera: &Tera, context: &Context) -> anyhow::Result<()> {
+        // the loop writing this record stops at its next check, so skip the rest of its batch
+        if shutdown_requested() {
+            return Ok(());
+        }
+        let template_name: &'static str = self.choose_template_name()?;
+        let timestamp_format: Option<&str> = self.timestamp_format.as_deref();
+        // a record outside of the shard is still rendered, so that every shard draws the same
+        // random values for the same record
+        let in_shard: bool = self
+            .shard_opt
+            .map_or(true, |shard| shard.contains(self.records_rendered));
+        self.records_rendered += 1;
+        let mut sink = std::io::sink();
+        let writer: &mut dyn Write = match &mut self.destination {
+            _ if !in_shard => &mut sink,
+            RecordDestination::Stdout(stdout) => stdout,
+            RecordDestination::File(file) => file,
+            RecordDestination::InMemory(buffer) => buffer,
+        };
+
+        if in_shard {
+            write_timestamp_prefix(timestamp_format, &mut *writer)?;
+        }
+        match &self.render_worker_opt {
+            Some(render_worker) => {
+                let rendered: String = render_worker.render(template_name)?;
+                writer.write_all(rendered.as_bytes())?;
+            }
+            None => {
+                clear_once_values();
+                tera.render_to(template_name, context, &mut *writer)?;
+            }
+        }
+
+        if let RecordDestination::InMemory(buffer) = &mut self.destination {
+            buffer.clear();
+        }
+        if in_shard {
+            self.records_written += 1;
+            self.records_unflushed += 1;
+            if self.flush_is_due() {
+                self.flush()?;
+            }
+        }
+        Ok(())
+    }
+
+    fn flush_is_due(&self) -> bool {
+        match self.flush_cadence {
+            FlushCadence {
+                every: None,
+                interval: None,
+            } => true,
+            FlushCadence { every, interval } => {
+                every.is_some_and(|every| self.records_unflushed >= every)
+                    || interval.is_some_and(|interval| self.last_flush_time.elapsed() >= interval)
+            }
+        }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        match &mut self.destination {
+            RecordDestination::Stdout(stdout) => stdout.flush()?,
+            RecordDestination::File(file) => file.flush()?,
+            RecordDestination::InMemory(_) => {}
+        }
+        self.records_unflushed = 0;
+        self.last_flush_time = Instant::now();
+        Ok(())
+    }
+
+    /// Sleep until `deadline`, if it has not passed yet, such as until the next record is due.
+    fn sleep_until(&mut self, deadline: Instant) -> std::io::Result<()> {
+        match deadline.checked_duration_since(Instant::now()) {
+            Some(time_remaining) => self.sleep(time_remaining),
+            None => Ok(()),
+        }
+    }
+
+    /// Sleep for `duration`, such as between batches, waking up to flush any buffered records
+    /// whose flush interval ends during the sleep.
+    fn sleep(&mut self, duration: core::time::Duration) -> std::io::Result<()> {
+        if let (Some(interval), true) = (self.flush_cadence.interval, self.records_unflushed > 0) {
+            let until_flush: core::time::Duration =
+                interval.saturating_sub(self.last_flush_time.elapsed());
+            if until_flush < duration {
+                sleep_unless_shutdown(until_flush);
+                self.flush()?;
+                sleep_unless_shutdown(duration - until_flush);
+                return Ok(());
+            }
+        }
+        sleep_unless_shutdown(duration);
+        Ok(())
+    }
+
+    // Roll against the anomaly rate to choose which template to render. The roll goes through
+    // tera-rand's random number generator so that `--seed` also reproduces which records are
+    // anomalies.
+    fn choose_template_name(&self) -> anyhow::Result<&'static str> {
+        let Some(anomaly_rate) = self.anomaly_rate else {
+            return Ok("template");
+        };
+        let roll: Value = random_float64(&HashMap::new())?;
+        if roll.as_f64().is_some_and(|roll| roll < anomaly_rate) {
+            Ok("anomaly")
+        } else {
+            Ok("template")
+        }
+    }
+}
+
+fn write_timestamp_prefix(
+    timestamp_format: Option<&str>,
+    writer: &mut (impl Write + ?Sized),
+) -> std::io::Result<()> {
+    match timestamp_format {
+        Some(timestamp_format) => write!(writer, "{} ", Local::now().format(timestamp_format)),
+        None => Ok(()),
+    }
+}
+
+/// A thread which renders records on request, so that a render which takes too long can be
+/// abandoned. Rust cannot stop a thread from the outside, so an abandoned render keeps running until
+/// the program exits, which it does as soon as the timeout is reported.
+#[derive(Debug)]
+struct RenderWorker {
+    template_name_sender: Sender<&'static str>,
+    rendered_receiver: Receiver<tera::Result<String>>,
+    timeout: core::time::Duration,
+}
+
+impl RenderWorker {
+    /// Spawn a thread which owns `tera`. The seed for the random number generator is applied on
+    /// that thread, since each thread has its own generator.
+    fn spawn(
+        tera: Tera,
+        context: Context,
+        seed_opt: Option<u64>,
+        timeout: core::time::Duration,
+    ) -> Self {
+        let (template_name_sender, template_name_receiver) = mpsc::channel::<&'static str>();
+        let (rendered_sender, rendered_receiver) = mpsc::channel::<tera::Result<String>>();
+
+        thread::spawn(move || {
+            if let Some(seed) = seed_opt {
+                set_seed(seed);
+            }
+            for template_name in template_name_receiver {
+                clear_once_values();
+                if rendered_sender
+                    .send(tera.render(template_name, &context))
+                    .is_err()
+                {
+                    break;
+                }
+            }
+        });
+        Self {
+            template_name_sender,
+            rendered_receiver,
+            timeout,
+        }
+    }
+
+    /// Render the template named `template_name` on the worker thread, waiting at most `timeout`.
+    fn render(&self, template_name: &'static str) -> anyhow::Result<String> {
+        self.template_name_sender
+            .send(template_name)
+            .map_err(|_| TeraRandCliError::RenderWorkerStopped)?;
+        match self.rendered_receiver.recv_timeout(self.timeout) {
+            Ok(rendered) => Ok(rendered?),
+            Err(RecvTimeoutError::Timeout) => {
+                Err(TeraRandCliError::RenderTimedOut(self.timeout).into())
+            }
+            Err(RecvTimeoutError::Disconnected) => {
+                Err(TeraRandCliError::RenderWorkerStopped.into())
+            }
+        }
+    }
 }
 
 /// Use the Tera instance passed in to render the template provided by the user via the command
-/// line. Depending on the command line options, this function may run in an infinite loop.
-fn render_template(tera: &mut Tera, cli_args: CliArgs) -> anyhow::Result<()> {
-    let context: Context = Context::new();
+/// line into `output`. Depending on the command line options, this function may run in an
+/// infinite loop.
+fn render_template(
+    tera: &Tera,
+    context: &Context,
+    feed_args: FeedArgs,
+    output: &mut RecordOutput,
+) -> anyhow::Result<()> {
+    // the header comes before every combination of the cli arguments below, so write it once here
+    output.write_csv_header()?;
 
     // the base logic when just filename is specified is just "render a template in an infinite
     // loop". It is so simple that each cli argument has a proportionally large impact on the logic.
@@ -84,23 +977,59 @@ fn render_template(tera: &mut Tera, cli_args: CliArgs) -> anyhow::Result<()> {
     // arguments grows (and thus the number of combinations).
 
     // batch_size and batch_interval go hand in hand. In this outer match block, do not allow one
-    // of the two arguments to be specified without the other.
-    match cli_args {
-        CliArgs {
-            file,
+    // of the two arguments to be specified without the other. The same goes for burst_size and
+    // burst_gap, which clap already requires together and keeps apart from the batch arguments.
+    // rate stands alone, so it may not be specified with any of them.
+    match feed_args {
+        FeedArgs {
+            rate: Some(rate),
+            batch_size: None,
+            batch_interval: None,
+            burst_size: None,
+            burst_gap: None,
+            record_limit: total_records,
+            time_limit: total_duration,
+            ..
+        } => render_at_rate(tera, context, output, rate, total_records, total_duration),
+        FeedArgs { rate: Some(_), .. } => Err(TeraRandCliError::RateWithBatchArguments.into()),
+        FeedArgs {
             batch_size: None,
             batch_interval: None,
+            burst_size: Some(burst_size),
+            burst_gap: Some(burst_gap),
             record_limit: total_records,
             time_limit: total_duration,
+            ..
         } => {
-            tera.add_template_file(file, Some("template"))?;
+            let burst_gap: core::time::Duration = burst_gap.into();
+
             match (total_records, total_duration) {
-                (None, None) => loop {
-                    tera.render_to("template", &context, std::io::stdout())?;
-                },
+                (None, None) => {
+                    while !shutdown_requested() {
+                        // render a burst
+                        for _ in 0..burst_size {
+                            output.write_record(tera, context)?;
+                        }
+                        output.sleep(burst_gap)?;
+                    }
+                    Ok(())
+                }
                 (Some(total_records), None) => {
-                    for _ in 0..total_records {
-                        tera.render_to("template", &context, std::io::stdout())?;
+                    let mut records_remaining: u32 = total_records;
+
+                    // produce until we've hit our record limit
+                    while !shutdown_requested() && records_remaining > 0u32 {
+                        let current_burst_size: u32 = records_remaining.min(burst_size);
+                        // render a burst
+                        for _ in 0..current_burst_size {
+                            output.write_record(tera, context)?;
+                        }
+
+                        records_remaining -= current_burst_size;
+                        // pause between bursts, but not after the last one
+                        if records_remaining > 0u32 {
+                            output.sleep(burst_gap)?;
+                        }
                     }
                     Ok(())
                 }
@@ -108,62 +1037,134 @@ fn render_template(tera: &mut Tera, cli_args: CliArgs) -> anyhow::Result<()> {
                     let total_duration: core::time::Duration = total_duration.into();
                     let program_start_time: Instant = Instant::now();
 
-                    while total_duration
-                        .checked_sub(program_start_time.elapsed())
-                        .is_some()
+                    // produce until we've hit our time limit
+                    while !shutdown_requested()
+                        && total_duration
+                            .checked_sub(program_start_time.elapsed())
+                            .is_some()
                     {
-                        tera.render_to("template", &context, std::io::stdout())?;
+                        // render a burst
+                        for _ in 0..burst_size {
+                            output.write_record(tera, context)?;
+                        }
+                        output.sleep(burst_gap)?;
                     }
                     Ok(())
                 }
                 (Some(total_records), Some(total_duration)) => {
+                    let mut records_remaining: u32 = total_records;
                     let total_duration: core::time::Duration = total_duration.into();
                     let program_start_time: Instant = Instant::now();
-                    let mut records_remaining: u32 = total_records;
 
-                    while total_duration
-                        .checked_sub(program_start_time.elapsed())
-                        .is_some()
-                        && records_remaining > 0
+                    // produce until we've hit our record limit or our time limit,
+                    // whichever comes first
+                    while !shutdown_requested()
+                        && records_remaining > 0u32
+                        && total_duration
+                            .checked_sub(program_start_time.elapsed())
+                            .is_some()
                     {
-                        tera.render_to("template", &context, std::io::stdout())?;
-                        records_remaining -= 1;
+                        let current_burst_size: u32 = records_remaining.min(burst_size);
+                        // render a burst
+                        for _ in 0..current_burst_size {
+                            output.write_record(tera, context)?;
+                        }
+
+                        records_remaining -= current_burst_size;
+                        // pause between bursts, but not after the last one
+                        if records_remaining > 0u32 {
+                            output.sleep(burst_gap)?;
+                        }
                     }
                     Ok(())
                 }
             }
         }
-        CliArgs {
-            file,
+        FeedArgs {
+            batch_size: None,
+            batch_interval: None,
+            burst_size: None,
+            burst_gap: None,
+            record_limit: total_records,
+            time_limit: total_duration,
+            ..
+        } => match (total_records, total_duration) {
+            (None, None) => {
+                while !shutdown_requested() {
+                    output.write_record(tera, context)?;
+                }
+                Ok(())
+            }
+            (Some(total_records), None) => {
+                for _ in 0..total_records {
+                    if shutdown_requested() {
+                        break;
+                    }
+                    output.write_record(tera, context)?;
+                }
+                Ok(())
+            }
+            (None, Some(total_duration)) => {
+                let total_duration: core::time::Duration = total_duration.into();
+                let program_start_time: Instant = Instant::now();
+
+                while !shutdown_requested()
+                    && total_duration
+                        .checked_sub(program_start_time.elapsed())
+                        .is_some()
+                {
+                    output.write_record(tera, context)?;
+                }
+                Ok(())
+            }
+            (Some(total_records), Some(total_duration)) => {
+                let total_duration: core::time::Duration = total_duration.into();
+                let program_start_time: Instant = Instant::now();
+                let mut records_remaining: u32 = total_records;
+
+                while !shutdown_requested()
+                    && total_duration
+                        .checked_sub(program_start_time.elapsed())
+                        .is_some()
+                    && records_remaining > 0
+                {
+                    output.write_record(tera, context)?;
+                    records_remaining -= 1;
+                }
+                Ok(())
+            }
+        },
+        FeedArgs {
             batch_size: Some(batch_size),
             batch_interval: Some(batch_interval),
             record_limit: total_records,
             time_limit: total_duration,
+            ..
         } => {
-            tera.add_template_file(file, Some("template"))?;
             let batch_interval: core::time::Duration = batch_interval.into();
 
             match (total_records, total_duration) {
                 (None, None) => {
-                    loop {
+                    while !shutdown_requested() {
                         let loop_start_time: Instant = Instant::now();
                         // render a batch
                         for _ in 0..batch_size {
-                            tera.render_to("template", &context, std::io::stdout())?;
+                            output.write_record(tera, context)?;
                         }
                         // sleep off the time left
                         if let Some(time_remaining) =
                             batch_interval.checked_sub(loop_start_time.elapsed())
                         {
-                            std::thread::sleep(time_remaining);
+                            output.sleep(time_remaining)?;
                         }
                     }
+                    Ok(())
                 }
                 (Some(total_records), None) => {
                     let mut remaining_records: u32 = total_records;
 
                     // produce until we've hit our record limit
-                    while remaining_records > 0u32 {
+                    while !shutdown_requested() && remaining_records > 0u32 {
                         let loop_start_time: Instant = Instant::now();
 
                         let current_batch_size: u32 = if remaining_records > batch_size {
@@ -173,7 +1174,7 @@ fn render_template(tera: &mut Tera, cli_args: CliArgs) -> anyhow::Result<()> {
                         };
                         // render a batch
                         for _ in 0..current_batch_size {
-                            tera.render_to("template", &context, std::io::stdout())?;
+                            output.write_record(tera, context)?;
                         }
 
                         remaining_records -= current_batch_size;
@@ -181,7 +1182,7 @@ fn render_template(tera: &mut Tera, cli_args: CliArgs) -> anyhow::Result<()> {
                         if let Some(time_remaining) =
                             batch_interval.checked_sub(loop_start_time.elapsed())
                         {
-                            std::thread::sleep(time_remaining);
+                            output.sleep(time_remaining)?;
                         }
                     }
                     Ok(())
@@ -191,20 +1192,21 @@ fn render_template(tera: &mut Tera, cli_args: CliArgs) -> anyhow::Result<()> {
                     let program_start_time: Instant = Instant::now();
 
                     // produce until we've hit our time limit
-                    while total_duration
-                        .checked_sub(program_start_time.elapsed())
-                        .is_some()
+                    while !shutdown_requested()
+                        && total_duration
+                            .checked_sub(program_start_time.elapsed())
+                            .is_some()
                     {
                         let loop_start_time: Instant = Instant::now();
                         // render a batch
                         for _ in 0..batch_size {
-                            tera.render_to("template", &context, std::io::stdout())?;
+                            output.write_record(tera, context)?;
                         }
                         // sleep off the time left
                         if let Some(time_remaining) =
                             batch_interval.checked_sub(loop_start_time.elapsed())
                         {
-                            std::thread::sleep(time_remaining);
+                            output.sleep(time_remaining)?;
                         }
                     }
                     Ok(())
@@ -216,7 +1218,8 @@ fn render_template(tera: &mut Tera, cli_args: CliArgs) -> anyhow::Result<()> {
 
                     // produce until we've hit our record limit or our time limit,
                     // whichever comes first
-                    while records_remaining > 0u32
+                    while !shutdown_requested()
+                        && records_remaining > 0u32
                         && total_duration
                             .checked_sub(program_start_time.elapsed())
                             .is_some()
@@ -230,7 +1233,7 @@ fn render_template(tera: &mut Tera, cli_args: CliArgs) -> anyhow::Result<()> {
                         };
                         // render a batch
                         for _ in 0..current_batch_size {
-                            tera.render_to("template", &context, std::io::stdout())?;
+                            output.write_record(tera, context)?;
                         }
 
                         records_remaining -= current_batch_size;
@@ -238,7 +1241,7 @@ fn render_template(tera: &mut Tera, cli_args: CliArgs) -> anyhow::Result<()> {
                         if let Some(time_remaining) =
                             batch_interval.checked_sub(loop_start_time.elapsed())
                         {
-                            std::thread::sleep(time_remaining);
+                            output.sleep(time_remaining)?;
                         }
                     }
                     Ok(())
@@ -248,3 +1251,82 @@ fn render_template(tera: &mut Tera, cli_args: CliArgs) -> anyhow::Result<()> {
         _ => Err(TeraRandCliError::InvalidBatchArguments.into()),
     }
 }
+
+/// Render the template at a steady `rate` of records per second. Each record is due one interval
+/// after the previous one was due, rather than after it was written, so that the time spent
+/// rendering does not slow down the rate.
+fn render_at_rate(
+    tera: &Tera,
+    context: &Context,
+    output: &mut RecordOutput,
+    rate: u32,
+    total_records: Option<u32>,
+    total_duration: Option<Duration>,
+) -> anyhow::Result<()> {
+    let record_interval: core::time::Duration = core::time::Duration::from_secs(1) / rate;
+    let mut next_record_time: Instant = Instant::now();
+
+    match (total_records, total_duration) {
+        (None, None) => {
+            while !shutdown_requested() {
+                output.write_record(tera, context)?;
+                next_record_time += record_interval;
+                output.sleep_until(next_record_time)?;
+            }
+            Ok(())
+        }
+        (Some(total_records), None) => {
+            for record_num in 0..total_records {
+                if shutdown_requested() {
+                    break;
+                }
+                // wait for the next record, but not after the last one
+                if record_num > 0 {
+                    output.sleep_until(next_record_time)?;
+                }
+                output.write_record(tera, context)?;
+                next_record_time += record_interval;
+            }
+            Ok(())
+        }
+        (None, Some(total_duration)) => {
+            let total_duration: core::time::Duration = total_duration.into();
+            let program_start_time: Instant = Instant::now();
+
+            // produce until we've hit our time limit
+            while !shutdown_requested()
+                && total_duration
+                    .checked_sub(program_start_time.elapsed())
+                    .is_some()
+            {
+                output.write_record(tera, context)?;
+                next_record_time += record_interval;
+                output.sleep_until(next_record_time)?;
+            }
+            Ok(())
+        }
+        (Some(total_records), Some(total_duration)) => {
+            let total_duration: core::time::Duration = total_duration.into();
+            let program_start_time: Instant = Instant::now();
+            let mut records_remaining: u32 = total_records;
+
+            // produce until we've hit our record limit or our time limit,
+            // whichever comes first
+            while !shutdown_requested()
+                && records_remaining > 0u32
+                && total_duration
+                    .checked_sub(program_start_time.elapsed())
+                    .is_some()
+            {
+                output.write_record(tera, context)?;
+                records_remaining -= 1;
+                next_record_time += record_interval;
+                // wait for the next record, but not after the last one
+                if records_remaining > 0u32 {
+                    output.sleep_until(next_record_time)?;
+                }
+            }
+            Ok(())
+        }
+    }
+}
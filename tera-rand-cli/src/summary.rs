@@ -0,0 +1,59 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
+use tera::{Tera, Value};
+use tera_rand::random_choice;
+
+/// The number of times each value was produced, by the name of the value pool it was sampled from.
+pub(crate) type ChoiceTally = Arc<Mutex<BTreeMap<String, BTreeMap<String, u64>>>>;
+
+/// Register `random_choice` on `tera`, replacing any function of the same name, such that every
+/// value produced by a call with a `name` is counted under that name. Calls without a `name` are
+/// not tracked.
+///
+/// Returns the tally, which grows as the template is rendered.
+pub(crate) fn register_tallying_random_choice(tera: &mut Tera) -> ChoiceTally {
+    let tally: ChoiceTally = Arc::new(Mutex::new(BTreeMap::new()));
+    let function_tally: ChoiceTally = tally.clone();
+
+    tera.register_function("random_choice", move |args: &HashMap<String, Value>| {
+        let value: Value = random_choice(args)?;
+        if let Some(name) = args.get("name").and_then(Value::as_str) {
+            // count strings by their contents, rather than by their quoted JSON representation
+            let value_key: String = match &value {
+                Value::String(value) => value.clone(),
+                value => value.to_string(),
+            };
+            let mut tally = function_tally
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            *tally
+                .entry(name.to_string())
+                .or_default()
+                .entry(value_key)
+                .or_default() += 1;
+        }
+        Ok(value)
+    });
+    tally
+}
+
+/// Print the frequency of each value in `tally` to standard error, from the most to the least
+/// frequent.
+pub(crate) fn print_choice_summary(tally: &ChoiceTally) {
+    let tally = tally
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    for (name, value_counts) in tally.iter() {
+        let total: u64 = value_counts.values().sum();
+        eprintln!("random_choice(name=\"{name}\") produced {total} values:");
+
+        let mut value_counts: Vec<(&String, &u64)> = value_counts.iter().collect();
+        value_counts.sort_by(|(value_a, count_a), (value_b, count_b)| {
+            count_b.cmp(count_a).then_with(|| value_a.cmp(value_b))
+        });
+        for (value, count) in value_counts {
+            let percentage: f64 = *count as f64 / total as f64 * 100.0;
+            eprintln!("  {value}: {count} ({percentage:.1}%)");
+        }
+    }
+}
@@ -1,7 +1,11 @@
+use assert_cmd::cargo::CommandCargoExt;
 use assert_cmd::output::OutputError;
 use assert_cmd::Command;
 use regex::Regex;
-use std::process::Output;
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader};
+use std::process::{Child, Command as StdCommand, Output, Stdio};
+use std::time::Instant;
 use tracing::trace;
 use tracing_test::traced_test;
 
@@ -9,6 +13,7 @@ use tracing_test::traced_test;
 #[traced_test]
 fn test_simple_output_with_record_limit() {
     let mut cmd: Command = Command::cargo_bin("tera-rand-cli").unwrap();
+    #[allow(clippy::needless_borrows_for_generic_args)]
     cmd.args(&["-f", "resources/test/cpu_util.json", "--record-limit", "1"]);
 
     let output: Output = cmd.unwrap();
@@ -30,13 +35,52 @@ fn test_error_when_file_not_passed_in() {
     let stderr: String = String::from_utf8(output.stderr.clone()).unwrap();
     trace!(stderr);
 
-    assert!(stderr.contains("the following required arguments were not provided:\n  --file <FILE>"));
+    assert!(stderr.contains("Exactly one of `file` and `template` should be included."));
+}
+
+#[test]
+#[traced_test]
+fn test_inline_template() {
+    let mut cmd: Command = Command::cargo_bin("tera-rand-cli").unwrap();
+    cmd.args([
+        "--template",
+        r#"{"x": {{ random_uint32() }}}"#,
+        "--record-limit",
+        "1",
+    ]);
+
+    let output: Output = cmd.unwrap();
+    let stdout: String = String::from_utf8(output.stdout).unwrap();
+    trace!(stdout);
+
+    let expected_regex: Regex = Regex::new(r#"^\{"x": \d+}$"#).unwrap();
+    assert!(expected_regex.is_match(stdout.as_str()));
+}
+
+#[test]
+#[traced_test]
+fn test_error_when_both_file_and_template_passed_in() {
+    let mut cmd: Command = Command::cargo_bin("tera-rand-cli").unwrap();
+    cmd.args([
+        "-f",
+        "resources/test/cpu_util.json",
+        "--template",
+        r#"{"x": {{ random_uint32() }}}"#,
+    ]);
+
+    let output_error: OutputError = cmd.unwrap_err();
+    let output: &Output = output_error.as_output().unwrap();
+    let stderr: String = String::from_utf8(output.stderr.clone()).unwrap();
+    trace!(stderr);
+
+    assert!(stderr.contains("Exactly one of `file` and `template` should be included."));
 }
 
 #[test]
 #[traced_test]
 fn test_error_when_file_does_not_exist() {
     let mut cmd: Command = Command::cargo_bin("tera-rand-cli").unwrap();
+    #[allow(clippy::needless_borrows_for_generic_args)]
     cmd.args(&["-f", "this-file-does-not-exist.json"]);
 
     let output_error: OutputError = cmd.unwrap_err();
@@ -46,3 +90,1090 @@ fn test_error_when_file_does_not_exist() {
 
     assert!(stderr.contains("Couldn't open template '\"this-file-does-not-exist.json\""));
 }
+
+fn run_with_seed_env(seed_env: &str, extra_args: &[&str]) -> String {
+    let mut cmd: Command = Command::cargo_bin("tera-rand-cli").unwrap();
+    cmd.env("TERA_RAND_SEED", seed_env)
+        .args(["-f", "resources/test/cpu_util.json", "--record-limit", "5"])
+        .args(extra_args);
+
+    let output: Output = cmd.unwrap();
+    let stdout: String = String::from_utf8(output.stdout).unwrap();
+    trace!(stdout);
+    stdout
+}
+
+#[test]
+#[traced_test]
+fn test_seed_from_env_var_is_reproducible() {
+    let first_stdout: String = run_with_seed_env("42", &[]);
+    let second_stdout: String = run_with_seed_env("42", &[]);
+
+    assert_eq!(first_stdout, second_stdout);
+}
+
+#[test]
+#[traced_test]
+fn test_seed_flag_takes_precedence_over_env_var() {
+    let env_only_stdout: String = run_with_seed_env("42", &[]);
+    let flag_stdout: String = run_with_seed_env("42", &["--seed", "7"]);
+    let other_env_stdout: String = run_with_seed_env("7", &[]);
+
+    assert_ne!(env_only_stdout, flag_stdout);
+    assert_eq!(flag_stdout, other_env_stdout);
+}
+
+#[test]
+#[traced_test]
+fn test_values_stdin() {
+    let mut cmd: Command = Command::cargo_bin("tera-rand-cli").unwrap();
+    cmd.args([
+        "-f",
+        "resources/test/value_pool.json",
+        "--record-limit",
+        "20",
+        "--values-stdin",
+        "hosts",
+    ])
+    .write_stdin("alpha\nbeta\n\ngamma\n");
+
+    let output: Output = cmd.unwrap();
+    let stdout: String = String::from_utf8(output.stdout).unwrap();
+    trace!(stdout);
+
+    let expected_regex: Regex = Regex::new(r#"^\{"host": "(alpha|beta|gamma)"}$"#).unwrap();
+    assert_eq!(stdout.lines().count(), 20);
+    assert!(stdout.lines().all(|line| expected_regex.is_match(line)));
+}
+
+#[test]
+#[traced_test]
+fn test_file_cache_bytes() {
+    let mut cmd: Command = Command::cargo_bin("tera-rand-cli").unwrap();
+    cmd.args([
+        "-f",
+        "resources/test/protocol.json",
+        "--record-limit",
+        "5",
+        "--file-cache-bytes",
+        "1",
+    ]);
+
+    let output: Output = cmd.unwrap();
+    let stdout: String = String::from_utf8(output.stdout).unwrap();
+    trace!(stdout);
+
+    let expected_regex: Regex = Regex::new(r#"^\{"protocol": "(TCP|UDP)"}$"#).unwrap();
+    assert_eq!(stdout.lines().count(), 5);
+    assert!(stdout.lines().all(|line| expected_regex.is_match(line)));
+}
+
+#[test]
+#[traced_test]
+fn test_file_cache_ttl_secs() {
+    let mut cmd: Command = Command::cargo_bin("tera-rand-cli").unwrap();
+    cmd.args([
+        "-f",
+        "resources/test/protocol.json",
+        "--record-limit",
+        "5",
+        "--file-cache-ttl-secs",
+        "0",
+    ]);
+
+    let output: Output = cmd.unwrap();
+    let stdout: String = String::from_utf8(output.stdout).unwrap();
+    trace!(stdout);
+
+    let expected_regex: Regex = Regex::new(r#"^\{"protocol": "(TCP|UDP)"}$"#).unwrap();
+    assert_eq!(stdout.lines().count(), 5);
+    assert!(stdout.lines().all(|line| expected_regex.is_match(line)));
+}
+
+#[test]
+#[traced_test]
+#[cfg(feature = "uuid")]
+fn test_random_uuid_with_uuid_feature() {
+    let mut cmd: Command = Command::cargo_bin("tera-rand-cli").unwrap();
+    cmd.args(["-f", "resources/test/uuid.json", "--record-limit", "1"]);
+
+    let output: Output = cmd.unwrap();
+    let stdout: String = String::from_utf8(output.stdout).unwrap();
+    trace!(stdout);
+
+    let expected_regex: Regex =
+        Regex::new(r#"\{"id": "[\da-f]{8}-([\da-f]{4}-){3}[\da-f]{12}"}"#).unwrap();
+    assert!(expected_regex.is_match(stdout.as_str()));
+}
+
+#[test]
+#[traced_test]
+#[cfg(not(feature = "uuid"))]
+fn test_error_when_random_uuid_used_without_uuid_feature() {
+    let mut cmd: Command = Command::cargo_bin("tera-rand-cli").unwrap();
+    cmd.args(["-f", "resources/test/uuid.json", "--record-limit", "1"]);
+
+    let output_error: OutputError = cmd.unwrap_err();
+    let output: &Output = output_error.as_output().unwrap();
+    let stderr: String = String::from_utf8(output.stderr.clone()).unwrap();
+    trace!(stderr);
+
+    assert!(stderr.contains("`random_uuid` requires the `uuid` feature, which is not enabled"));
+}
+
+#[test]
+#[traced_test]
+fn test_feed_subcommand_matches_default() {
+    let run = |subcommand_args: &[&str]| {
+        let mut cmd: Command = Command::cargo_bin("tera-rand-cli").unwrap();
+        cmd.args(subcommand_args).args([
+            "-f",
+            "resources/test/cpu_util.json",
+            "--record-limit",
+            "3",
+            "--seed",
+            "11",
+        ]);
+
+        let output: Output = cmd.unwrap();
+        let stdout: String = String::from_utf8(output.stdout).unwrap();
+        trace!(stdout);
+        stdout
+    };
+
+    let feed_stdout: String = run(&["feed"]);
+    let default_stdout: String = run(&[]);
+
+    let expected_regex: Regex =
+        Regex::new(r#"^\{"hostname": "[\w\d]{8}", "cpu_util": \d+}$"#).unwrap();
+    assert_eq!(feed_stdout.lines().count(), 3);
+    assert!(feed_stdout
+        .lines()
+        .all(|line| expected_regex.is_match(line)));
+    assert_eq!(feed_stdout, default_stdout);
+}
+
+fn run_shuffle(seed: &str) -> String {
+    let mut cmd: Command = Command::cargo_bin("tera-rand-cli").unwrap();
+    cmd.args([
+        "shuffle",
+        "-f",
+        "resources/test/letters.txt",
+        "--seed",
+        seed,
+    ]);
+
+    let output: Output = cmd.unwrap();
+    let stdout: String = String::from_utf8(output.stdout).unwrap();
+    trace!(stdout);
+    stdout
+}
+
+#[test]
+#[traced_test]
+fn test_shuffle_subcommand() {
+    let shuffled_stdout: String = run_shuffle("3");
+
+    let mut shuffled_lines: Vec<&str> = shuffled_stdout.lines().collect();
+    shuffled_lines.sort();
+    assert_eq!(
+        shuffled_lines,
+        ["a", "b", "c", "d", "e", "f", "g", "h", "i", "j"]
+    );
+    assert_eq!(shuffled_stdout, run_shuffle("3"));
+}
+
+#[test]
+#[traced_test]
+fn test_check_subcommand() {
+    let mut cmd: Command = Command::cargo_bin("tera-rand-cli").unwrap();
+    cmd.args(["check", "-f", "resources/test/cpu_util.json"]);
+
+    let output: Output = cmd.unwrap();
+    let stdout: String = String::from_utf8(output.stdout).unwrap();
+    trace!(stdout);
+
+    assert_eq!(
+        stdout,
+        "resources/test/cpu_util.json rendered successfully\n"
+    );
+}
+
+#[test]
+#[traced_test]
+fn test_check_subcommand_with_invalid_template() {
+    let mut cmd: Command = Command::cargo_bin("tera-rand-cli").unwrap();
+    cmd.args(["check", "-f", "resources/test/invalid_range.json"]);
+
+    let output_error: OutputError = cmd.unwrap_err();
+    let output: &Output = output_error.as_output().unwrap();
+    let stdout: String = String::from_utf8(output.stdout.clone()).unwrap();
+    let stderr: String = String::from_utf8(output.stderr.clone()).unwrap();
+    trace!(stderr);
+
+    assert!(stdout.is_empty());
+    assert!(stderr.contains("The range from 2 to 1 is empty"));
+}
+
+#[test]
+#[traced_test]
+fn test_ensure_all_covers_every_value_within_record_limit() {
+    let mut cmd: Command = Command::cargo_bin("tera-rand-cli").unwrap();
+    cmd.args([
+        "-f",
+        "resources/test/ensure_all.json",
+        "--record-limit",
+        "8",
+    ]);
+
+    let output: Output = cmd.unwrap();
+    let stdout: String = String::from_utf8(output.stdout).unwrap();
+    trace!(stdout);
+
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 8);
+    for round in lines.chunks(4) {
+        let mut round: Vec<&str> = round.to_vec();
+        round.sort();
+        assert_eq!(
+            round,
+            [
+                r#"{"region": "ap-south"}"#,
+                r#"{"region": "eu-central"}"#,
+                r#"{"region": "us-east"}"#,
+                r#"{"region": "us-west"}"#,
+            ]
+        );
+    }
+}
+
+#[test]
+#[traced_test]
+fn test_measure_only_reports_records_without_output() {
+    let mut cmd: Command = Command::cargo_bin("tera-rand-cli").unwrap();
+    cmd.args([
+        "-f",
+        "resources/test/cpu_util.json",
+        "--record-limit",
+        "250",
+        "--measure-only",
+    ]);
+
+    let output: Output = cmd.unwrap();
+    let stdout: String = String::from_utf8(output.stdout).unwrap();
+    let stderr: String = String::from_utf8(output.stderr).unwrap();
+    trace!(stderr);
+
+    let summary_regex: Regex =
+        Regex::new(r"^rendered 250 records in \d+\.\d{3}s \((\d+\.\d) records/sec\)\n$").unwrap();
+    let records_per_sec: f64 = summary_regex.captures(&stderr).unwrap()[1]
+        .parse::<f64>()
+        .unwrap();
+    assert!(stdout.is_empty());
+    assert!(records_per_sec > 0.0);
+}
+
+#[test]
+#[traced_test]
+fn test_timestamp_prefix() {
+    let mut cmd: Command = Command::cargo_bin("tera-rand-cli").unwrap();
+    cmd.args([
+        "-f",
+        "resources/test/cpu_util.json",
+        "--record-limit",
+        "3",
+        "--timestamp-prefix",
+        "%Y-%m-%dT%H:%M:%S%.3f",
+    ]);
+
+    let output: Output = cmd.unwrap();
+    let stdout: String = String::from_utf8(output.stdout).unwrap();
+    trace!(stdout);
+
+    let expected_regex: Regex = Regex::new(
+        r#"^\d{4}-\d{2}-\d{2}T\d{2}:\d{2}:\d{2}\.\d{3} \{"hostname": "[\w\d]{8}", "cpu_util": \d+}\r?$"#,
+    )
+    .unwrap();
+    assert_eq!(stdout.lines().count(), 3);
+    assert!(stdout.lines().all(|line| expected_regex.is_match(line)));
+}
+
+#[test]
+#[traced_test]
+fn test_error_with_invalid_timestamp_prefix() {
+    let mut cmd: Command = Command::cargo_bin("tera-rand-cli").unwrap();
+    cmd.args([
+        "-f",
+        "resources/test/cpu_util.json",
+        "--record-limit",
+        "1",
+        "--timestamp-prefix",
+        "%Q",
+    ]);
+
+    let output_error: OutputError = cmd.unwrap_err();
+    let output: &Output = output_error.as_output().unwrap();
+    let stderr: String = String::from_utf8(output.stderr.clone()).unwrap();
+    trace!(stderr);
+
+    assert!(stderr.contains("`%Q` is not a valid timestamp format"));
+}
+
+#[test]
+#[traced_test]
+fn test_generator_used_as_filter() {
+    let mut cmd: Command = Command::cargo_bin("tera-rand-cli").unwrap();
+    cmd.args(["-f", "resources/test/filter.json", "--record-limit", "5"]);
+
+    let output: Output = cmd.unwrap();
+    let stdout: String = String::from_utf8(output.stdout).unwrap();
+    trace!(stdout);
+
+    let expected_regex: Regex = Regex::new(r#"^\{"code": "[A-Z\d]{6}"}$"#).unwrap();
+    assert_eq!(stdout.lines().count(), 5);
+    assert!(stdout.lines().all(|line| expected_regex.is_match(line)));
+}
+
+#[test]
+#[traced_test]
+fn test_check_subcommand_with_lint_reports_every_issue() {
+    let mut cmd: Command = Command::cargo_bin("tera-rand-cli").unwrap();
+    cmd.args(["check", "--lint", "-f", "resources/test/lint_mistakes.json"]);
+
+    let output_error: OutputError = cmd.unwrap_err();
+    let output: &Output = output_error.as_output().unwrap();
+    let stderr: String = String::from_utf8(output.stderr.clone()).unwrap();
+    trace!(stderr);
+
+    let issue_lines: Vec<&str> = stderr
+        .lines()
+        .filter(|line| line.starts_with("resources/test/lint_mistakes.json: "))
+        .collect();
+    assert_eq!(issue_lines.len(), 5);
+    assert!(issue_lines[0].contains("`random_string` has no parameter `lenght`"));
+    assert!(issue_lines[1].contains("`random_uint32` returned an error"));
+    assert!(issue_lines[1].contains("The range from 10 to 1 is empty"));
+    assert!(issue_lines[2].contains("`random_bool` has no parameter `fair`"));
+    assert!(issue_lines[3].contains("`random_from_file` returned an error"));
+    assert!(issue_lines[4].contains("`random_hex` has no parameter `lenght`"));
+    assert!(stderr.contains("Found 5 issue(s) in the template"));
+}
+
+#[test]
+#[traced_test]
+fn test_check_subcommand_with_lint_and_valid_template() {
+    let mut cmd: Command = Command::cargo_bin("tera-rand-cli").unwrap();
+    cmd.args(["check", "--lint", "-f", "resources/test/cpu_util.json"]);
+
+    let output: Output = cmd.unwrap();
+    let stdout: String = String::from_utf8(output.stdout).unwrap();
+    trace!(stdout);
+
+    assert_eq!(
+        stdout,
+        "resources/test/cpu_util.json rendered successfully\n"
+    );
+}
+
+#[test]
+#[traced_test]
+fn test_anomalies_appear_at_configured_rate() {
+    let mut cmd: Command = Command::cargo_bin("tera-rand-cli").unwrap();
+    cmd.args([
+        "-f",
+        "resources/test/cpu_util.json",
+        "--record-limit",
+        "2000",
+        "--anomaly-rate",
+        "0.2",
+        "--anomaly-file",
+        "resources/test/anomaly.json",
+    ]);
+
+    let output: Output = cmd.unwrap();
+    let stdout: String = String::from_utf8(output.stdout).unwrap();
+
+    let anomaly_count: usize = stdout
+        .lines()
+        .filter(|line| *line == r#"{"hostname": null, "cpu_util": -1"#)
+        .count();
+    trace!(anomaly_count);
+    assert_eq!(stdout.lines().count(), 2000);
+    // the expected count is 400 with a standard deviation of roughly 18
+    assert!((300..=500).contains(&anomaly_count));
+}
+
+#[test]
+#[traced_test]
+fn test_error_with_anomaly_rate_out_of_bounds() {
+    let mut cmd: Command = Command::cargo_bin("tera-rand-cli").unwrap();
+    cmd.args([
+        "-f",
+        "resources/test/cpu_util.json",
+        "--record-limit",
+        "1",
+        "--anomaly-rate",
+        "1.5",
+        "--anomaly-file",
+        "resources/test/anomaly.json",
+    ]);
+
+    let output_error: OutputError = cmd.unwrap_err();
+    let output: &Output = output_error.as_output().unwrap();
+    let stderr: String = String::from_utf8(output.stderr.clone()).unwrap();
+    trace!(stderr);
+
+    assert!(stderr.contains("`1.5` is not a valid anomaly rate"));
+}
+
+#[test]
+#[traced_test]
+fn test_summary_counts_match_output() {
+    let mut cmd: Command = Command::cargo_bin("tera-rand-cli").unwrap();
+    cmd.args([
+        "-f",
+        "resources/test/value_pool.json",
+        "--record-limit",
+        "30",
+        "--seed",
+        "7",
+        "--values-stdin",
+        "hosts",
+        "--summary",
+    ])
+    .write_stdin("alpha\nbeta\ngamma\n");
+
+    let output: Output = cmd.unwrap();
+    let stdout: String = String::from_utf8(output.stdout).unwrap();
+    let stderr: String = String::from_utf8(output.stderr).unwrap();
+    trace!(stderr);
+
+    let mut emitted_counts: HashMap<String, u64> = HashMap::new();
+    let host_regex: Regex = Regex::new(r#"^\{"host": "(\w+)"}$"#).unwrap();
+    for line in stdout.lines() {
+        let host: &str = host_regex.captures(line).unwrap().get(1).unwrap().as_str();
+        *emitted_counts.entry(host.to_string()).or_default() += 1;
+    }
+
+    let mut summary_lines = stderr.lines();
+    assert_eq!(
+        summary_lines.next(),
+        Some(r#"random_choice(name="hosts") produced 30 values:"#)
+    );
+    let count_regex: Regex = Regex::new(r"^  (\w+): (\d+) \(\d+\.\d%\)$").unwrap();
+    let mut summary_counts: HashMap<String, u64> = HashMap::new();
+    for line in summary_lines {
+        let captures = count_regex.captures(line).unwrap();
+        summary_counts.insert(captures[1].to_string(), captures[2].parse::<u64>().unwrap());
+    }
+    assert_eq!(summary_counts, emitted_counts);
+}
+
+#[test]
+#[traced_test]
+fn test_render_timeout() {
+    let mut cmd: Command = Command::cargo_bin("tera-rand-cli").unwrap();
+    cmd.args([
+        "-f",
+        "resources/test/cpu_util.json",
+        "--record-limit",
+        "5",
+        "--seed",
+        "3",
+        "--render-timeout",
+        "PT10S",
+    ]);
+    let output: Output = cmd.unwrap();
+    let stdout: String = String::from_utf8(output.stdout).unwrap();
+
+    let mut cmd: Command = Command::cargo_bin("tera-rand-cli").unwrap();
+    cmd.args([
+        "-f",
+        "resources/test/cpu_util.json",
+        "--record-limit",
+        "5",
+        "--seed",
+        "3",
+    ]);
+    let output_without_timeout: Output = cmd.unwrap();
+
+    // rendering on a separate thread still honors the seed
+    assert_eq!(stdout.lines().count(), 5);
+    assert_eq!(
+        stdout,
+        String::from_utf8(output_without_timeout.stdout).unwrap()
+    );
+}
+
+#[test]
+#[traced_test]
+fn test_error_with_render_exceeding_render_timeout() {
+    let mut cmd: Command = Command::cargo_bin("tera-rand-cli").unwrap();
+    cmd.args([
+        "-f",
+        "resources/test/slow.json",
+        "--record-limit",
+        "1",
+        "--render-timeout",
+        "PT0.1S",
+    ]);
+
+    let start_time: Instant = Instant::now();
+    let output_error: OutputError = cmd.unwrap_err();
+    let output: &Output = output_error.as_output().unwrap();
+    let stderr: String = String::from_utf8(output.stderr.clone()).unwrap();
+    trace!(stderr);
+
+    assert!(stderr.contains("Rendering a record exceeded the render timeout of 100ms"));
+    assert!(start_time.elapsed() < std::time::Duration::from_secs(5));
+}
+
+#[test]
+#[traced_test]
+fn test_random_once_is_scoped_to_each_record() {
+    let mut cmd: Command = Command::cargo_bin("tera-rand-cli").unwrap();
+    cmd.args(["-f", "resources/test/once.json", "--record-limit", "20"]);
+
+    let output: Output = cmd.unwrap();
+    let stdout: String = String::from_utf8(output.stdout).unwrap();
+    trace!(stdout);
+
+    let record_regex: Regex = Regex::new(r#"^\{"id": (\d+), "parent_id": (\d+)}$"#).unwrap();
+    let mut ids: Vec<&str> = Vec::new();
+    for line in stdout.lines() {
+        let captures = record_regex.captures(line).unwrap();
+        assert_eq!(
+            captures.get(1).unwrap().as_str(),
+            captures.get(2).unwrap().as_str()
+        );
+        ids.push(captures.get(1).unwrap().as_str());
+    }
+    ids.dedup();
+    assert!(ids.len() > 1);
+}
+
+#[test]
+#[traced_test]
+fn test_burst_size_and_burst_gap() {
+    let mut cmd: Command = Command::cargo_bin("tera-rand-cli").unwrap();
+    cmd.args([
+        "-f",
+        "resources/test/cpu_util.json",
+        "--record-limit",
+        "10",
+        "--burst-size",
+        "3",
+        "--burst-gap",
+        "PT0.2S",
+    ]);
+
+    let start_time: Instant = Instant::now();
+    let output: Output = cmd.unwrap();
+    let elapsed: std::time::Duration = start_time.elapsed();
+    let stdout: String = String::from_utf8(output.stdout).unwrap();
+    trace!(stdout);
+
+    // bursts of 3, 3, 3, and 1 records, with a pause between each pair of bursts
+    assert_eq!(stdout.lines().count(), 10);
+    assert!(elapsed >= std::time::Duration::from_millis(600));
+}
+
+#[test]
+#[traced_test]
+fn test_error_with_burst_size_and_batch_size() {
+    let mut cmd: Command = Command::cargo_bin("tera-rand-cli").unwrap();
+    cmd.args([
+        "-f",
+        "resources/test/cpu_util.json",
+        "--record-limit",
+        "10",
+        "--burst-size",
+        "3",
+        "--burst-gap",
+        "PT0.2S",
+        "--batch-size",
+        "3",
+        "--batch-interval",
+        "PT1S",
+    ]);
+
+    let output_error: OutputError = cmd.unwrap_err();
+    let output: &Output = output_error.as_output().unwrap();
+    let stderr: String = String::from_utf8(output.stderr.clone()).unwrap();
+    trace!(stderr);
+
+    assert!(stderr.contains("cannot be used with"));
+}
+
+#[test]
+#[traced_test]
+fn test_error_with_zero_burst_size() {
+    let mut cmd: Command = Command::cargo_bin("tera-rand-cli").unwrap();
+    cmd.args([
+        "-f",
+        "resources/test/cpu_util.json",
+        "--record-limit",
+        "5",
+        "--burst-size",
+        "0",
+        "--burst-gap",
+        "PT1S",
+    ]);
+    cmd.timeout(std::time::Duration::from_secs(10));
+
+    let output_error: OutputError = cmd.unwrap_err();
+    let output: &Output = output_error.as_output().unwrap();
+    let stderr: String = String::from_utf8(output.stderr.clone()).unwrap();
+    trace!(stderr);
+
+    assert!(stderr.contains("invalid value '0' for '--burst-size <BURST_SIZE>'"));
+}
+
+fn run_shard(shard_args: &[&str]) -> String {
+    let mut cmd: Command = Command::cargo_bin("tera-rand-cli").unwrap();
+    cmd.args([
+        "-f",
+        "resources/test/cpu_util.json",
+        "--record-limit",
+        "11",
+        "--seed",
+        "5",
+    ])
+    .args(shard_args);
+    String::from_utf8(cmd.unwrap().stdout).unwrap()
+}
+
+#[test]
+#[traced_test]
+fn test_shards_together_reproduce_unsharded_feed() {
+    let unsharded_stdout: String = run_shard(&[]);
+    let first_shard_stdout: String = run_shard(&["--shard", "0/2"]);
+    let second_shard_stdout: String = run_shard(&["--shard", "1/2"]);
+    trace!(unsharded_stdout);
+
+    let first_shard: Vec<&str> = first_shard_stdout.lines().collect();
+    let second_shard: Vec<&str> = second_shard_stdout.lines().collect();
+    assert_eq!(first_shard.len(), 6);
+    assert_eq!(second_shard.len(), 5);
+
+    // interleave the shards back into a single feed
+    let mut interleaved: Vec<&str> = Vec::new();
+    for (record_index, record) in first_shard.iter().enumerate() {
+        interleaved.push(record);
+        if let Some(record) = second_shard.get(record_index) {
+            interleaved.push(record);
+        }
+    }
+    assert_eq!(interleaved, unsharded_stdout.lines().collect::<Vec<&str>>());
+}
+
+#[test]
+#[traced_test]
+fn test_error_with_invalid_shard() {
+    for shard in ["2/2", "1", "a/b"] {
+        let mut cmd: Command = Command::cargo_bin("tera-rand-cli").unwrap();
+        cmd.args(["-f", "resources/test/cpu_util.json", "--shard", shard]);
+
+        let output_error: OutputError = cmd.unwrap_err();
+        let output: &Output = output_error.as_output().unwrap();
+        let stderr: String = String::from_utf8(output.stderr.clone()).unwrap();
+        trace!(stderr);
+
+        assert!(stderr.contains("is not a valid shard"));
+    }
+}
+
+// Spawn a feed which writes one record every 5 seconds, and return how long its first record
+// takes to appear on standard output.
+fn time_until_first_record(flush_args: &[&str]) -> std::time::Duration {
+    let mut child: Child = StdCommand::cargo_bin("tera-rand-cli")
+        .unwrap()
+        .args([
+            "-f",
+            "resources/test/cpu_util.json",
+            "--batch-size",
+            "1",
+            "--batch-interval",
+            "PT5S",
+            "--record-limit",
+            "2",
+        ])
+        .args(flush_args)
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    let start_time: Instant = Instant::now();
+    let mut first_record: String = String::new();
+    BufReader::new(child.stdout.take().unwrap())
+        .read_line(&mut first_record)
+        .unwrap();
+    let elapsed: std::time::Duration = start_time.elapsed();
+    child.kill().unwrap();
+    child.wait().unwrap();
+
+    assert!(first_record.starts_with(r#"{"hostname": "#));
+    elapsed
+}
+
+#[test]
+#[traced_test]
+fn test_flush_interval_flushes_while_waiting_between_batches() {
+    // without the interval, the first record would wait for 100 records to be buffered
+    let elapsed: std::time::Duration =
+        time_until_first_record(&["--flush-every", "100", "--flush-interval", "PT0.2S"]);
+    assert!(elapsed < std::time::Duration::from_secs(3));
+}
+
+#[test]
+#[traced_test]
+fn test_flush_every_record_by_default() {
+    let elapsed: std::time::Duration = time_until_first_record(&[]);
+    assert!(elapsed < std::time::Duration::from_secs(3));
+}
+
+#[test]
+#[traced_test]
+fn test_flush_every_buffers_records() {
+    let mut cmd: Command = Command::cargo_bin("tera-rand-cli").unwrap();
+    cmd.args([
+        "-f",
+        "resources/test/cpu_util.json",
+        "--record-limit",
+        "10",
+        "--flush-every",
+        "3",
+    ]);
+
+    // the records left over after the last full group of 3 are flushed when the program exits
+    let output: Output = cmd.unwrap();
+    let stdout: String = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(stdout.lines().count(), 10);
+}
+
+#[test]
+#[traced_test]
+fn test_error_with_zero_flush_every() {
+    let mut cmd: Command = Command::cargo_bin("tera-rand-cli").unwrap();
+    cmd.args(["-f", "resources/test/cpu_util.json", "--flush-every", "0"]);
+
+    let output_error: OutputError = cmd.unwrap_err();
+    let output: &Output = output_error.as_output().unwrap();
+    let stderr: String = String::from_utf8(output.stderr.clone()).unwrap();
+    trace!(stderr);
+
+    assert!(stderr.contains("--flush-every"));
+}
+
+fn run_with_output(output_path: &std::path::Path, extra_args: &[&str]) -> Output {
+    let mut cmd: Command = Command::cargo_bin("tera-rand-cli").unwrap();
+    cmd.args(["-f", "resources/test/cpu_util.json", "--record-limit", "3"])
+        .arg("--output")
+        .arg(output_path)
+        .args(extra_args);
+    cmd.unwrap()
+}
+
+#[test]
+#[traced_test]
+fn test_output_writes_records_to_file() {
+    let output_path: std::path::PathBuf = std::env::temp_dir().join(format!(
+        "tera-rand-cli-test-output-{}.jsonl",
+        std::process::id()
+    ));
+    std::fs::write(&output_path, "left over from a previous run\n").unwrap();
+
+    let output: Output = run_with_output(&output_path, &[]);
+    let contents: String = std::fs::read_to_string(&output_path).unwrap();
+    trace!(contents);
+
+    let expected_regex: Regex =
+        Regex::new(r#"^\{"hostname": "[\w\d]{8}", "cpu_util": \d+}\r?$"#).unwrap();
+    assert!(output.stdout.is_empty());
+    assert_eq!(contents.lines().count(), 3);
+    assert!(contents.lines().all(|line| expected_regex.is_match(line)));
+
+    // appending keeps the records which are already there
+    run_with_output(&output_path, &["--append"]);
+    let contents: String = std::fs::read_to_string(&output_path).unwrap();
+    std::fs::remove_file(&output_path).unwrap();
+    assert_eq!(contents.lines().count(), 6);
+    assert!(contents.lines().all(|line| expected_regex.is_match(line)));
+}
+
+#[test]
+#[traced_test]
+fn test_error_with_append_without_output() {
+    let mut cmd: Command = Command::cargo_bin("tera-rand-cli").unwrap();
+    cmd.args([
+        "-f",
+        "resources/test/cpu_util.json",
+        "--record-limit",
+        "1",
+        "--append",
+    ]);
+
+    let output_error: OutputError = cmd.unwrap_err();
+    let output: &Output = output_error.as_output().unwrap();
+    let stderr: String = String::from_utf8(output.stderr.clone()).unwrap();
+    trace!(stderr);
+
+    assert!(stderr.contains("--output <OUTPUT>"));
+}
+
+fn run_csv(extra_args: &[&str]) -> String {
+    let mut cmd: Command = Command::cargo_bin("tera-rand-cli").unwrap();
+    cmd.args(["-f", "resources/test/cpu_util.csv", "--format", "csv"])
+        .args(extra_args);
+
+    let output: Output = cmd.unwrap();
+    let stdout: String = String::from_utf8(output.stdout).unwrap();
+    trace!(stdout);
+    stdout
+}
+
+#[test]
+#[traced_test]
+fn test_csv_format_writes_header_once() {
+    let record_regex: Regex = Regex::new(r"^[\w\d]{8},\d+$").unwrap();
+    for batch_args in [
+        &["--record-limit", "5"][..],
+        &[
+            "--record-limit",
+            "5",
+            "--batch-size",
+            "2",
+            "--batch-interval",
+            "PT0.01S",
+        ],
+        &[
+            "--record-limit",
+            "5",
+            "--burst-size",
+            "2",
+            "--burst-gap",
+            "PT0.01S",
+        ],
+    ] {
+        let stdout: String =
+            run_csv(&[&["--csv-header", "hostname,cpu_util"], batch_args].concat());
+        let mut lines = stdout.lines();
+
+        assert_eq!(lines.next(), Some("hostname,cpu_util"));
+        let records: Vec<&str> = lines.collect();
+        assert_eq!(records.len(), 5);
+        assert!(records.iter().all(|record| record_regex.is_match(record)));
+    }
+}
+
+#[test]
+#[traced_test]
+fn test_csv_format_with_delimiter() {
+    let stdout: String = run_csv(&[
+        "--record-limit",
+        "3",
+        "--delimiter",
+        "\\t",
+        "--csv-header",
+        "hostname,cpu\tutil",
+    ]);
+    let mut lines = stdout.lines();
+
+    // a column name containing the delimiter is quoted
+    assert_eq!(lines.next(), Some("hostname\t\"cpu\tutil\""));
+    let record_regex: Regex = Regex::new(r"^[\w\d]{8}\t\d+$").unwrap();
+    assert!(lines.all(|record| record_regex.is_match(record)));
+}
+
+#[test]
+#[traced_test]
+fn test_error_with_csv_header_without_csv_format() {
+    let mut cmd: Command = Command::cargo_bin("tera-rand-cli").unwrap();
+    cmd.args([
+        "-f",
+        "resources/test/cpu_util.json",
+        "--record-limit",
+        "1",
+        "--csv-header",
+        "hostname,cpu_util",
+    ]);
+
+    let output_error: OutputError = cmd.unwrap_err();
+    let output: &Output = output_error.as_output().unwrap();
+    let stderr: String = String::from_utf8(output.stderr.clone()).unwrap();
+    trace!(stderr);
+
+    assert!(stderr.contains("can only be used when `format` is `csv`"));
+}
+
+#[test]
+#[traced_test]
+fn test_template_from_stdin() {
+    let template: String = std::fs::read_to_string("resources/test/cpu_util.json").unwrap();
+    let mut cmd: Command = Command::cargo_bin("tera-rand-cli").unwrap();
+    cmd.args(["-f", "-", "--record-limit", "1"])
+        .write_stdin(template);
+
+    let output: Output = cmd.unwrap();
+    let stdout: String = String::from_utf8(output.stdout).unwrap();
+    trace!(stdout);
+
+    let expected_regex: Regex =
+        Regex::new(r#"^\{"hostname": "[\w\d]{8}", "cpu_util": \d+}\r?$"#).unwrap();
+    assert_eq!(stdout.lines().count(), 1);
+    assert!(stdout.lines().all(|line| expected_regex.is_match(line)));
+}
+
+#[test]
+#[traced_test]
+fn test_check_subcommand_with_template_from_stdin() {
+    let mut cmd: Command = Command::cargo_bin("tera-rand-cli").unwrap();
+    cmd.args(["check", "-f", "-"])
+        .write_stdin("{{ random_uint32() }}");
+
+    let output: Output = cmd.unwrap();
+    let stdout: String = String::from_utf8(output.stdout).unwrap();
+    trace!(stdout);
+
+    assert_eq!(stdout, "- rendered successfully\n");
+}
+
+#[test]
+#[traced_test]
+fn test_error_with_template_and_values_from_stdin() {
+    let mut cmd: Command = Command::cargo_bin("tera-rand-cli").unwrap();
+    cmd.args(["-f", "-", "--record-limit", "1", "--values-stdin", "hosts"])
+        .write_stdin("alpha\n");
+
+    let output_error: OutputError = cmd.unwrap_err();
+    let output: &Output = output_error.as_output().unwrap();
+    let stderr: String = String::from_utf8(output.stderr.clone()).unwrap();
+    trace!(stderr);
+
+    assert!(stderr.contains("either the template or `values_stdin`, but not both"));
+}
+
+#[test]
+#[traced_test]
+fn test_rate() {
+    let mut cmd: Command = Command::cargo_bin("tera-rand-cli").unwrap();
+    cmd.args([
+        "-f",
+        "resources/test/cpu_util.json",
+        "--record-limit",
+        "11",
+        "--rate",
+        "20",
+    ]);
+
+    let start_time: Instant = Instant::now();
+    let output: Output = cmd.unwrap();
+    let elapsed: std::time::Duration = start_time.elapsed();
+    let stdout: String = String::from_utf8(output.stdout).unwrap();
+    trace!(stdout);
+
+    // 11 records at 20 per second are spread over 10 intervals of 50ms
+    assert_eq!(stdout.lines().count(), 11);
+    assert!(elapsed >= std::time::Duration::from_millis(500));
+    assert!(elapsed < std::time::Duration::from_secs(5));
+}
+
+#[test]
+#[traced_test]
+fn test_error_with_rate_and_batch_size() {
+    let mut cmd: Command = Command::cargo_bin("tera-rand-cli").unwrap();
+    cmd.args([
+        "-f",
+        "resources/test/cpu_util.json",
+        "--record-limit",
+        "1",
+        "--rate",
+        "20",
+        "--batch-size",
+        "5",
+        "--batch-interval",
+        "PT1S",
+    ]);
+
+    let output_error: OutputError = cmd.unwrap_err();
+    let output: &Output = output_error.as_output().unwrap();
+    let stderr: String = String::from_utf8(output.stderr.clone()).unwrap();
+    trace!(stderr);
+
+    assert!(stderr.contains("`rate` cannot be combined with `batch_size`"));
+}
+
+#[cfg(unix)]
+#[test]
+#[traced_test]
+fn test_sigint_flushes_and_exits_cleanly() {
+    let mut child: Child = StdCommand::cargo_bin("tera-rand-cli")
+        .unwrap()
+        .args([
+            "-f",
+            "resources/test/cpu_util.json",
+            "--batch-size",
+            "1",
+            "--batch-interval",
+            "PT10S",
+            "--stats",
+        ])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    let mut stdout_reader = BufReader::new(child.stdout.take().unwrap());
+    let mut first_record: String = String::new();
+    stdout_reader.read_line(&mut first_record).unwrap();
+
+    // interrupt the program while it waits between batches
+    let start_time: Instant = Instant::now();
+    let kill_status = StdCommand::new("kill")
+        .args(["-INT", &child.id().to_string()])
+        .status()
+        .unwrap();
+    assert!(kill_status.success());
+    let output: Output = child.wait_with_output().unwrap();
+    let stderr: String = String::from_utf8(output.stderr).unwrap();
+    trace!(stderr);
+
+    assert!(output.status.success());
+    assert!(start_time.elapsed() < std::time::Duration::from_secs(5));
+    assert!(first_record.starts_with(r#"{"hostname": "#));
+    assert!(stderr.contains("interrupted after writing 1 records"));
+    assert!(stderr.contains("generated 1 records in "));
+}
+
+#[test]
+#[traced_test]
+fn test_stats_are_written_to_stderr() {
+    let stats_regex: Regex =
+        Regex::new(r"^generated 5 records in \d+\.\d{2}s \(\d+/s\)\n$").unwrap();
+    for limit_args in [
+        &["--record-limit", "5"][..],
+        &["--record-limit", "5", "--time-limit", "PT10S"],
+        &[
+            "--record-limit",
+            "5",
+            "--batch-size",
+            "2",
+            "--batch-interval",
+            "PT0.01S",
+        ],
+        &["--record-limit", "5", "--rate", "1000"],
+    ] {
+        let mut cmd: Command = Command::cargo_bin("tera-rand-cli").unwrap();
+        cmd.args(["-f", "resources/test/cpu_util.json", "--stats"])
+            .args(limit_args);
+
+        let output: Output = cmd.unwrap();
+        let stdout: String = String::from_utf8(output.stdout).unwrap();
+        let stderr: String = String::from_utf8(output.stderr).unwrap();
+        trace!(stderr);
+
+        assert!(stats_regex.is_match(&stderr), "{stderr}");
+        assert_eq!(stdout.lines().count(), 5);
+        assert!(!stdout.contains("generated"));
+    }
+}
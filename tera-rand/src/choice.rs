@@ -0,0 +1,1062 @@
+use crate::common::{parse_arg, parse_one_or_many_arg, sample_indices};
+use crate::error::{
+    conflicting_args, invalid_weights, missing_arg, no_values_to_sample,
+    probabilities_do_not_sum_to_one, unable_to_coerce, unknown_value_pool, unsupported_arg,
+};
+use crate::rng::with_rng;
+use dashmap::DashMap;
+use lazy_static::lazy_static;
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::Rng;
+use serde::Deserialize;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use tera::{Result, Value};
+
+// How far the sum of `weights` may stray from 1.0 when `probabilities=true`.
+const PROBABILITY_SUM_EPSILON: f64 = 1e-9;
+
+// The type which `random_choice` converts a sampled value into.
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Coerce {
+    None,
+    Number,
+    Bool,
+}
+
+lazy_static! {
+    static ref VALUE_POOLS: DashMap<String, Vec<Value>> = DashMap::new();
+    // for `ensure_all=true`, the indices of the values which have not been sampled yet in the
+    // current round
+    static ref COVERAGE_BAGS: DashMap<CoverageKey, Vec<usize>> = DashMap::new();
+}
+
+// Identifies a round of `ensure_all=true` sampling: the values sampled from, and the values
+// excluded from them, since different exclusions have different candidates.
+#[derive(Debug, PartialEq, Eq, Hash)]
+struct CoverageKey {
+    source: CoverageSource,
+    except_hash: u64,
+}
+
+#[derive(Debug, PartialEq, Eq, Hash)]
+enum CoverageSource {
+    // a pool registered with `set_value_pool`
+    Pool(String),
+    // an array of values passed inline, which is hashed rather than stored so that the key is
+    // cheap to build on every render
+    Values { len: usize, hash: u64 },
+}
+
+/// Register a named pool of values which [`random_choice`] can sample from via its `name`
+/// parameter. Registering a pool under an existing name replaces the old pool.
+///
+/// This is useful when the values are only known at runtime, for example when they are read from
+/// another program's output.
+///
+/// # Example usage
+///
+/// ```edition2021
+/// use tera::{Context, Tera, Value};
+/// use tera_rand::{random_choice, set_value_pool};
+///
+/// set_value_pool("regions", vec![Value::from("us-east-1"), Value::from("eu-west-1")]);
+///
+/// let mut tera: Tera = Tera::default();
+/// tera.register_function("random_choice", random_choice);
+/// let context: Context = Context::new();
+///
+/// let rendered: String = tera
+///     .render_str(r#"{{ random_choice(name="regions") }}"#, &context)
+///     .unwrap();
+/// ```
+pub fn set_value_pool(name: impl Into<String>, values: Vec<Value>) {
+    let name: String = name.into();
+    COVERAGE_BAGS.retain(|coverage_key, _| {
+        !matches!(&coverage_key.source, CoverageSource::Pool(pool) if *pool == name)
+    });
+    VALUE_POOLS.insert(name, values);
+}
+
+/// Forget which values [`random_choice`] has already sampled with `ensure_all=true`, so that the
+/// next call starts a new round in which every value appears before any repeats.
+///
+/// Call this between independent runs, such as between two feeds rendered by the same process.
+pub fn reset_choice_coverage() {
+    COVERAGE_BAGS.clear();
+}
+
+/// A Tera function to sample a random value from an array of values. The array should be passed
+/// in as an argument to the `values` parameter. The sampled value keeps its type, so numbers stay
+/// numbers and objects stay objects.
+///
+/// Instead of `values`, the `name` parameter may be used to sample from a pool of values
+/// registered with [`set_value_pool`]. It is an error to pass both.
+///
+/// The optional `except` parameter takes either a single value or an array of values which should
+/// never be sampled. It is an error for `except` to exclude every value.
+///
+/// The optional `weights` parameter takes an array of non-negative numbers, one per value, to
+/// sample some values more often than others. By default, weights are relative: `[3, 1]` means the
+/// first value is sampled three times as often as the second. If `probabilities` is `true`, the
+/// weights are instead treated as probabilities, and it is an error for them not to sum to 1.0.
+/// This catches mistakes such as a forgotten or mistyped probability. It is an error to pass
+/// `probabilities=true` without `weights`.
+///
+/// The optional `coerce` parameter converts the sampled value into another type, which is useful
+/// when the values are strings that represent numbers or booleans, such as values read with
+/// `--values-stdin`. It takes `none` (the default), `number`, or `bool`. Strings are parsed, and
+/// values which already have the target type are returned as they are. It is an error for the
+/// sampled value to not be convertible.
+///
+/// If `ensure_all` is `true`, values are sampled without replacement until every value has been
+/// sampled once, and only then does a new round begin. So, within any run of N calls with the
+/// same `values` or `name`, where N is the number of values, every value appears exactly once.
+/// This guarantees full coverage of the values as long as a run renders at least N records.
+/// Progress is tracked per `name`, or per array of `values`, and lasts until
+/// [`reset_choice_coverage`] is called. `ensure_all` cannot be combined with `weights`.
+///
+/// # Example usage
+///
+/// ```edition2021
+/// use tera::{Context, Tera};
+/// use tera_rand::random_choice;
+///
+/// let mut tera: Tera = Tera::default();
+/// tera.register_function("random_choice", random_choice);
+/// let context: Context = Context::new();
+///
+/// let rendered: String = tera
+///     .render_str(r#"{{ random_choice(values=["GET", "POST", "PUT"]) }}"#, &context)
+///     .unwrap();
+/// // exclude a single value
+/// let rendered: String = tera
+///     .render_str(r#"{{ random_choice(values=[200, 404, 500], except=200) }}"#, &context)
+///     .unwrap();
+/// // exclude several values
+/// let rendered: String = tera
+///     .render_str(
+///         r#"{{ random_choice(values=[200, 404, 500], except=[200, 404]) }}"#,
+///         &context
+///     )
+///     .unwrap();
+/// // sample "GET" three times as often as "POST"
+/// let rendered: String = tera
+///     .render_str(r#"{{ random_choice(values=["GET", "POST"], weights=[3, 1]) }}"#, &context)
+///     .unwrap();
+/// // the same, but with the weights checked to sum to 1.0
+/// let rendered: String = tera
+///     .render_str(
+///         r#"{{ random_choice(values=["GET", "POST"], weights=[0.75, 0.25], probabilities=true) }}"#,
+///         &context
+///     )
+///     .unwrap();
+/// // render "200" as the number 200
+/// let rendered: String = tera
+///     .render_str(r#"{{ random_choice(values=["200", "404"], coerce="number") }}"#, &context)
+///     .unwrap();
+/// ```
+pub fn random_choice(args: &HashMap<String, Value>) -> Result<Value> {
+    let values_opt: Option<Vec<Value>> = parse_arg(args, "values")?;
+    let name_opt: Option<String> = parse_arg(args, "name")?;
+    let except: Vec<Value> = parse_one_or_many_arg(args, "except")?.unwrap_or_default();
+    let weights_opt: Option<Vec<f64>> = parse_arg(args, "weights")?;
+    let probabilities: bool = parse_arg(args, "probabilities")?.unwrap_or(false);
+    let coerce: Coerce = parse_arg(args, "coerce")?.unwrap_or(Coerce::None);
+
+    if probabilities {
+        let weights: &Vec<f64> = weights_opt.as_ref().ok_or_else(|| missing_arg("weights"))?;
+        check_probabilities_sum_to_one(weights)?;
+    }
+
+    let ensure_all: bool = parse_arg(args, "ensure_all")?.unwrap_or(false);
+    if ensure_all && weights_opt.is_some() {
+        return Err(conflicting_args("ensure_all", "weights"));
+    }
+
+    let sampled_value: Value = match (values_opt, name_opt) {
+        (Some(_), Some(_)) => Err(conflicting_args("values", "name")),
+        (Some(values), None) if ensure_all => {
+            let source: CoverageSource = CoverageSource::Values {
+                len: values.len(),
+                hash: hash_values(&values),
+            };
+            sample_value_covering_all(source, &values, &except)
+        }
+        (Some(values), None) => sample_value(&values, weights_opt.as_deref(), &except),
+        (None, Some(name)) => {
+            let values_ref = VALUE_POOLS
+                .get(&name)
+                .ok_or_else(|| unknown_value_pool(name.clone()))?;
+            if ensure_all {
+                let source: CoverageSource = CoverageSource::Pool(name.clone());
+                sample_value_covering_all(source, values_ref.value(), &except)
+            } else {
+                sample_value(values_ref.value(), weights_opt.as_deref(), &except)
+            }
+        }
+        (None, None) => Err(missing_arg("values")),
+    }?;
+    coerce_value(sampled_value, coerce)
+}
+
+/// A Tera function to sample a random subset of an array of values, without replacement. The
+/// array should be passed in as an argument to the `values` parameter, and the number of values to
+/// sample should be passed in as an argument to the `count` parameter. It is an error for `count`
+/// to be larger than the number of values.
+///
+/// By default, the returned array is in a random order. Pass `ordered=true` to instead keep the
+/// chosen values in the order they appear in `values`, for example to select some columns in
+/// their original order. Under a fixed seed (see [`set_seed`]), both the chosen values and their
+/// order are reproducible.
+///
+/// # Example usage
+///
+/// ```edition2021
+/// use tera::{Context, Tera};
+/// use tera_rand::random_subset;
+///
+/// let mut tera: Tera = Tera::default();
+/// tera.register_function("random_subset", random_subset);
+/// let context: Context = Context::new();
+///
+/// let rendered: String = tera
+///     .render_str(
+///         r#"{{ random_subset(values=["read", "write", "admin"], count=2) | join(sep=",") }}"#,
+///         &context
+///     )
+///     .unwrap();
+/// // keep the chosen columns in their original order
+/// let rendered: String = tera
+///     .render_str(
+///         r#"SELECT {{ random_subset(values=["id", "name", "email"], count=2, ordered=true) | join(sep=", ") }} FROM users"#,
+///         &context
+///     )
+///     .unwrap();
+/// ```
+///
+/// [`set_seed`]: crate::set_seed
+pub fn random_subset(args: &HashMap<String, Value>) -> Result<Value> {
+    let values_opt: Option<Vec<Value>> = parse_arg(args, "values")?;
+    let values: Vec<Value> = values_opt.ok_or_else(|| missing_arg("values"))?;
+    let count_opt: Option<usize> = parse_arg(args, "count")?;
+    let count: usize = count_opt.ok_or_else(|| missing_arg("count"))?;
+    let ordered: bool = parse_arg(args, "ordered")?.unwrap_or(false);
+
+    let mut indices: Vec<usize> = sample_indices(values.len(), count)?;
+    if ordered {
+        indices.sort_unstable();
+    }
+    let subset: Vec<Value> = indices
+        .into_iter()
+        .map(|index| values[index].clone())
+        .collect();
+    Ok(Value::Array(subset))
+}
+
+/// A Tera function to sample one of a small set of values with non-uniform probabilities, such as
+/// an HTTP method which is `GET` 80% of the time and `POST` 20% of the time.
+///
+/// The `choices` parameter takes an array of values, and the `weights` parameter takes an array of
+/// non-negative numbers, one per choice. Each choice is sampled in proportion to its weight, so
+/// `[8, 2]` and `[0.8, 0.2]` are equivalent. It is an error for `choices` to be empty, for the
+/// arrays to differ in length, or for every weight to be zero.
+///
+/// This is a shorthand for [`random_choice`] with its `values` and `weights` parameters, which also
+/// supports excluding values and checking that the weights are probabilities.
+///
+/// # Example usage
+///
+/// ```edition2021
+/// use tera::{Context, Tera};
+/// use tera_rand::random_weighted;
+///
+/// let mut tera: Tera = Tera::default();
+/// tera.register_function("random_weighted", random_weighted);
+/// let context: Context = Context::new();
+///
+/// let rendered: String = tera
+///     .render_str(
+///         r#"{{ random_weighted(choices=["GET", "POST"], weights=[80, 20]) }}"#,
+///         &context,
+///     )
+///     .unwrap();
+/// ```
+pub fn random_weighted(args: &HashMap<String, Value>) -> Result<Value> {
+    let choices_opt: Option<Vec<Value>> = parse_arg(args, "choices")?;
+    let choices: Vec<Value> = choices_opt.ok_or_else(|| missing_arg("choices"))?;
+    let weights_opt: Option<Vec<f64>> = parse_arg(args, "weights")?;
+    let weights: Vec<f64> = weights_opt.ok_or_else(|| missing_arg("weights"))?;
+    if choices.is_empty() {
+        return Err(no_values_to_sample("choices"));
+    }
+
+    sample_value(&choices, Some(&weights), &[])
+}
+
+// Convert `value` into the type requested by `coerce`.
+fn coerce_value(value: Value, coerce: Coerce) -> Result<Value> {
+    match (coerce, value) {
+        (Coerce::None, value) => Ok(value),
+        (Coerce::Number, Value::Number(number)) => Ok(Value::Number(number)),
+        (Coerce::Number, Value::String(string)) => {
+            let trimmed: &str = string.trim();
+            if let Ok(integer) = trimmed.parse::<i64>() {
+                return Ok(Value::from(integer));
+            }
+            if let Ok(integer) = trimmed.parse::<u64>() {
+                return Ok(Value::from(integer));
+            }
+            trimmed
+                .parse::<f64>()
+                .ok()
+                .and_then(tera::Number::from_f64)
+                .map(Value::Number)
+                .ok_or_else(|| unable_to_coerce(string, "number"))
+        }
+        (Coerce::Bool, Value::Bool(boolean)) => Ok(Value::Bool(boolean)),
+        (Coerce::Bool, Value::String(string)) => match string.trim() {
+            "true" => Ok(Value::Bool(true)),
+            "false" => Ok(Value::Bool(false)),
+            _ => Err(unable_to_coerce(string, "bool")),
+        },
+        (Coerce::Number, value) => Err(unable_to_coerce(value.to_string(), "number")),
+        (Coerce::Bool, value) => Err(unable_to_coerce(value.to_string(), "bool")),
+    }
+}
+
+fn check_probabilities_sum_to_one(probabilities: &[f64]) -> Result<()> {
+    let sum: f64 = probabilities.iter().sum();
+    if (sum - 1.0).abs() > PROBABILITY_SUM_EPSILON {
+        return Err(probabilities_do_not_sum_to_one(sum));
+    }
+    Ok(())
+}
+
+/// A Tera function to sample values that match a known distribution at a few quantiles, such as
+/// the p50, p90, and p99 of a latency profile. Every sampled value is one of the configured values.
+///
+/// The `quantiles` parameter takes either an array of quantiles between 0 and 1, such as
+/// `[0.5, 0.9, 0.99]`, along with a `values` array holding the value at each quantile, or an
+/// object mapping quantile labels to values, such as `{"p50": 12, "p90": 48, "p99": 120}`. Labels
+/// may be written as percentiles like `p99.9` or as quantiles like `0.999`. Since Tera templates
+/// cannot contain object literals, the object form is passed in through the context.
+///
+/// By default, each value is sampled with the probability between its quantile and the previous
+/// one, and the last value also takes the probability above the last quantile. This preserves the
+/// configured percentiles: for `[0.5, 0.9, 0.99]`, half of the samples are at most the p50 value
+/// and 90% are at most the p90 value. To choose the frequencies yourself, pass a `weights` array
+/// with one relative weight per quantile, in ascending order of quantile.
+///
+/// # Example usage
+///
+/// ```edition2021
+/// use tera::{Context, Tera};
+/// use tera_rand::random_quantile;
+///
+/// let mut tera: Tera = Tera::default();
+/// tera.register_function("random_quantile", random_quantile);
+/// let mut context: Context = Context::new();
+///
+/// let rendered: String = tera
+///     .render_str(
+///         "{{ random_quantile(quantiles=[0.5, 0.9, 0.99], values=[12.0, 48.5, 120.0]) }}",
+///         &context
+///     )
+///     .unwrap();
+/// // pass in a latency profile through the context
+/// context.insert(
+///     "latency_ms",
+///     &serde_json::json!({"p50": 12.0, "p90": 48.5, "p99": 120.0}),
+/// );
+/// let rendered: String = tera
+///     .render_str("{{ random_quantile(quantiles=latency_ms) }}", &context)
+///     .unwrap();
+/// ```
+pub fn random_quantile(args: &HashMap<String, Value>) -> Result<Value> {
+    let quantiles_value: Value = args
+        .get("quantiles")
+        .cloned()
+        .ok_or_else(|| missing_arg("quantiles"))?;
+    let weights_opt: Option<Vec<f64>> = parse_arg(args, "weights")?;
+
+    let mut quantile_values: Vec<(f64, Value)> = match quantiles_value {
+        Value::Object(quantile_map) => quantile_map
+            .into_iter()
+            .map(|(label, value)| Ok((parse_quantile_label(&label)?, value)))
+            .collect::<Result<Vec<(f64, Value)>>>()?,
+        quantiles_value => {
+            let quantiles: Vec<f64> = parse_arg(args, "quantiles")?.unwrap_or_default();
+            let values_opt: Option<Vec<Value>> = parse_arg(args, "values")?;
+            let values: Vec<Value> = values_opt.ok_or_else(|| missing_arg("values"))?;
+            if quantiles.len() != values.len() {
+                return Err(unsupported_arg(
+                    "values",
+                    format!("{} values for quantiles {}", values.len(), quantiles_value),
+                ));
+            }
+            quantiles.into_iter().zip(values).collect()
+        }
+    };
+    if quantile_values.is_empty() {
+        return Err(no_values_to_sample("quantiles"));
+    }
+
+    quantile_values.sort_by(|(first, _), (second, _)| first.total_cmp(second));
+    let quantiles: Vec<f64> = quantile_values
+        .iter()
+        .map(|(quantile, _)| *quantile)
+        .collect();
+    if let Some(quantile) = quantiles
+        .iter()
+        .find(|quantile| !(**quantile > 0.0 && **quantile <= 1.0))
+    {
+        return Err(unsupported_arg("quantiles", quantile.to_string()));
+    }
+    if quantiles.windows(2).any(|pair| pair[0] == pair[1]) {
+        return Err(unsupported_arg(
+            "quantiles",
+            String::from("the same quantile more than once"),
+        ));
+    }
+
+    let weights: Vec<f64> = match weights_opt {
+        Some(weights) => weights,
+        None => weights_from_quantile_gaps(&quantiles),
+    };
+    let values: Vec<Value> = quantile_values
+        .into_iter()
+        .map(|(_, value)| value)
+        .collect();
+    sample_value(&values, Some(&weights), &[])
+}
+
+// Parse a quantile label such as `p99.9` or `0.999` into a quantile between 0 and 1.
+fn parse_quantile_label(label: &str) -> Result<f64> {
+    let quantile_opt: Option<f64> = match label.strip_prefix('p') {
+        Some(percentile) => percentile
+            .parse::<f64>()
+            .ok()
+            .map(|percentile| percentile / 100.0),
+        None => label.parse::<f64>().ok(),
+    };
+    quantile_opt.ok_or_else(|| unsupported_arg("quantiles", label.to_string()))
+}
+
+// Weigh each quantile by its distance from the previous quantile, and give the last quantile the
+// remaining distance to 1 as well, so that the configured quantiles hold exactly.
+fn weights_from_quantile_gaps(sorted_quantiles: &[f64]) -> Vec<f64> {
+    let mut weights: Vec<f64> = Vec::with_capacity(sorted_quantiles.len());
+    let mut previous_quantile: f64 = 0.0;
+    for quantile in sorted_quantiles {
+        weights.push(quantile - previous_quantile);
+        previous_quantile = *quantile;
+    }
+    if let Some(last_weight) = weights.last_mut() {
+        *last_weight += 1.0 - previous_quantile;
+    }
+    weights
+}
+
+// Sample a value which is not in `except` without replacement: each candidate value is sampled
+// once before any value is sampled again. Progress is tracked in `COVERAGE_BAGS` under `source`
+// along with `except`, and the candidates are only found again when a new round begins.
+fn sample_value_covering_all(
+    source: CoverageSource,
+    values: &[Value],
+    except: &[Value],
+) -> Result<Value> {
+    let coverage_key: CoverageKey = CoverageKey {
+        source,
+        except_hash: hash_values(except),
+    };
+
+    let mut bag = COVERAGE_BAGS.entry(coverage_key).or_default();
+    if bag.is_empty() {
+        *bag = candidate_indices(values, except)?;
+    }
+    let bag_position: usize = with_rng(|rng| rng.gen_range(0usize..bag.len()));
+    let index_to_sample: usize = bag.swap_remove(bag_position);
+    Ok(values[index_to_sample].clone())
+}
+
+// Hash an array of values without serializing it, since `Value` does not implement `Hash`.
+fn hash_values(values: &[Value]) -> u64 {
+    let mut hasher: DefaultHasher = DefaultHasher::new();
+    values.len().hash(&mut hasher);
+    for value in values {
+        hash_value(value, &mut hasher);
+    }
+    hasher.finish()
+}
+
+fn hash_value<H: Hasher>(value: &Value, hasher: &mut H) {
+    match value {
+        Value::Null => 0u8.hash(hasher),
+        Value::Bool(boolean) => (1u8, boolean).hash(hasher),
+        Value::Number(number) => {
+            2u8.hash(hasher);
+            match (number.as_u64(), number.as_i64()) {
+                (Some(unsigned), _) => unsigned.hash(hasher),
+                (None, Some(signed)) => signed.hash(hasher),
+                (None, None) => number.as_f64().map(f64::to_bits).hash(hasher),
+            }
+        }
+        Value::String(string) => (3u8, string).hash(hasher),
+        Value::Array(array) => {
+            (4u8, array.len()).hash(hasher);
+            for element in array {
+                hash_value(element, hasher);
+            }
+        }
+        Value::Object(object) => {
+            (5u8, object.len()).hash(hasher);
+            for (key, element) in object {
+                key.hash(hasher);
+                hash_value(element, hasher);
+            }
+        }
+    }
+}
+
+// The indices of the values which are not in `except`.
+fn candidate_indices(values: &[Value], except: &[Value]) -> Result<Vec<usize>> {
+    if values.is_empty() {
+        return Err(no_values_to_sample("values"));
+    }
+
+    let candidate_indices: Vec<usize> = (0usize..values.len())
+        .filter(|index| !except.contains(&values[*index]))
+        .collect();
+    if candidate_indices.is_empty() {
+        return Err(no_values_to_sample("except"));
+    }
+    Ok(candidate_indices)
+}
+
+// Sample a value which is not in `except`. If `weights_opt` is provided, it must have one weight
+// per value, and values are sampled in proportion to their weights.
+fn sample_value(values: &[Value], weights_opt: Option<&[f64]>, except: &[Value]) -> Result<Value> {
+    let candidate_indices: Vec<usize> = candidate_indices(values, except)?;
+
+    let index_to_sample: usize = match weights_opt {
+        Some(weights) => {
+            if weights.len() != values.len() {
+                return Err(invalid_weights(format!(
+                    "expected {} weights, one per value, but found {}",
+                    values.len(),
+                    weights.len()
+                )));
+            }
+            let candidate_weights = candidate_indices.iter().map(|index| weights[*index]);
+            let weighted_index: WeightedIndex<f64> = WeightedIndex::new(candidate_weights)
+                .map_err(|source| invalid_weights(source.to_string()))?;
+            with_rng(|rng| weighted_index.sample(rng))
+        }
+        None => with_rng(|rng| rng.gen_range(0usize..candidate_indices.len())),
+    };
+    Ok(values[candidate_indices[index_to_sample]].clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::choice::*;
+    use crate::common::tests::{test_tera_rand_function, test_tera_rand_function_returns_error};
+    use crate::rng::{clear_seed, set_seed};
+    use tracing_test::traced_test;
+
+    #[test]
+    #[traced_test]
+    fn test_random_choice() {
+        test_tera_rand_function(
+            random_choice,
+            "random_choice",
+            r#"{ "some_field": "{{ random_choice(values=["a", "b", "c"]) }}" }"#,
+            r#"\{ "some_field": "(a|b|c)" }"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_choice_keeps_number_type() {
+        test_tera_rand_function(
+            random_choice,
+            "random_choice",
+            r#"{ "some_field": {{ random_choice(values=[1, 2]) + 1 }} }"#,
+            r#"\{ "some_field": (2|3) }"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_choice_with_name() {
+        set_value_pool(
+            "test_random_choice_with_name",
+            vec![Value::from("x"), Value::from("y")],
+        );
+        test_tera_rand_function(
+            random_choice,
+            "random_choice",
+            r#"{ "some_field": "{{ random_choice(name="test_random_choice_with_name") }}" }"#,
+            r#"\{ "some_field": "(x|y)" }"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_choice_with_unknown_name_returns_error() {
+        test_tera_rand_function_returns_error(
+            random_choice,
+            "random_choice",
+            r#"{{ random_choice(name="this_pool_does_not_exist") }}"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_choice_with_values_and_name_returns_error() {
+        set_value_pool(
+            "test_random_choice_with_values_and_name",
+            vec![Value::from("x")],
+        );
+        let args: HashMap<String, Value> = HashMap::from([
+            (String::from("values"), Value::from(vec!["a", "b"])),
+            (
+                String::from("name"),
+                Value::from("test_random_choice_with_values_and_name"),
+            ),
+        ]);
+
+        let error_message: String = random_choice(&args).unwrap_err().to_string();
+        assert!(
+            error_message.contains("`values` cannot be used together with `name`"),
+            "{error_message}"
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_choice_with_single_except() {
+        for _ in 0..100 {
+            test_tera_rand_function(
+                random_choice,
+                "random_choice",
+                r#"{ "some_field": "{{ random_choice(values=["a", "b", "c"], except="b") }}" }"#,
+                r#"\{ "some_field": "(a|c)" }"#,
+            );
+        }
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_choice_with_except_list() {
+        for _ in 0..100 {
+            test_tera_rand_function(
+                random_choice,
+                "random_choice",
+                r#"{ "some_field": {{ random_choice(values=[1, 2, 3], except=[1, 3]) }} }"#,
+                r#"\{ "some_field": 2 }"#,
+            );
+        }
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_choice_with_every_value_excluded_returns_error() {
+        test_tera_rand_function_returns_error(
+            random_choice,
+            "random_choice",
+            r#"{{ random_choice(values=["a", "b"], except=["a", "b"]) }}"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_choice_with_weights_normalizes_them() {
+        let args: HashMap<String, Value> = HashMap::from([
+            (String::from("values"), Value::from(vec!["a", "b"])),
+            (String::from("weights"), Value::from(vec![900, 100])),
+        ]);
+
+        let a_count: usize = (0..1000)
+            .filter(|_| random_choice(&args).unwrap() == "a")
+            .count();
+        assert!(a_count > 800, "expected about 900 a's, but found {a_count}");
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_choice_with_zero_weight_is_never_sampled() {
+        for _ in 0..100 {
+            test_tera_rand_function(
+                random_choice,
+                "random_choice",
+                r#"{ "some_field": "{{ random_choice(values=["a", "b"], weights=[0, 5]) }}" }"#,
+                r#"\{ "some_field": "b" }"#,
+            );
+        }
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_choice_with_probabilities() {
+        test_tera_rand_function(
+            random_choice,
+            "random_choice",
+            r#"{ "some_field": "{{ random_choice(values=["a", "b", "c"], weights=[0.1, 0.2, 0.7], probabilities=true) }}" }"#,
+            r#"\{ "some_field": "(a|b|c)" }"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_choice_with_probabilities_not_summing_to_one_returns_error() {
+        test_tera_rand_function_returns_error(
+            random_choice,
+            "random_choice",
+            r#"{{ random_choice(values=["a", "b"], weights=[0.5, 0.4], probabilities=true) }}"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_choice_with_probabilities_but_no_weights_returns_error() {
+        let args: HashMap<String, Value> = HashMap::from([
+            (String::from("values"), Value::from(vec!["a", "b"])),
+            (String::from("probabilities"), Value::from(true)),
+        ]);
+
+        let error_message: String = random_choice(&args).unwrap_err().to_string();
+        assert!(
+            error_message.contains("Required argument missing for parameter `weights`"),
+            "{error_message}"
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_choice_with_mismatched_weights_returns_error() {
+        test_tera_rand_function_returns_error(
+            random_choice,
+            "random_choice",
+            r#"{{ random_choice(values=["a", "b"], weights=[1]) }}"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_choice_with_negative_weight_returns_error() {
+        test_tera_rand_function_returns_error(
+            random_choice,
+            "random_choice",
+            r#"{{ random_choice(values=["a", "b"], weights=[1, -1]) }}"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_choice_with_number_coercion() {
+        test_tera_rand_function(
+            random_choice,
+            "random_choice",
+            r#"{ "some_field": {{ random_choice(values=["200", " 404 "], coerce="number") | json_encode() }} }"#,
+            r#"^\{ "some_field": (200|404) }$"#,
+        );
+
+        let args: HashMap<String, Value> = HashMap::from([
+            (String::from("values"), Value::from(vec!["2.5"])),
+            (String::from("coerce"), Value::from("number")),
+        ]);
+        assert_eq!(random_choice(&args).unwrap(), Value::from(2.5));
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_choice_with_bool_coercion() {
+        test_tera_rand_function(
+            random_choice,
+            "random_choice",
+            r#"{ "some_field": {{ random_choice(values=["true", false], coerce="bool") | json_encode() }} }"#,
+            r#"^\{ "some_field": (true|false) }$"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_choice_with_unparseable_coercion_returns_error() {
+        test_tera_rand_function_returns_error(
+            random_choice,
+            "random_choice",
+            r#"{{ random_choice(values=["two hundred"], coerce="number") }}"#,
+        );
+        test_tera_rand_function_returns_error(
+            random_choice,
+            "random_choice",
+            r#"{{ random_choice(values=["yes"], coerce="bool") }}"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_choice_with_ensure_all_covers_every_value_before_repeating() {
+        let values: Vec<&str> = vec!["north", "south", "east", "west", "up"];
+        let args: HashMap<String, Value> = HashMap::from([
+            (String::from("values"), Value::from(values.clone())),
+            (String::from("ensure_all"), Value::from(true)),
+        ]);
+
+        for _ in 0..20 {
+            let mut round: Vec<String> = (0..values.len())
+                .map(|_| random_choice(&args).unwrap().as_str().unwrap().to_string())
+                .collect();
+            round.sort();
+
+            let mut expected_round: Vec<&str> = values.clone();
+            expected_round.sort();
+            assert_eq!(round, expected_round);
+        }
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_choice_with_ensure_all_tracks_each_array_separately() {
+        let arg_sets: Vec<HashMap<String, Value>> = [vec![1, 2, 3], vec![1, 2, 4]]
+            .into_iter()
+            .map(|values| {
+                HashMap::from([
+                    (String::from("values"), Value::from(values)),
+                    (String::from("ensure_all"), Value::from(true)),
+                ])
+            })
+            .collect();
+
+        for _ in 0..20 {
+            let mut rounds: Vec<Vec<u64>> = vec![Vec::new(), Vec::new()];
+            for _ in 0..3 {
+                for (round, args) in rounds.iter_mut().zip(&arg_sets) {
+                    round.push(random_choice(args).unwrap().as_u64().unwrap());
+                }
+            }
+            for round in rounds.iter_mut() {
+                round.sort();
+            }
+            assert_eq!(rounds, vec![vec![1, 2, 3], vec![1, 2, 4]]);
+        }
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_choice_with_ensure_all_and_pool_name() {
+        set_value_pool(
+            "test_ensure_all_pool",
+            vec![Value::from(1), Value::from(2), Value::from(3)],
+        );
+        test_tera_rand_function(
+            random_choice,
+            "random_choice",
+            r#"{% for i in range(end=3) %}{{ random_choice(name="test_ensure_all_pool", ensure_all=true) }}{% endfor %}"#,
+            r#"^(123|132|213|231|312|321)$"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_choice_with_ensure_all_and_weights_returns_error() {
+        test_tera_rand_function_returns_error(
+            random_choice,
+            "random_choice",
+            r#"{{ random_choice(values=["a", "b"], weights=[1, 2], ensure_all=true) }}"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_quantile_preserves_configured_frequencies() {
+        let args: HashMap<String, Value> = HashMap::from([
+            (String::from("quantiles"), Value::from(vec![0.5, 0.9, 0.99])),
+            (String::from("values"), Value::from(vec![10, 50, 100])),
+        ]);
+
+        let sample_count: usize = 20_000;
+        let mut value_counts: HashMap<i64, usize> = HashMap::new();
+        for _ in 0..sample_count {
+            let value: i64 = random_quantile(&args).unwrap().as_i64().unwrap();
+            *value_counts.entry(value).or_default() += 1;
+        }
+
+        assert_eq!(value_counts.len(), 3usize);
+        for (value, expected_frequency) in [(10, 0.5), (50, 0.4), (100, 0.1)] {
+            let frequency: f64 = value_counts[&value] as f64 / sample_count as f64;
+            assert!((frequency - expected_frequency).abs() < 0.02);
+        }
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_quantile_with_object_and_weights() {
+        let quantiles: Value =
+            serde_json::json!({"p99": "slow", "p50": "typical", "0.999": "timeout"});
+        let args: HashMap<String, Value> = HashMap::from([
+            (String::from("quantiles"), quantiles),
+            (String::from("weights"), Value::from(vec![0.0, 1.0, 0.0])),
+        ]);
+        for _ in 0..100 {
+            assert_eq!(random_quantile(&args).unwrap(), "slow");
+        }
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_quantile_with_invalid_quantile_returns_error() {
+        test_tera_rand_function_returns_error(
+            random_quantile,
+            "random_quantile",
+            r#"{{ random_quantile(quantiles=[0.5, 1.5], values=[1, 2]) }}"#,
+        );
+        test_tera_rand_function_returns_error(
+            random_quantile,
+            "random_quantile",
+            r#"{{ random_quantile(quantiles=[0.5, 0.9], values=[1]) }}"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_subset() {
+        let args: HashMap<String, Value> = HashMap::from([
+            (String::from("values"), Value::from(vec![1, 2, 3, 4, 5])),
+            (String::from("count"), Value::from(3)),
+        ]);
+        let subset: Value = random_subset(&args).unwrap();
+        let mut subset: Vec<i64> = subset
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|value| value.as_i64().unwrap())
+            .collect();
+
+        assert_eq!(subset.len(), 3usize);
+        subset.sort();
+        subset.dedup();
+        assert_eq!(subset.len(), 3usize);
+        assert!(subset.iter().all(|value| (1..=5).contains(value)));
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_subset_with_ordered_is_subsequence_of_values() {
+        let values: Vec<i32> = (0..20).collect();
+        let args: HashMap<String, Value> = HashMap::from([
+            (String::from("values"), Value::from(values.clone())),
+            (String::from("count"), Value::from(8)),
+            (String::from("ordered"), Value::from(true)),
+        ]);
+        for _ in 0..100 {
+            let subset: Value = random_subset(&args).unwrap();
+            let subset: Vec<i64> = subset
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|element| element.as_i64().unwrap())
+                .collect();
+            assert_eq!(subset.len(), 8usize);
+
+            // every element must be found after the previous one in `values`
+            let mut remaining_values = values.iter();
+            assert!(subset
+                .iter()
+                .all(|element| remaining_values.any(|value| i64::from(*value) == *element)));
+        }
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_subset_is_reproducible_under_fixed_seed() {
+        let args: HashMap<String, Value> = HashMap::from([
+            (
+                String::from("values"),
+                Value::from((0..100).collect::<Vec<i32>>()),
+            ),
+            (String::from("count"), Value::from(10)),
+        ]);
+
+        set_seed(17);
+        let first: Vec<Value> = (0..5).map(|_| random_subset(&args).unwrap()).collect();
+        set_seed(17);
+        let second: Vec<Value> = (0..5).map(|_| random_subset(&args).unwrap()).collect();
+        clear_seed();
+
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_subset_with_count_larger_than_values_returns_error() {
+        test_tera_rand_function_returns_error(
+            random_subset,
+            "random_subset",
+            r#"{{ random_subset(values=["a", "b"], count=3) }}"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_choice_with_empty_values_returns_error() {
+        test_tera_rand_function_returns_error(
+            random_choice,
+            "random_choice",
+            r#"{{ random_choice(values=[]) }}"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_weighted() {
+        test_tera_rand_function(
+            random_weighted,
+            "random_weighted",
+            r#"{ "some_field": "{{ random_weighted(choices=["GET", "POST"], weights=[80, 20]) }}" }"#,
+            r#"\{ "some_field": "(GET|POST)" }"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_weighted_follows_weights() {
+        let args: HashMap<String, Value> = HashMap::from([
+            (
+                String::from("choices"),
+                Value::from(vec!["GET", "POST", "DELETE"]),
+            ),
+            (String::from("weights"), Value::from(vec![0.8, 0.2, 0.0])),
+        ]);
+
+        let sample_count: usize = 10_000;
+        let mut get_count: usize = 0;
+        for _ in 0..sample_count {
+            let method: Value = random_weighted(&args).unwrap();
+            assert_ne!(method, "DELETE");
+            if method == "GET" {
+                get_count += 1;
+            }
+        }
+        assert!((get_count as f64 / sample_count as f64 - 0.8).abs() < 0.03);
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_weighted_with_invalid_args_returns_error() {
+        for template in [
+            r#"{{ random_weighted(weights=[1]) }}"#,
+            r#"{{ random_weighted(choices=["a"]) }}"#,
+            r#"{{ random_weighted(choices=[], weights=[]) }}"#,
+            r#"{{ random_weighted(choices=["a", "b"], weights=[1]) }}"#,
+            r#"{{ random_weighted(choices=["a", "b"], weights=[0, 0]) }}"#,
+            r#"{{ random_weighted(choices=["a", "b"], weights=[1, -1]) }}"#,
+        ] {
+            test_tera_rand_function_returns_error(random_weighted, "random_weighted", template);
+        }
+    }
+}
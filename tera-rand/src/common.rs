@@ -1,13 +1,22 @@
-use crate::error::arg_parse_error;
+use crate::error::{
+    arg_parse_error, conflicting_args, empty_range, internal_error, sample_larger_than_population,
+    unsupported_arg,
+};
+use crate::number::{integer_to_value, number_mode, wide_integer_to_value, NumberMode};
+use crate::rng::with_rng;
 use rand::distributions::uniform::{SampleRange, SampleUniform};
 use rand::distributions::Standard;
 use rand::prelude::Distribution;
-use rand::{random, thread_rng, Rng};
+use rand::seq::index;
+use rand::{Rng, RngCore};
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 use std::collections::HashMap;
-use std::ops::RangeInclusive;
-use tera::{from_value, to_value, Result, Value};
+use std::fmt::Debug;
+use std::ops::{Range, RangeInclusive};
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tera::{from_value, Result, Value};
 
 // Parse an argument for the given `parameter` name from `args`, a map of arguments.
 //
@@ -29,6 +38,78 @@ where
         .map_err(|source| arg_parse_error(parameter, source))
 }
 
+// Parse an argument for the given `parameter` name which may be either a single value or an array
+// of values. A single value is returned as an array of one element.
+//
+// If an argument is not found at all, this function returns `tera::Result::Ok(None)`.
+pub(crate) fn parse_one_or_many_arg<T>(
+    args: &HashMap<String, Value>,
+    parameter: &'static str,
+) -> Result<Option<Vec<T>>>
+where
+    T: DeserializeOwned,
+{
+    args.get(parameter)
+        .cloned()
+        .map(|value| match value {
+            Value::Array(_) => from_value(value),
+            value => from_value(value).map(|single_value: T| vec![single_value]),
+        })
+        .transpose()
+        .map_err(|source| arg_parse_error(parameter, source))
+}
+
+// Whether `c` must be escaped to appear inside a JSON string, i.e. whether it is a quotation mark,
+// a reverse solidus, or a control character between U+0000 and U+001F.
+pub(crate) fn needs_json_escape(c: char) -> bool {
+    c == '"' || c == '\\' || c < '\u{20}'
+}
+
+// Sample a char from the standard distribution, resampling if it would need escaping in a JSON
+// string. Those chars make up a tiny fraction of the distribution, so this rarely loops.
+pub(crate) fn gen_json_safe_char(rng: &mut dyn RngCore) -> char {
+    loop {
+        let c: char = rng.gen::<char>();
+        if !needs_json_escape(c) {
+            return c;
+        }
+    }
+}
+
+// If the `quote` argument is true, convert `value` into a String containing its JSON
+// representation, which for a String value is the String wrapped in quotation marks with any
+// special characters escaped. Otherwise, return `value` unchanged.
+pub(crate) fn quote_if_requested(args: &HashMap<String, Value>, value: Value) -> Result<Value> {
+    let quote: bool = parse_arg(args, "quote")?.unwrap_or(false);
+    if quote {
+        Ok(Value::String(value.to_string()))
+    } else {
+        Ok(value)
+    }
+}
+
+// Sample `count` distinct indices in `0..len` in a random order. All randomness is drawn in a
+// single pass from the thread's generator, so the order is reproducible under a fixed seed.
+pub(crate) fn sample_indices(len: usize, count: usize) -> Result<Vec<usize>> {
+    if count > len {
+        return Err(sample_larger_than_population(count, len));
+    }
+    Ok(with_rng(|rng| index::sample(rng, len, count).into_vec()))
+}
+
+// Parse whether the end of a range is excluded, which may be passed as either `exclusive` or its
+// alias `exclusive_end`, but not both.
+pub(crate) fn parse_exclusive_arg(args: &HashMap<String, Value>) -> Result<bool> {
+    let exclusive_opt: Option<bool> = parse_arg(args, "exclusive")?;
+    let exclusive_end_opt: Option<bool> = parse_arg(args, "exclusive_end")?;
+    match (exclusive_opt, exclusive_end_opt) {
+        (Some(_), Some(_)) => Err(conflicting_args("exclusive", "exclusive_end")),
+        (exclusive_opt, exclusive_end_opt) => {
+            Ok(exclusive_opt.or(exclusive_end_opt).unwrap_or(false))
+        }
+    }
+}
+
 // Generate a random value.
 //
 // If both `start_opt` and `end_opt` are provided, they will bound the space from which the value
@@ -40,44 +121,260 @@ where
 // If neither `start_opt` nor `end_opt` is provided, then this function will generate a value from
 // the standard distribution, notably NOT using either `default_start` or `default_end`. This is
 // done for performance.
+//
+// If the resulting range is empty because its start is greater than its end, this function
+// returns an error rather than panicking.
 pub(crate) fn gen_value_in_range<T>(
     start_opt: Option<T>,
     end_opt: Option<T>,
     default_start: T,
     default_end: T,
-) -> T
+) -> Result<T>
 where
-    T: SampleUniform,
+    T: SampleUniform + PartialOrd + Debug,
     RangeInclusive<T>: SampleRange<T>,
     Standard: Distribution<T>,
 {
-    match (start_opt, end_opt) {
-        (Some(start), Some(end)) => thread_rng().gen_range(start..=end),
-        (Some(start), None) => thread_rng().gen_range(start..=default_end),
-        (None, Some(end)) => thread_rng().gen_range(default_start..=end),
-        (None, None) => random::<T>(),
+    let (start, end): (T, T) = match (start_opt, end_opt) {
+        (Some(start), Some(end)) => (start, end),
+        (Some(start), None) => (start, default_end),
+        (None, Some(end)) => (default_start, end),
+        (None, None) => return Ok(with_rng(|rng| rng.gen::<T>())),
+    };
+    if start > end {
+        return Err(empty_range(format!("{start:?}"), format!("{end:?}")));
     }
+    Ok(with_rng(|rng| rng.gen_range(start..=end)))
 }
 
-// convenience function to parse `start` and `end` arguments from the Tera template function call,
-// generate a random value in between `start` and/or `end` if specified, and then convert the
-// result into a value for Tera to render.
-pub(crate) fn parse_range_and_gen_value_in_range<T>(
+// Generate a random integer of the form `start + k * step` for some non-negative integer `k`,
+// between `start` and `end`. `end` is inclusive unless `exclusive` is true.
+//
+// The arithmetic is done in `i128`, which can hold every `i64` and `u64` as well as the difference
+// between any two of them, so that bounds at the exact minimum or maximum of `T` never overflow.
+pub(crate) fn gen_integer_in_range_with_step<T>(
+    start: T,
+    end: T,
+    step: u64,
+    exclusive: bool,
+) -> Result<T>
+where
+    T: Into<i128> + TryFrom<i128> + Debug + Copy,
+{
+    if step == 0 {
+        return Err(unsupported_arg("step", step.to_string()));
+    }
+    let start_i128: i128 = start.into();
+    let end_i128: i128 = if exclusive {
+        Into::<i128>::into(end) - 1
+    } else {
+        end.into()
+    };
+    if start_i128 > end_i128 {
+        return Err(empty_range(format!("{start:?}"), format!("{end:?}")));
+    }
+
+    let max_step_count: u128 = (end_i128 - start_i128) as u128 / u128::from(step);
+    let step_count: u128 = with_rng(|rng| rng.gen_range(0u128..=max_step_count));
+    let random_value: i128 = start_i128 + (step_count * u128::from(step)) as i128;
+    T::try_from(random_value).map_err(|_| {
+        internal_error(format!(
+            "Generated value {random_value} between {start:?} and {end:?} is out of bounds"
+        ))
+    })
+}
+
+// Generate a random multiple of `multiple_of` between `start` and `end`. `end` is inclusive
+// unless `exclusive` is true.
+//
+// `multiple_of` must be positive and no larger than the distance between `start` and `end`, which
+// guarantees that there is a multiple to generate even if `end` is excluded.
+pub(crate) fn gen_multiple_in_range<T>(
+    start: T,
+    end: T,
+    multiple_of: u64,
+    exclusive: bool,
+) -> Result<T>
+where
+    T: Into<i128> + TryFrom<i128> + Debug + Copy,
+{
+    let start_i128: i128 = start.into();
+    let end_i128: i128 = end.into();
+    if start_i128 > end_i128 {
+        return Err(empty_range(format!("{start:?}"), format!("{end:?}")));
+    }
+    let multiple_of_i128: i128 = i128::from(multiple_of);
+    if multiple_of == 0 || multiple_of_i128 > end_i128 - start_i128 {
+        return Err(unsupported_arg("multiple_of", multiple_of.to_string()));
+    }
+
+    // round `start` up to the nearest multiple, which is at most `end` since the range is at
+    // least `multiple_of` wide
+    let first_multiple: i128 = start_i128
+        + (multiple_of_i128 - start_i128.rem_euclid(multiple_of_i128)) % multiple_of_i128;
+    let first_multiple: T = T::try_from(first_multiple).map_err(|_| {
+        internal_error(format!(
+            "Multiple {first_multiple} between {start:?} and {end:?} is out of bounds"
+        ))
+    })?;
+    gen_integer_in_range_with_step(first_multiple, end, multiple_of, exclusive)
+}
+
+// Generate a random float between `start` and `end`, both inclusive, which default to `0.0` and
+// `1.0`, respectively.
+//
+// `rand` samples an inclusive float range by scaling the width of the range by a factor slightly
+// greater than 1, and panics if that overflows, as it does for a range from `-f64::MAX` to `0.0`.
+// Such a range is sampled by interpolating between its bounds instead.
+pub(crate) fn gen_float_in_range(start_opt: Option<f64>, end_opt: Option<f64>) -> Result<f64> {
+    let start: f64 = start_opt.unwrap_or(0.0);
+    let end: f64 = end_opt.unwrap_or(1.0);
+    if start > end {
+        return Err(empty_range(format!("{start:?}"), format!("{end:?}")));
+    }
+
+    if ((end - start) / (1.0 - f64::EPSILON)).is_finite() {
+        Ok(with_rng(|rng| rng.gen_range(start..=end)))
+    } else {
+        let fraction: f64 = with_rng(|rng| rng.gen_range(0.0..=1.0));
+        Ok((start * (1.0 - fraction) + end * fraction).clamp(start, end))
+    }
+}
+
+// Round `value` to `precision` decimal places. A value too large to be rounded to that many
+// decimal places already has none, so it is returned unchanged.
+pub(crate) fn round_to_precision(value: f64, precision: u32) -> f64 {
+    let scale: f64 = 10f64.powi(i32::try_from(precision).unwrap_or(i32::MAX));
+    let rounded: f64 = (value * scale).round() / scale;
+    if rounded.is_finite() {
+        rounded
+    } else {
+        value
+    }
+}
+
+// Like `parse_range_and_gen_value_in_range`, but for integers, which are rendered according to the
+// `number_mode` argument if there is one, or the mode set by `set_number_mode` otherwise.
+pub(crate) fn parse_range_and_gen_integer_in_range<T>(
     args: &HashMap<String, Value>,
     default_start: T,
     default_end: T,
 ) -> Result<Value>
 where
-    T: SampleUniform + DeserializeOwned + Serialize,
+    T: SampleUniform
+        + PartialOrd
+        + Debug
+        + DeserializeOwned
+        + Into<i128>
+        + TryFrom<i128>
+        + Into<Value>
+        + ToString
+        + Copy,
     RangeInclusive<T>: SampleRange<T>,
     Standard: Distribution<T>,
 {
     let start_opt: Option<T> = parse_arg(args, "start")?;
     let end_opt: Option<T> = parse_arg(args, "end")?;
+    let step_opt: Option<u64> = parse_arg(args, "step")?;
+    let multiple_of_opt: Option<u64> = parse_arg(args, "multiple_of")?;
+    let exclusive: bool = parse_exclusive_arg(args)?;
+    let number_mode: NumberMode = parse_arg(args, "number_mode")?.unwrap_or_else(number_mode);
+
+    let random_value: T = match (step_opt, multiple_of_opt, exclusive) {
+        (Some(_), Some(_), _) => return Err(conflicting_args("step", "multiple_of")),
+        (None, Some(multiple_of), exclusive) => gen_multiple_in_range(
+            start_opt.unwrap_or(default_start),
+            end_opt.unwrap_or(default_end),
+            multiple_of,
+            exclusive,
+        )?,
+        (None, None, false) => gen_value_in_range(start_opt, end_opt, default_start, default_end)?,
+        (step_opt, None, exclusive) => gen_integer_in_range_with_step(
+            start_opt.unwrap_or(default_start),
+            end_opt.unwrap_or(default_end),
+            step_opt.unwrap_or(1u64),
+            exclusive,
+        )?,
+    };
+    Ok(integer_to_value(random_value, number_mode))
+}
+
+// Parse a 128-bit integer argument, which may be passed either as a number or as a string of
+// decimal digits. Tera's template parser cannot read integer literals beyond the range of `i64`,
+// so larger bounds have to be passed as strings.
+pub(crate) fn parse_wide_integer_arg<T>(
+    args: &HashMap<String, Value>,
+    parameter: &'static str,
+) -> Result<Option<T>>
+where
+    T: DeserializeOwned + FromStr,
+{
+    match args.get(parameter) {
+        Some(Value::String(digits)) => digits
+            .trim()
+            .parse()
+            .map(Some)
+            .map_err(|_| unsupported_arg(parameter, digits.clone())),
+        _ => parse_arg(args, parameter),
+    }
+}
+
+// Like `parse_range_and_gen_integer_in_range`, but for 128-bit integers, whose bounds may be
+// passed as strings and which may be too large for Tera to render as numbers. `step` is not
+// supported, since the arithmetic behind it is done in `i128`.
+pub(crate) fn parse_range_and_gen_wide_integer_in_range<T>(
+    args: &HashMap<String, Value>,
+    default_start: T,
+    default_end: T,
+) -> Result<Value>
+where
+    T: SampleUniform
+        + PartialOrd
+        + Debug
+        + DeserializeOwned
+        + FromStr
+        + Serialize
+        + TryInto<i64>
+        + ToString
+        + Copy,
+    Range<T>: SampleRange<T>,
+    RangeInclusive<T>: SampleRange<T>,
+    Standard: Distribution<T>,
+{
+    let start_opt: Option<T> = parse_wide_integer_arg(args, "start")?;
+    let end_opt: Option<T> = parse_wide_integer_arg(args, "end")?;
+    let exclusive: bool = parse_exclusive_arg(args)?;
+    let number_mode: NumberMode = parse_arg(args, "number_mode")?.unwrap_or_else(number_mode);
+
+    let random_value: T = if exclusive {
+        let start: T = start_opt.unwrap_or(default_start);
+        let end: T = end_opt.unwrap_or(default_end);
+        if start >= end {
+            return Err(empty_range(format!("{start:?}"), format!("{end:?}")));
+        }
+        with_rng(|rng| rng.gen_range(start..end))
+    } else {
+        gen_value_in_range(start_opt, end_opt, default_start, default_end)?
+    };
+    Ok(wide_integer_to_value(random_value, number_mode))
+}
+
+// The current time as seconds since the Unix epoch.
+pub(crate) fn current_unix_timestamp() -> Result<i64> {
+    let since_epoch = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_err(|source| internal_error(source.to_string()))?;
+    i64::try_from(since_epoch.as_secs()).map_err(|source| internal_error(source.to_string()))
+}
 
-    let random_value: T = gen_value_in_range(start_opt, end_opt, default_start, default_end);
-    let json_value: Value = to_value(random_value)?;
-    Ok(json_value)
+// The current time as milliseconds since the Unix epoch, for identifiers which start with a
+// timestamp.
+#[cfg(any(feature = "uuid", feature = "ulid"))]
+pub(crate) fn current_unix_millis() -> Result<u64> {
+    let since_epoch = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_err(|source| internal_error(source.to_string()))?;
+    u64::try_from(since_epoch.as_millis()).map_err(|source| internal_error(source.to_string()))
 }
 
 #[cfg(test)]
@@ -0,0 +1,123 @@
+use crate::common::{parse_arg, quote_if_requested, sample_indices};
+use crate::error::unsupported_arg;
+use crate::rng::with_rng;
+use rand::{Rng, RngCore};
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
+use tera::{to_value, Result, Value};
+
+// The bounds of each of the 5 fields of a cron expression: minute, hour, day of month, month, and
+// day of week.
+const CRON_FIELD_BOUNDS: [RangeInclusive<u32>; 5] = [0..=59, 0..=23, 1..=31, 1..=12, 0..=6];
+
+/// A Tera function to generate a random, syntactically valid 5-field cron expression, such as
+/// `*/15 9-17 * * 1-5`.
+///
+/// The `complexity` parameter takes the number of fields, from 0 to 5, which are not a wildcard
+/// (`*`). Each of those fields is randomly one of a single value like `30`, a range like `9-17`,
+/// a step like `*/15`, or a list like `1,15`. The fields are chosen at random. `complexity`
+/// defaults to 2.
+///
+/// Pass `quote=true` to render the expression wrapped in JSON quotation marks.
+///
+/// # Example usage
+///
+/// ```edition2021
+/// use tera::{Context, Tera};
+/// use tera_rand::random_cron;
+///
+/// let mut tera: Tera = Tera::default();
+/// tera.register_function("random_cron", random_cron);
+/// let context: Context = Context::new();
+///
+/// let rendered: String = tera
+///     .render_str("{{ random_cron() }}", &context)
+///     .unwrap();
+/// // constrain every field
+/// let rendered: String = tera
+///     .render_str("{{ random_cron(complexity=5) }}", &context)
+///     .unwrap();
+/// ```
+pub fn random_cron(args: &HashMap<String, Value>) -> Result<Value> {
+    let complexity: usize = parse_arg(args, "complexity")?.unwrap_or(2usize);
+    if complexity > CRON_FIELD_BOUNDS.len() {
+        return Err(unsupported_arg("complexity", complexity.to_string()));
+    }
+
+    let mut fields: Vec<String> = vec![String::from("*"); CRON_FIELD_BOUNDS.len()];
+    for field_index in sample_indices(CRON_FIELD_BOUNDS.len(), complexity)? {
+        fields[field_index] = with_rng(|rng| gen_cron_field(rng, &CRON_FIELD_BOUNDS[field_index]));
+    }
+
+    let json_value: Value = to_value(fields.join(" "))?;
+    quote_if_requested(args, json_value)
+}
+
+// Generate a field which is not a wildcard and whose values are within `bounds`.
+fn gen_cron_field(rng: &mut dyn RngCore, bounds: &RangeInclusive<u32>) -> String {
+    let (min, max): (u32, u32) = (*bounds.start(), *bounds.end());
+    match rng.gen_range(0..4) {
+        0 => rng.gen_range(min..=max).to_string(),
+        1 => {
+            let start: u32 = rng.gen_range(min..max);
+            let end: u32 = rng.gen_range(start + 1..=max);
+            format!("{start}-{end}")
+        }
+        2 => format!("*/{}", rng.gen_range(2..=(max - min + 1) / 2)),
+        _ => {
+            let first: u32 = rng.gen_range(min..max);
+            let second: u32 = rng.gen_range(first + 1..=max);
+            format!("{first},{second}")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::tests::{test_tera_rand_function, test_tera_rand_function_returns_error};
+    use crate::cron::*;
+    use croner::Cron;
+    use tracing_test::traced_test;
+
+    #[test]
+    #[traced_test]
+    fn test_random_cron() {
+        test_tera_rand_function(
+            random_cron,
+            "random_cron",
+            r#"{ "some_field": "{{ random_cron() }}" }"#,
+            r#"\{ "some_field": "[\d*/,-]+( [\d*/,-]+){4}" }"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_cron_parses_at_every_complexity() {
+        for complexity in 0..=5 {
+            let args: HashMap<String, Value> =
+                HashMap::from([(String::from("complexity"), Value::from(complexity))]);
+            for _ in 0..200 {
+                let expression: Value = random_cron(&args).unwrap();
+                let expression: &str = expression.as_str().unwrap();
+
+                let wildcard_count: usize =
+                    expression.split(' ').filter(|field| *field == "*").count();
+                assert_eq!(wildcard_count, 5 - complexity);
+                assert!(
+                    Cron::new(expression).parse().is_ok(),
+                    "{expression} should parse"
+                );
+            }
+        }
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_cron_with_complexity_too_large_returns_error() {
+        test_tera_rand_function_returns_error(
+            random_cron,
+            "random_cron",
+            "{{ random_cron(complexity=6) }}",
+        );
+    }
+}
@@ -0,0 +1,254 @@
+use crate::common::{parse_arg, quote_if_requested};
+use crate::error::{no_values_to_sample, unsupported_arg};
+use crate::rng::with_rng;
+use rand::Rng;
+use std::collections::HashMap;
+use tera::{to_value, Result, Value};
+
+// An ISO 4217 currency along with its commonly used symbol and English name.
+#[derive(Debug)]
+struct Currency {
+    code: &'static str,
+    symbol: &'static str,
+    name: &'static str,
+}
+
+const CURRENCIES: &[Currency] = &[
+    Currency {
+        code: "AUD",
+        symbol: "A$",
+        name: "Australian Dollar",
+    },
+    Currency {
+        code: "BRL",
+        symbol: "R$",
+        name: "Brazilian Real",
+    },
+    Currency {
+        code: "CAD",
+        symbol: "CA$",
+        name: "Canadian Dollar",
+    },
+    Currency {
+        code: "CHF",
+        symbol: "CHF",
+        name: "Swiss Franc",
+    },
+    Currency {
+        code: "CNY",
+        symbol: "CN¥",
+        name: "Chinese Yuan",
+    },
+    Currency {
+        code: "EUR",
+        symbol: "€",
+        name: "Euro",
+    },
+    Currency {
+        code: "GBP",
+        symbol: "£",
+        name: "Pound Sterling",
+    },
+    Currency {
+        code: "HKD",
+        symbol: "HK$",
+        name: "Hong Kong Dollar",
+    },
+    Currency {
+        code: "INR",
+        symbol: "₹",
+        name: "Indian Rupee",
+    },
+    Currency {
+        code: "JPY",
+        symbol: "¥",
+        name: "Japanese Yen",
+    },
+    Currency {
+        code: "KRW",
+        symbol: "₩",
+        name: "South Korean Won",
+    },
+    Currency {
+        code: "MXN",
+        symbol: "MX$",
+        name: "Mexican Peso",
+    },
+    Currency {
+        code: "NOK",
+        symbol: "kr",
+        name: "Norwegian Krone",
+    },
+    Currency {
+        code: "NZD",
+        symbol: "NZ$",
+        name: "New Zealand Dollar",
+    },
+    Currency {
+        code: "PLN",
+        symbol: "zł",
+        name: "Polish Zloty",
+    },
+    Currency {
+        code: "SEK",
+        symbol: "kr",
+        name: "Swedish Krona",
+    },
+    Currency {
+        code: "SGD",
+        symbol: "S$",
+        name: "Singapore Dollar",
+    },
+    Currency {
+        code: "TRY",
+        symbol: "₺",
+        name: "Turkish Lira",
+    },
+    Currency {
+        code: "USD",
+        symbol: "$",
+        name: "US Dollar",
+    },
+    Currency {
+        code: "ZAR",
+        symbol: "R",
+        name: "South African Rand",
+    },
+];
+
+/// A Tera function to generate a random ISO 4217 currency code, such as `USD` or `EUR`.
+///
+/// The `format` parameter chooses what is returned for the sampled currency:
+/// - `code` (the default) returns the three-letter ISO 4217 code, like `EUR`
+/// - `symbol` returns the currency symbol, like `€`
+/// - `name` returns the English name, like `Euro`
+///
+/// The optional `only` parameter takes an array of currency codes to restrict sampling to. Codes
+/// which are not in the built-in table of common currencies result in an error.
+///
+/// Pass `quote=true` to render the result wrapped in JSON quotation marks.
+///
+/// # Example usage
+///
+/// ```edition2021
+/// use tera::{Context, Tera};
+/// use tera_rand::random_currency_code;
+///
+/// let mut tera: Tera = Tera::default();
+/// tera.register_function("random_currency_code", random_currency_code);
+/// let context: Context = Context::new();
+///
+/// let rendered: String = tera
+///     .render_str("{{ random_currency_code() }}", &context)
+///     .unwrap();
+/// // return the symbol instead of the code
+/// let rendered: String = tera
+///     .render_str(r#"{{ random_currency_code(format="symbol") }}"#, &context)
+///     .unwrap();
+/// // only sample from a few currencies
+/// let rendered: String = tera
+///     .render_str(r#"{{ random_currency_code(only=["USD", "EUR", "GBP"]) }}"#, &context)
+///     .unwrap();
+/// ```
+pub fn random_currency_code(args: &HashMap<String, Value>) -> Result<Value> {
+    let format: String = parse_arg(args, "format")?.unwrap_or_else(|| String::from("code"));
+    let only_opt: Option<Vec<String>> = parse_arg(args, "only")?;
+
+    let candidates: Vec<&Currency> = match only_opt {
+        Some(only) => only
+            .into_iter()
+            .map(|code| {
+                CURRENCIES
+                    .iter()
+                    .find(|currency| currency.code == code)
+                    .ok_or_else(|| unsupported_arg("only", code))
+            })
+            .collect::<Result<Vec<&Currency>>>()?,
+        None => CURRENCIES.iter().collect(),
+    };
+    if candidates.is_empty() {
+        return Err(no_values_to_sample("only"));
+    }
+
+    let currency: &Currency = candidates[with_rng(|rng| rng.gen_range(0usize..candidates.len()))];
+    let formatted_currency: &str = match format.as_str() {
+        "code" => Ok(currency.code),
+        "symbol" => Ok(currency.symbol),
+        "name" => Ok(currency.name),
+        _ => Err(unsupported_arg("format", format)),
+    }?;
+    let json_value: Value = to_value(formatted_currency)?;
+    quote_if_requested(args, json_value)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::tests::{test_tera_rand_function, test_tera_rand_function_returns_error};
+    use crate::currency::*;
+    use tracing_test::traced_test;
+
+    #[test]
+    #[traced_test]
+    fn test_random_currency_code() {
+        for _ in 0..100 {
+            let code: Value = random_currency_code(&HashMap::new()).unwrap();
+            let code: &str = code.as_str().unwrap();
+            assert!(code.len() == 3 && code.chars().all(|c| c.is_ascii_uppercase()));
+            assert!(CURRENCIES.iter().any(|currency| currency.code == code));
+        }
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_currency_code_with_symbol_format() {
+        for (code, symbol) in [("USD", "$"), ("EUR", "€"), ("GBP", "£"), ("JPY", "¥")] {
+            let args: HashMap<String, Value> = HashMap::from([
+                (String::from("format"), Value::from("symbol")),
+                (String::from("only"), Value::from(vec![code])),
+            ]);
+            assert_eq!(random_currency_code(&args).unwrap(), symbol);
+        }
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_currency_code_with_name_format() {
+        test_tera_rand_function(
+            random_currency_code,
+            "random_currency_code",
+            r#"{ "some_field": "{{ random_currency_code(format="name", only=["EUR"]) }}" }"#,
+            r#"\{ "some_field": "Euro" }"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_currency_code_with_only() {
+        test_tera_rand_function(
+            random_currency_code,
+            "random_currency_code",
+            r#"{ "some_field": "{{ random_currency_code(only=["USD", "CAD"]) }}" }"#,
+            r#"\{ "some_field": "(USD|CAD)" }"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_currency_code_with_unknown_code_returns_error() {
+        test_tera_rand_function_returns_error(
+            random_currency_code,
+            "random_currency_code",
+            r#"{{ random_currency_code(only=["XYZ"]) }}"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_currency_code_with_unknown_format_returns_error() {
+        test_tera_rand_function_returns_error(
+            random_currency_code,
+            "random_currency_code",
+            r#"{{ random_currency_code(format="emoji") }}"#,
+        );
+    }
+}
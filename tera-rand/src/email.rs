@@ -0,0 +1,122 @@
+use crate::common::{parse_arg, quote_if_requested};
+use crate::error::{no_values_to_sample, unsupported_arg};
+use crate::rng::with_rng;
+use rand::distributions::{Alphanumeric, DistString};
+use rand::Rng;
+use std::collections::HashMap;
+use tera::{to_value, Result, Value};
+
+// Common email providers, along with the reserved example domains.
+const DEFAULT_DOMAINS: &[&str] = &[
+    "gmail.com",
+    "yahoo.com",
+    "outlook.com",
+    "hotmail.com",
+    "icloud.com",
+    "proton.me",
+    "aol.com",
+    "example.com",
+    "example.org",
+    "example.net",
+];
+
+/// A Tera function to generate a random email address, such as `k3vq9tza@example.com`.
+///
+/// The local part, before the `@`, is made of lowercase letters and digits, starting with a
+/// letter. The `local_length` parameter takes its length, which must be positive. It defaults to
+/// 8.
+///
+/// The domain is sampled from the `domains` parameter, which takes an array of domains like
+/// `["acme.com", "acme.io"]`. If `domains` is not passed in, a built-in list of common email
+/// providers and example domains is used.
+///
+/// Pass `quote=true` to render the address wrapped in JSON quotation marks.
+///
+/// # Example usage
+///
+/// ```edition2021
+/// use tera::{Context, Tera};
+/// use tera_rand::random_email;
+///
+/// let mut tera: Tera = Tera::default();
+/// tera.register_function("random_email", random_email);
+/// let context: Context = Context::new();
+///
+/// let rendered: String = tera
+///     .render_str("{{ random_email() }}", &context)
+///     .unwrap();
+/// // employees of a single company
+/// let rendered: String = tera
+///     .render_str(r#"{{ random_email(domains=["acme.com"], local_length=12) }}"#, &context)
+///     .unwrap();
+/// ```
+pub fn random_email(args: &HashMap<String, Value>) -> Result<Value> {
+    let local_length: usize = parse_arg(args, "local_length")?.unwrap_or(8usize);
+    if local_length == 0 {
+        return Err(unsupported_arg("local_length", local_length.to_string()));
+    }
+    let domains: Vec<String> = match parse_arg::<Vec<String>>(args, "domains")? {
+        Some(domains) => domains,
+        None => DEFAULT_DOMAINS
+            .iter()
+            .map(|domain| domain.to_string())
+            .collect(),
+    };
+    if domains.is_empty() {
+        return Err(no_values_to_sample("domains"));
+    }
+
+    let random_email: String = with_rng(|rng| {
+        let first_letter: char = char::from(b'a' + rng.gen_range(0u8..26));
+        let rest: String = Alphanumeric
+            .sample_string(rng, local_length - 1)
+            .to_ascii_lowercase();
+        let domain: &str = &domains[rng.gen_range(0usize..domains.len())];
+        format!("{first_letter}{rest}@{domain}")
+    });
+
+    let json_value: Value = to_value(random_email)?;
+    quote_if_requested(args, json_value)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::tests::{test_tera_rand_function, test_tera_rand_function_returns_error};
+    use crate::email::*;
+    use tracing_test::traced_test;
+
+    #[test]
+    #[traced_test]
+    fn test_random_email() {
+        for _ in 0..100 {
+            test_tera_rand_function(
+                random_email,
+                "random_email",
+                r#"{ "some_field": "{{ random_email() }}" }"#,
+                r#"^\{ "some_field": "[\w.]+@[\w.]+" }$"#,
+            );
+        }
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_email_with_domains_and_local_length() {
+        test_tera_rand_function(
+            random_email,
+            "random_email",
+            r#"{ "some_field": "{{ random_email(domains=["acme.com", "acme.io"], local_length=12) }}" }"#,
+            r#"^\{ "some_field": "[a-z][a-z0-9]{11}@acme\.(com|io)" }$"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_email_with_invalid_args_returns_error() {
+        for template in [
+            "{{ random_email(local_length=0) }}",
+            "{{ random_email(domains=[]) }}",
+        ] {
+            test_tera_rand_function_returns_error(random_email, "random_email", template);
+        }
+    }
+}
@@ -0,0 +1,292 @@
+use crate::common::{parse_arg, quote_if_requested};
+use crate::error::unsupported_arg;
+use crate::rng::with_rng;
+use rand::Rng;
+use std::collections::HashMap;
+use tera::{to_value, Result, Value};
+
+// Each emoji is one complete grapheme, which may be a sequence of several code points joined by
+// zero width joiners (U+200D) or followed by a variation selector (U+FE0F). Sequences are stored
+// whole so that they are never split.
+const FACES: &[&str] = &[
+    "😀",
+    "😃",
+    "😄",
+    "😁",
+    "😆",
+    "😅",
+    "😂",
+    "🙂",
+    "🙃",
+    "😉",
+    "😊",
+    "😇",
+    "😍",
+    "🤩",
+    "😘",
+    "😋",
+    "😛",
+    "😜",
+    "🤪",
+    "🤔",
+    "🤨",
+    "😐",
+    "😑",
+    "😶",
+    "😏",
+    "😒",
+    "🙄",
+    "😬",
+    "😌",
+    "😔",
+    "😴",
+    "😷",
+    "🤒",
+    "🤢",
+    "🥵",
+    "🥶",
+    "😵",
+    "🤯",
+    "🥳",
+    "😎",
+    "🤓",
+    "😕",
+    "😟",
+    "😮",
+    "😲",
+    "😳",
+    "🥺",
+    "😢",
+    "😭",
+    "😱",
+    "😤",
+    "😡",
+    "🤬",
+    "😈",
+    "💀",
+    "🤡",
+    "😶\u{200D}🌫\u{FE0F}",
+    "😮\u{200D}💨",
+    "😵\u{200D}💫",
+    "☺\u{FE0F}",
+];
+
+const ANIMALS: &[&str] = &[
+    "🐶",
+    "🐱",
+    "🐭",
+    "🐹",
+    "🐰",
+    "🦊",
+    "🐻",
+    "🐼",
+    "🐨",
+    "🐯",
+    "🦁",
+    "🐮",
+    "🐷",
+    "🐸",
+    "🐵",
+    "🐔",
+    "🐧",
+    "🐦",
+    "🦆",
+    "🦅",
+    "🦉",
+    "🐺",
+    "🐴",
+    "🦄",
+    "🐝",
+    "🦋",
+    "🐌",
+    "🐞",
+    "🐢",
+    "🐍",
+    "🦎",
+    "🐙",
+    "🦑",
+    "🦀",
+    "🐠",
+    "🐬",
+    "🐳",
+    "🦈",
+    "🐊",
+    "🦓",
+    "🦒",
+    "🐘",
+    "🦔",
+    "🦥",
+    "🐕\u{200D}🦺",
+    "🐈\u{200D}⬛",
+    "🐻\u{200D}❄\u{FE0F}",
+    "🐦\u{200D}⬛",
+    "🕊\u{FE0F}",
+    "🐿\u{FE0F}",
+];
+
+const FOOD: &[&str] = &[
+    "🍏",
+    "🍎",
+    "🍐",
+    "🍊",
+    "🍋",
+    "🍌",
+    "🍉",
+    "🍇",
+    "🍓",
+    "🫐",
+    "🍒",
+    "🍑",
+    "🥭",
+    "🍍",
+    "🥥",
+    "🥝",
+    "🍅",
+    "🥑",
+    "🥦",
+    "🥕",
+    "🌽",
+    "🥔",
+    "🥐",
+    "🥖",
+    "🧀",
+    "🥚",
+    "🥓",
+    "🥞",
+    "🍔",
+    "🍟",
+    "🍕",
+    "🌭",
+    "🌮",
+    "🌯",
+    "🍣",
+    "🍜",
+    "🍝",
+    "🍩",
+    "🍪",
+    "🎂",
+    "🍫",
+    "🍿",
+    "☕",
+    "🍵",
+    "🍺",
+    "🍷",
+    "🌶\u{FE0F}",
+    "🍽\u{FE0F}",
+];
+
+// The built-in emoji categories which can be passed to `random_emoji`.
+const CATEGORIES: &[(&str, &[&str])] = &[("faces", FACES), ("animals", ANIMALS), ("food", FOOD)];
+
+/// A Tera function to generate a random emoji, such as for the messages or reactions of a chat
+/// application.
+///
+/// The `category` parameter restricts the emoji to one of the built-in categories: `"faces"`,
+/// `"animals"`, or `"food"`. If not provided, every category is sampled from, with each emoji
+/// equally likely.
+///
+/// Some emoji, such as 🐻‍❄️ (polar bear), are a sequence of several Unicode code points which
+/// display as a single symbol. These are always generated whole.
+///
+/// Pass `quote=true` to render the emoji wrapped in JSON quotation marks.
+///
+/// # Example usage
+///
+/// ```edition2021
+/// use tera::{Context, Tera};
+/// use tera_rand::random_emoji;
+///
+/// let mut tera: Tera = Tera::default();
+/// tera.register_function("random_emoji", random_emoji);
+/// let context: Context = Context::new();
+///
+/// let rendered: String = tera
+///     .render_str("{{ random_emoji() }}", &context)
+///     .unwrap();
+/// // only sample from animals
+/// let rendered: String = tera
+///     .render_str(r#"{{ random_emoji(category="animals") }}"#, &context)
+///     .unwrap();
+/// ```
+pub fn random_emoji(args: &HashMap<String, Value>) -> Result<Value> {
+    let category_opt: Option<String> = parse_arg(args, "category")?;
+
+    let candidates: Vec<&str> = match category_opt {
+        Some(category) => CATEGORIES
+            .iter()
+            .find(|(name, _)| *name == category)
+            .map(|(_, emoji)| emoji.to_vec())
+            .ok_or_else(|| unsupported_arg("category", category))?,
+        None => CATEGORIES
+            .iter()
+            .flat_map(|(_, emoji)| emoji.iter().copied())
+            .collect(),
+    };
+
+    let emoji: &str = candidates[with_rng(|rng| rng.gen_range(0usize..candidates.len()))];
+    let json_value: Value = to_value(emoji)?;
+    quote_if_requested(args, json_value)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::tests::test_tera_rand_function_returns_error;
+    use crate::emoji::*;
+    use tracing_test::traced_test;
+
+    #[test]
+    #[traced_test]
+    fn test_random_emoji() {
+        for _ in 0..200 {
+            let emoji: Value = random_emoji(&HashMap::new()).unwrap();
+            let emoji: &str = emoji.as_str().unwrap();
+            assert!(CATEGORIES
+                .iter()
+                .any(|(_, category_emoji)| category_emoji.contains(&emoji)));
+        }
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_emoji_with_category() {
+        for (category, category_emoji) in CATEGORIES {
+            let args: HashMap<String, Value> =
+                HashMap::from([(String::from("category"), Value::from(*category))]);
+            for _ in 0..200 {
+                let emoji: Value = random_emoji(&args).unwrap();
+                let emoji: &str = emoji.as_str().unwrap();
+                assert!(
+                    category_emoji.contains(&emoji),
+                    "{emoji} is not in {category}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_emoji_are_single_complete_sequences() {
+        for (_, category_emoji) in CATEGORIES {
+            for emoji in *category_emoji {
+                let mut chars = emoji.chars();
+                let first: char = chars.next().unwrap();
+                assert!(!first.is_ascii() && first != '\u{200D}' && first != '\u{FE0F}');
+                // a joiner is always followed by another code point, never left dangling
+                assert!(!emoji.ends_with('\u{200D}'));
+                assert!(chars.all(|c| !c.is_ascii() && !c.is_whitespace()));
+            }
+        }
+        // the polar bear is one emoji made of 4 code points
+        assert!(ANIMALS.contains(&"🐻\u{200D}❄\u{FE0F}"));
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_emoji_with_unknown_category_returns_error() {
+        test_tera_rand_function_returns_error(
+            random_emoji,
+            "random_emoji",
+            r#"{{ random_emoji(category="vehicles") }}"#,
+        );
+    }
+}
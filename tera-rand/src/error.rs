@@ -13,15 +13,89 @@ pub(crate) enum TeraRandError {
         argument: String,
     },
 
+    #[error("`{0}` cannot be used together with `{1}`")]
+    ConflictingArguments(&'static str, &'static str),
+
     #[error("Required argument missing for parameter `{0}`")]
     RequiredArgumentMissing(&'static str),
 
     #[error("Unable to read file at path: `{0}`")]
     UnableToReadFile(String, #[source] anyhow::Error),
 
+    #[cfg(feature = "schema")]
+    #[error("Unsupported JSON Schema: {0}")]
+    UnsupportedSchema(String),
+
+    #[cfg(feature = "sqlite")]
+    #[error(
+        "Unable to run query `{query}` against the SQLite database at path `{path}`: {reason}"
+    )]
+    UnableToQueryDatabase {
+        path: String,
+        query: String,
+        reason: String,
+    },
+
     #[error("Unable to sample from an empty file: `{0}`")]
     EmptyFile(String),
 
+    #[error("No values left to sample from after applying `{0}`")]
+    NoValuesToSample(&'static str),
+
+    #[error("No value pool registered under the name `{0}`")]
+    UnknownValuePool(String),
+
+    #[error(
+        "Unable to sample {requested} values without replacement from only {available} values"
+    )]
+    SampleLargerThanPopulation { requested: usize, available: usize },
+
+    #[error("`{function}` requires the `{feature}` feature, which is not enabled")]
+    FeatureNotEnabled {
+        function: &'static str,
+        feature: &'static str,
+    },
+
+    #[error("Unable to generate a value which is not in the blocklist after {0} attempts")]
+    BlocklistAttemptsExhausted(usize),
+
+    #[error(
+        "Unable to generate an address which is not excluded by the `exclude` argument after {0} \
+         attempts"
+    )]
+    ExcludeAttemptsExhausted(usize),
+
+    #[error("The range from {start} to {end} is empty")]
+    EmptyRange { start: String, end: String },
+
+    #[error("Unable to coerce the value {value} into a {target}")]
+    UnableToCoerce { value: String, target: &'static str },
+
+    #[error("Invalid weights: {0}")]
+    InvalidWeights(String),
+
+    #[error("Probabilities should sum to 1.0, but they sum to {0}")]
+    ProbabilitiesDoNotSumToOne(f64),
+
+    #[error("Unable to parse line `{line}` in file at path: `{path}`")]
+    UnableToParseLine { path: String, line: String },
+
+    #[error(
+        "Unable to parse weight `{weight}` on line {line_num}, `{line}`, of file at path: `{path}`"
+    )]
+    UnableToParseWeight {
+        path: String,
+        line_num: usize,
+        line: String,
+        weight: String,
+    },
+
+    #[error("Unable to parse JSON file at path: `{0}`")]
+    UnableToParseJsonFile(String, #[source] anyhow::Error),
+
+    #[error("JSON pointer `{pointer}` does not refer to an array in file at path: `{path}`")]
+    NotAJsonArray { path: String, pointer: String },
+
     #[error(
         "Provided cidr length {provided_bound}, which is out of bounds. \
          Cidr length should be between {valid_bound_start} and {valid_bound_end}"
@@ -38,6 +112,7 @@ pub(crate) enum TeraRandError {
 
 // Tera functions must return a `Result` using `tera::Error`, so
 // we need to convert our internal errors
+#[allow(clippy::from_over_into, clippy::match_single_binding)]
 impl Into<tera::Error> for TeraRandError {
     fn into(self) -> Error {
         match &self {
@@ -70,16 +145,127 @@ pub(crate) fn missing_arg(parameter: &'static str) -> tera::Error {
     Into::<tera::Error>::into(tera_rand_error)
 }
 
+pub(crate) fn conflicting_args(
+    parameter: &'static str,
+    other_parameter: &'static str,
+) -> tera::Error {
+    let tera_rand_error: TeraRandError =
+        TeraRandError::ConflictingArguments(parameter, other_parameter);
+    Into::<tera::Error>::into(tera_rand_error)
+}
+
 pub(crate) fn read_file_error(path: String, source: impl Into<anyhow::Error>) -> tera::Error {
     let tera_rand_error: TeraRandError = TeraRandError::UnableToReadFile(path, anyhow!(source));
     Into::<tera::Error>::into(tera_rand_error)
 }
 
+#[cfg(feature = "schema")]
+pub(crate) fn unsupported_schema(reason: String) -> tera::Error {
+    let tera_rand_error: TeraRandError = TeraRandError::UnsupportedSchema(reason);
+    Into::<tera::Error>::into(tera_rand_error)
+}
+
+#[cfg(feature = "sqlite")]
+pub(crate) fn query_database_error(path: String, query: String, reason: String) -> tera::Error {
+    let tera_rand_error: TeraRandError = TeraRandError::UnableToQueryDatabase {
+        path,
+        query,
+        reason,
+    };
+    Into::<tera::Error>::into(tera_rand_error)
+}
+
 pub(crate) fn empty_file(path: String) -> tera::Error {
     let tera_rand_error: TeraRandError = TeraRandError::EmptyFile(path);
     Into::<tera::Error>::into(tera_rand_error)
 }
 
+pub(crate) fn no_values_to_sample(parameter: &'static str) -> tera::Error {
+    let tera_rand_error: TeraRandError = TeraRandError::NoValuesToSample(parameter);
+    Into::<tera::Error>::into(tera_rand_error)
+}
+
+pub(crate) fn unknown_value_pool(name: String) -> tera::Error {
+    let tera_rand_error: TeraRandError = TeraRandError::UnknownValuePool(name);
+    Into::<tera::Error>::into(tera_rand_error)
+}
+
+pub(crate) fn sample_larger_than_population(requested: usize, available: usize) -> tera::Error {
+    let tera_rand_error: TeraRandError = TeraRandError::SampleLargerThanPopulation {
+        requested,
+        available,
+    };
+    Into::<tera::Error>::into(tera_rand_error)
+}
+
+// only called by stubs which replace functions behind a disabled optional feature
+#[allow(dead_code)]
+pub(crate) fn feature_not_enabled(function: &'static str, feature: &'static str) -> tera::Error {
+    let tera_rand_error: TeraRandError = TeraRandError::FeatureNotEnabled { function, feature };
+    Into::<tera::Error>::into(tera_rand_error)
+}
+
+pub(crate) fn blocklist_attempts_exhausted(attempts: usize) -> tera::Error {
+    let tera_rand_error: TeraRandError = TeraRandError::BlocklistAttemptsExhausted(attempts);
+    Into::<tera::Error>::into(tera_rand_error)
+}
+
+pub(crate) fn exclude_attempts_exhausted(attempts: usize) -> tera::Error {
+    let tera_rand_error: TeraRandError = TeraRandError::ExcludeAttemptsExhausted(attempts);
+    Into::<tera::Error>::into(tera_rand_error)
+}
+
+pub(crate) fn empty_range(start: String, end: String) -> tera::Error {
+    let tera_rand_error: TeraRandError = TeraRandError::EmptyRange { start, end };
+    Into::<tera::Error>::into(tera_rand_error)
+}
+
+pub(crate) fn unable_to_coerce(value: String, target: &'static str) -> tera::Error {
+    let tera_rand_error: TeraRandError = TeraRandError::UnableToCoerce { value, target };
+    Into::<tera::Error>::into(tera_rand_error)
+}
+
+pub(crate) fn invalid_weights(reason: String) -> tera::Error {
+    let tera_rand_error: TeraRandError = TeraRandError::InvalidWeights(reason);
+    Into::<tera::Error>::into(tera_rand_error)
+}
+
+pub(crate) fn probabilities_do_not_sum_to_one(sum: f64) -> tera::Error {
+    let tera_rand_error: TeraRandError = TeraRandError::ProbabilitiesDoNotSumToOne(sum);
+    Into::<tera::Error>::into(tera_rand_error)
+}
+
+pub(crate) fn parse_line_error(path: String, line: String) -> tera::Error {
+    let tera_rand_error: TeraRandError = TeraRandError::UnableToParseLine { path, line };
+    Into::<tera::Error>::into(tera_rand_error)
+}
+
+pub(crate) fn parse_weight_error(
+    path: String,
+    line_num: usize,
+    line: String,
+    weight: String,
+) -> tera::Error {
+    let tera_rand_error: TeraRandError = TeraRandError::UnableToParseWeight {
+        path,
+        line_num,
+        line,
+        weight,
+    };
+    Into::<tera::Error>::into(tera_rand_error)
+}
+
+pub(crate) fn parse_json_file_error(path: String, source: impl Into<anyhow::Error>) -> tera::Error {
+    let tera_rand_error: TeraRandError =
+        TeraRandError::UnableToParseJsonFile(path, anyhow!(source));
+    Into::<tera::Error>::into(tera_rand_error)
+}
+
+pub(crate) fn not_a_json_array(path: String, pointer: String) -> tera::Error {
+    let tera_rand_error: TeraRandError = TeraRandError::NotAJsonArray { path, pointer };
+    Into::<tera::Error>::into(tera_rand_error)
+}
+
 pub(crate) fn cidr_prefix_length_out_of_bounds(
     provided_bound: u32,
     valid_bound_start: u32,
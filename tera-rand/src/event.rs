@@ -0,0 +1,155 @@
+use crate::common::{current_unix_timestamp, parse_arg};
+use crate::error::{missing_arg, no_values_to_sample, unsupported_arg};
+use crate::rng::with_rng;
+use rand::Rng;
+use std::collections::HashMap;
+use tera::{Map, Result, Value};
+
+const DEFAULT_EVENT_TYPES: &[&str] = &["page_view", "search", "click", "add_to_cart", "checkout"];
+// The largest supported `count`, so that a mistyped count cannot exhaust memory.
+const MAX_COUNT: usize = 1_000_000;
+
+/// A Tera function to generate an array of events in time order, such as the events of a user
+/// session. Each event is an object with a `timestamp`, in seconds since the Unix epoch, and a
+/// random `type`.
+///
+/// The `count` parameter takes the number of events to generate, which must be at most
+/// 1,000,000.
+///
+/// The `start` parameter takes the timestamp of the first event in seconds since the Unix epoch.
+/// If not provided, it defaults to the current time.
+///
+/// Each following event occurs between 1 second and `max_gap` seconds after the previous one, so
+/// timestamps are strictly increasing. `max_gap` defaults to `60`.
+///
+/// The `types` parameter takes an array of event types to sample from. If not provided, a small
+/// built-in set of web analytics event types is used.
+///
+/// Since the timestamps are integers, Tera's built-in `date` filter can format them.
+///
+/// # Example usage
+///
+/// ```edition2021
+/// use tera::{Context, Tera};
+/// use tera_rand::random_event_sequence;
+///
+/// let mut tera: Tera = Tera::default();
+/// tera.register_function("random_event_sequence", random_event_sequence);
+/// let context: Context = Context::new();
+///
+/// let rendered: String = tera
+///     .render_str(
+///         r#"{% for event in random_event_sequence(count=3, start=1700000000, max_gap=30, types=["login", "view", "logout"]) -%}
+///            {"ts": "{{ event.timestamp | date(format="%Y-%m-%dT%H:%M:%SZ") }}", "type": "{{ event.type }}"}
+///            {% endfor %}"#,
+///         &context
+///     )
+///     .unwrap();
+/// ```
+pub fn random_event_sequence(args: &HashMap<String, Value>) -> Result<Value> {
+    let count_opt: Option<usize> = parse_arg(args, "count")?;
+    let count: usize = count_opt.ok_or_else(|| missing_arg("count"))?;
+    if count > MAX_COUNT {
+        return Err(unsupported_arg("count", count.to_string()));
+    }
+    let start_opt: Option<i64> = parse_arg(args, "start")?;
+    let max_gap: u32 = parse_arg(args, "max_gap")?.unwrap_or(60u32);
+    if max_gap == 0 {
+        return Err(unsupported_arg("max_gap", max_gap.to_string()));
+    }
+    let types: Vec<Value> = match parse_arg::<Vec<Value>>(args, "types")? {
+        Some(types) => types,
+        None => DEFAULT_EVENT_TYPES
+            .iter()
+            .map(|event_type| Value::from(*event_type))
+            .collect(),
+    };
+    if types.is_empty() {
+        return Err(no_values_to_sample("types"));
+    }
+
+    let mut timestamp: i64 = match start_opt {
+        Some(start) => start,
+        None => current_unix_timestamp()?,
+    };
+    let mut events: Vec<Value> = Vec::with_capacity(count);
+    for event_num in 0..count {
+        if event_num > 0 {
+            let gap: i64 = with_rng(|rng| rng.gen_range(1..=i64::from(max_gap)));
+            timestamp = timestamp
+                .checked_add(gap)
+                .ok_or_else(|| unsupported_arg("start", timestamp.to_string()))?;
+        }
+        let event_type: Value = types[with_rng(|rng| rng.gen_range(0usize..types.len()))].clone();
+
+        let mut event: Map<String, Value> = Map::new();
+        event.insert(String::from("timestamp"), Value::from(timestamp));
+        event.insert(String::from("type"), event_type);
+        events.push(Value::Object(event));
+    }
+    Ok(Value::Array(events))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::tests::test_tera_rand_function_returns_error;
+    use crate::event::*;
+    use tracing_test::traced_test;
+
+    #[test]
+    #[traced_test]
+    fn test_random_event_sequence_timestamps_are_strictly_increasing() {
+        let args: HashMap<String, Value> = HashMap::from([
+            (String::from("count"), Value::from(50)),
+            (String::from("start"), Value::from(1_700_000_000i64)),
+            (String::from("max_gap"), Value::from(5)),
+            (String::from("types"), Value::from(vec!["login", "logout"])),
+        ]);
+
+        for _ in 0..20 {
+            let events: Value = random_event_sequence(&args).unwrap();
+            let events: &Vec<Value> = events.as_array().unwrap();
+            assert_eq!(events.len(), 50);
+            assert_eq!(events[0]["timestamp"], 1_700_000_000i64);
+
+            for pair in events.windows(2) {
+                let gap: i64 =
+                    pair[1]["timestamp"].as_i64().unwrap() - pair[0]["timestamp"].as_i64().unwrap();
+                assert!((1..=5).contains(&gap));
+            }
+            assert!(events
+                .iter()
+                .all(|event| event["type"] == "login" || event["type"] == "logout"));
+        }
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_event_sequence_defaults_to_now() {
+        let args: HashMap<String, Value> = HashMap::from([(String::from("count"), Value::from(3))]);
+        let before: i64 = current_unix_timestamp().unwrap();
+
+        let events: Value = random_event_sequence(&args).unwrap();
+        let first_timestamp: i64 = events[0]["timestamp"].as_i64().unwrap();
+        assert!(first_timestamp >= before && first_timestamp <= before + 1);
+        assert!(DEFAULT_EVENT_TYPES.contains(&events[0]["type"].as_str().unwrap()));
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_event_sequence_with_invalid_args_returns_error() {
+        for template in [
+            "{{ random_event_sequence() }}",
+            "{{ random_event_sequence(count=1000001) }}",
+            "{{ random_event_sequence(count=3, max_gap=0) }}",
+            "{{ random_event_sequence(count=3, types=[]) }}",
+            "{{ random_event_sequence(count=3, start=9223372036854775807) }}",
+        ] {
+            test_tera_rand_function_returns_error(
+                random_event_sequence,
+                "random_event_sequence",
+                template,
+            );
+        }
+    }
+}
@@ -1,22 +1,84 @@
-use crate::common::parse_arg;
-use crate::error::{empty_file, internal_error, missing_arg, read_file_error};
-use dashmap::mapref::one::Ref;
+use crate::common::{
+    gen_value_in_range, parse_arg, parse_one_or_many_arg, quote_if_requested, sample_indices,
+};
+use crate::error::{
+    conflicting_args, empty_file, internal_error, invalid_weights, missing_arg,
+    no_values_to_sample, not_a_json_array, parse_json_file_error, parse_line_error,
+    parse_weight_error, read_file_error, unsupported_arg,
+};
+use crate::file_cache::{cache_lines, get_cached_lines};
+use crate::geo::clear_geo_ip_files;
+use crate::number::{integer_to_value, number_mode};
+use crate::rng::with_rng;
 use dashmap::DashMap;
 use lazy_static::lazy_static;
-use rand::{thread_rng, Rng};
-use std::collections::HashMap;
-use std::fs::File;
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::Rng;
+use std::collections::{HashMap, HashSet};
+use std::fs::{DirEntry, File};
 use std::io::{BufRead, BufReader};
-use tera::{to_value, Result, Value};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tera::{to_value, Map, Result, Value};
 
 lazy_static! {
-    static ref FILE_CACHE: DashMap<String, Vec<String>> = DashMap::new();
+    static ref WEIGHTED_FILES: DashMap<String, Arc<WeightedValues>> = DashMap::new();
+    static ref BLOCKLISTS: DashMap<String, Arc<HashSet<String>>> = DashMap::new();
+    // keyed by decay and file, along with the line count each index was built for
+    static ref RECENCY_INDEXES: DashMap<String, (usize, Arc<WeightedIndex<f64>>)> = DashMap::new();
+    static ref JSON_ARRAYS: DashMap<String, Arc<Vec<Value>>> = DashMap::new();
+}
+
+#[cfg(feature = "schema")]
+lazy_static! {
+    static ref JSON_DOCUMENTS: DashMap<String, Arc<Value>> = DashMap::new();
 }
 
 /// A Tera function to sample a random value from a line-delimited file of strings. The filepath
 /// should be passed in as an argument to the `path` parameter.
 ///
-/// Note that the contents of the filepath is read only once and cached.
+/// The optional `except` parameter takes either a single String or an array of Strings which
+/// should never be sampled. Similarly, the optional `blocklist_path` parameter takes the path to
+/// a line-delimited file of values which should never be sampled, such as profanity or reserved
+/// words. It is an error for `except` and the blocklist to exclude every line of the file.
+///
+/// Note that the contents of the filepath is read only once and cached. To bound the memory used
+/// by the cache, see [`set_file_cache_max_bytes`]. Pass `cache=false` to read the file again on
+/// every call instead, which picks up lines appended to the file while rendering. Alternatively,
+/// pass `cache_ttl_secs` to read the file again once its cached contents are older than that many
+/// seconds; see also [`set_file_cache_ttl`] to set a default for every file.
+///
+/// For files which another process appends to during a run, such as a log of recently created
+/// IDs, pass `tail=true` along with `cache=false`. Lines are then sampled with a bias toward the
+/// end of the file: each line is weighted by its line number, so the last line is the most likely
+/// to be sampled and the first line is the least likely. Since the whole file is read and weighed
+/// on every call, this is much slower than sampling from a cached file, and it gets slower as the
+/// file grows.
+///
+/// To favor the end of the file more strongly, pass a `decay` between 0 and 1. The last line then
+/// has a weight of 1, and the weight of each line is `decay` times the weight of the line after it.
+/// For example, with `decay=0.5`, the last line is sampled about half of the time. `decay` can be
+/// used with or without `tail=true`; when the file is cached, the weights are computed only once.
+///
+/// For delimited files such as TSV or CSV files, pass the 0-indexed `column` to sample from. Each
+/// sampled line is split on the `delimiter` parameter, which defaults to a tab, and only the value
+/// in that column is returned. Quoted fields are not supported, so a value cannot contain the
+/// delimiter. It is an error for a sampled line to have too few columns. Note that `except`,
+/// `blocklist_path` and `tail` still apply to whole lines.
+///
+/// To ignore lines which should never be sampled, pass `skip_empty=true` to skip blank lines, or a
+/// `comment_prefix` such as `#` to skip lines which begin with it, ignoring leading whitespace.
+/// Lines are skipped as the file is loaded, so skipped lines do not count toward `decay` or `tail`.
+///
+/// For files which encode ranges of integers, such as port ranges, pass `expand_ranges=true`. A
+/// sampled line of the form `N-M`, where `N` and `M` are non-negative integers, then produces a
+/// random integer between `N` and `M`, inclusive, instead of the literal line. Other lines are
+/// returned as-is. It is an error for a sampled range to have `N` greater than `M`.
+///
+/// Pass `quote=true` to render the sampled line as a quoted JSON string literal with any special
+/// characters escaped. This is useful when the lines of the file may contain quotation marks or
+/// backslashes. See the [crate-level documentation](crate#quoting).
 ///
 /// # Example usage
 ///
@@ -31,23 +93,413 @@ lazy_static! {
 /// let rendered: String = tera
 ///     .render_str(r#"{{ random_from_file(path="resources/test/addresses.txt") }}"#, &context)
 ///     .unwrap();
+/// // never sample "Sunday"
+/// let rendered: String = tera
+///     .render_str(
+///         r#"{{ random_from_file(path="resources/test/days.txt", except="Sunday") }}"#,
+///         &context
+///     )
+///     .unwrap();
+/// // favor the most recently appended lines
+/// let rendered: String = tera
+///     .render_str(
+///         r#"{{ random_from_file(path="resources/test/days.txt", tail=true, cache=false) }}"#,
+///         &context
+///     )
+///     .unwrap();
+/// // strongly favor the last lines of the file
+/// let rendered: String = tera
+///     .render_str(
+///         r#"{{ random_from_file(path="resources/test/days.txt", decay=0.5) }}"#,
+///         &context
+///     )
+///     .unwrap();
+/// // ignore blank lines and lines starting with "#"
+/// let rendered: String = tera
+///     .render_str(
+///         r##"{{ random_from_file(path="resources/test/commented_days.txt", skip_empty=true, comment_prefix="#") }}"##,
+///         &context
+///     )
+///     .unwrap();
+/// // sample the second column of a TSV file
+/// let rendered: String = tera
+///     .render_str(
+///         r#"{{ random_from_file(path="resources/test/hosts.tsv", column=1) }}"#,
+///         &context
+///     )
+///     .unwrap();
+/// // sample an integer from lines like "10-20"
+/// let rendered: String = tera
+///     .render_str(
+///         r#"{{ random_from_file(path="resources/test/ranges.txt", expand_ranges=true) }}"#,
+///         &context
+///     )
+///     .unwrap();
 /// ```
+///
+/// [`set_file_cache_max_bytes`]: crate::set_file_cache_max_bytes
+/// [`set_file_cache_ttl`]: crate::set_file_cache_ttl
 pub fn random_from_file(args: &HashMap<String, Value>) -> Result<Value> {
     let filepath: Option<String> = parse_arg(args, "path")?;
     let filepath: String = filepath.ok_or_else(|| missing_arg("path"))?;
+    let except: Vec<String> = parse_one_or_many_arg(args, "except")?.unwrap_or_default();
+    let blocklist_path_opt: Option<String> = parse_arg(args, "blocklist_path")?;
+    let blocklist_opt: Option<Arc<HashSet<String>>> = blocklist_path_opt
+        .map(|blocklist_path| read_blocklist(&blocklist_path))
+        .transpose()?;
+    let cache_opt: Option<bool> = parse_arg(args, "cache")?;
+    let tail: bool = parse_arg(args, "tail")?.unwrap_or(false);
+    let cache: bool = cache_opt.unwrap_or(true);
+    if tail && cache {
+        return Err(conflicting_args("tail", "cache"));
+    }
+    let decay_opt: Option<f64> = parse_arg(args, "decay")?;
+    if let Some(decay) = decay_opt.filter(|decay| !(*decay > 0.0 && *decay <= 1.0)) {
+        return Err(unsupported_arg("decay", decay.to_string()));
+    }
+    let expand_ranges: bool = parse_arg(args, "expand_ranges")?.unwrap_or(false);
+    let column_opt: Option<usize> = parse_arg(args, "column")?;
+    let delimiter_opt: Option<String> = parse_arg(args, "delimiter")?;
+    if delimiter_opt.is_some() && column_opt.is_none() {
+        return Err(missing_arg("column"));
+    }
+    let delimiter: String = delimiter_opt.unwrap_or_else(|| String::from("\t"));
+    if delimiter.is_empty() {
+        return Err(unsupported_arg("delimiter", delimiter));
+    }
+    let line_filter: LineFilter = LineFilter::from_args(args)?;
+    let cache_ttl_secs_opt: Option<f64> = parse_arg(args, "cache_ttl_secs")?;
+    if cache_ttl_secs_opt.is_some() && !cache {
+        return Err(conflicting_args("cache_ttl_secs", "cache"));
+    }
+    let cache_ttl_opt: Option<Duration> = cache_ttl_secs_opt
+        .map(|cache_ttl_secs| {
+            Duration::try_from_secs_f64(cache_ttl_secs)
+                .map_err(|_| unsupported_arg("cache_ttl_secs", cache_ttl_secs.to_string()))
+        })
+        .transpose()?;
+
+    let possible_values: Arc<Vec<String>> =
+        read_filtered_file_lines(&filepath, &line_filter, cache, cache_ttl_opt)?;
+
+    let line_count: usize = possible_values.len();
+    let index_to_sample: usize = match decay_opt {
+        Some(decay) if cache && except.is_empty() && blocklist_opt.is_none() => {
+            let recency_index: Arc<WeightedIndex<f64>> =
+                read_recency_index(&line_filter.cache_key(&filepath), line_count, decay)?;
+            with_rng(|rng| recency_index.sample(rng))
+        }
+        Some(decay) => sample_recent_index_except(
+            &possible_values,
+            &except,
+            blocklist_opt.as_deref(),
+            |index| exponential_recency_weight(index, line_count, decay),
+        )?,
+        None if tail => sample_recent_index_except(
+            &possible_values,
+            &except,
+            blocklist_opt.as_deref(),
+            |index| (index + 1) as f64,
+        )?,
+        None => sample_index_except(&possible_values, &except, blocklist_opt.as_deref())?,
+    };
+    let line: &str = get_line(&filepath, &possible_values, index_to_sample)?;
+    let sampled_value: &str = match column_opt {
+        Some(column) => line.split(delimiter.as_str()).nth(column).ok_or_else(|| {
+            internal_error(format!(
+                "Unable to sample column {column} from line `{line}` in file at path {filepath}, \
+                 which has fewer columns"
+            ))
+        })?,
+        None => line,
+    };
+    let range_opt: Option<(u64, u64)> = Some(sampled_value)
+        .filter(|_| expand_ranges)
+        .and_then(parse_integer_range);
+    let json_value: Value = match range_opt {
+        Some((start, end)) => integer_to_value(
+            gen_value_in_range(Some(start), Some(end), u64::MIN, u64::MAX)?,
+            number_mode(),
+        ),
+        None => to_value(sampled_value)?,
+    };
+    quote_if_requested(args, json_value)
+}
+
+// Parse a line of the form `N-M`, where `N` and `M` are non-negative integers, into `(N, M)`.
+// Return `None` for any other line.
+fn parse_integer_range(line: &str) -> Option<(u64, u64)> {
+    let (start, end): (&str, &str) = line.trim().split_once('-')?;
+    let is_integer = |bound: &str| !bound.is_empty() && bound.bytes().all(|b| b.is_ascii_digit());
+    if !is_integer(start) || !is_integer(end) {
+        return None;
+    }
+    Some((start.parse::<u64>().ok()?, end.parse::<u64>().ok()?))
+}
+
+/// A Tera function to sample a random line from all of the files under a directory, including
+/// files in nested directories. The directory path should be passed in as an argument to the
+/// `root` parameter.
+///
+/// The lines of every file are pooled together and sampled uniformly, so a file with more lines
+/// is more likely to be sampled from. The optional `extension` parameter restricts the pool to
+/// files with the given extension, such as `txt`; a leading `.` is ignored. Symbolic links to
+/// directories are not followed.
+///
+/// Note that the directory is walked and its files are read only once, and the pooled lines are
+/// cached like the contents of a single file.
+///
+/// Pass `quote=true` to render the sampled line as a quoted JSON string literal.
+///
+/// # Example usage
+///
+/// ```edition2021
+/// use tera::{Context, Tera};
+/// use tera_rand::random_from_tree;
+///
+/// let mut tera: Tera = Tera::default();
+/// tera.register_function("random_from_tree", random_from_tree);
+/// let context: Context = Context::new();
+///
+/// let rendered: String = tera
+///     .render_str(r#"{{ random_from_tree(root="resources/test/tree") }}"#, &context)
+///     .unwrap();
+/// // only sample from .txt files
+/// let rendered: String = tera
+///     .render_str(
+///         r#"{{ random_from_tree(root="resources/test/tree", extension="txt") }}"#,
+///         &context
+///     )
+///     .unwrap();
+/// ```
+pub fn random_from_tree(args: &HashMap<String, Value>) -> Result<Value> {
+    let root_opt: Option<String> = parse_arg(args, "root")?;
+    let root: String = root_opt.ok_or_else(|| missing_arg("root"))?;
+    let extension_opt: Option<String> = parse_arg(args, "extension")?;
+    let extension_opt: Option<&str> = extension_opt
+        .as_deref()
+        .map(|extension| extension.trim_start_matches('.'));
+
+    let cache_key: String = format!(
+        "random_from_tree:{}:{root}",
+        extension_opt.unwrap_or_default()
+    );
+    let possible_values: Arc<Vec<String>> = match get_cached_lines(&cache_key, None)? {
+        Some(possible_values) => possible_values,
+        None => cache_lines(cache_key, read_tree_lines(&root, extension_opt)?)?,
+    };
+
+    let index_to_sample: usize = with_rng(|rng| rng.gen_range(0usize..possible_values.len()));
+    let json_value: Value = convert_line_to_json_value(&root, &possible_values, index_to_sample)?;
+    quote_if_requested(args, json_value)
+}
+
+/// A Tera function to sample several distinct lines from a line-delimited file of strings,
+/// without replacement. The filepath should be passed in as an argument to the `path` parameter,
+/// and the number of lines to sample should be passed in as an argument to the `count` parameter.
+/// It is an error for `count` to be larger than the number of lines in the file.
+///
+/// The lines are returned as an array in a random order. Under a fixed seed (see [`set_seed`]),
+/// both the chosen lines and their order are reproducible.
+///
+/// Note that the contents of the filepath is read only once and cached.
+///
+/// # Example usage
+///
+/// ```edition2021
+/// use tera::{Context, Tera};
+/// use tera_rand::random_sample_from_file;
+///
+/// let mut tera: Tera = Tera::default();
+/// tera.register_function("random_sample_from_file", random_sample_from_file);
+/// let context: Context = Context::new();
+///
+/// let rendered: String = tera
+///     .render_str(
+///         r#"{{ random_sample_from_file(path="resources/test/days.txt", count=3) | join(sep=", ") }}"#,
+///         &context
+///     )
+///     .unwrap();
+/// ```
+///
+/// [`set_seed`]: crate::set_seed
+pub fn random_sample_from_file(args: &HashMap<String, Value>) -> Result<Value> {
+    let filepath_opt: Option<String> = parse_arg(args, "path")?;
+    let filepath: String = filepath_opt.ok_or_else(|| missing_arg("path"))?;
+    let count_opt: Option<usize> = parse_arg(args, "count")?;
+    let count: usize = count_opt.ok_or_else(|| missing_arg("count"))?;
+
+    let possible_values: Arc<Vec<String>> = read_all_file_lines(&filepath)?;
+
+    let sample: Vec<Value> = sample_indices(possible_values.len(), count)?
+        .into_iter()
+        .map(|index| Value::from(possible_values[index].as_str()))
+        .collect();
+    Ok(Value::Array(sample))
+}
+
+/// A Tera function to sample a random word from a word frequency file, such as a unigram frequency
+/// list, in proportion to each word's frequency. The filepath should be passed in as an argument to
+/// the `path` parameter.
+///
+/// Each line of the file should contain a word followed by its frequency, separated by whitespace:
+/// ```text
+/// the 23135851162
+/// of 13151942776
+/// quixotic 25416
+/// ```
+/// Blank lines are skipped, and any other line which does not have exactly this form results in an
+/// error.
+///
+/// Note that the file is read and parsed only once, and the parsed words and their cumulative
+/// frequencies are cached.
+///
+/// Pass `quote=true` to render the word wrapped in JSON quotation marks.
+///
+/// # Example usage
+///
+/// ```edition2021
+/// use tera::{Context, Tera};
+/// use tera_rand::random_frequent_word;
+///
+/// let mut tera: Tera = Tera::default();
+/// tera.register_function("random_frequent_word", random_frequent_word);
+/// let context: Context = Context::new();
+///
+/// let rendered: String = tera
+///     .render_str(
+///         r#"{{ random_frequent_word(path="resources/test/word_frequencies.txt") }}"#,
+///         &context
+///     )
+///     .unwrap();
+/// ```
+pub fn random_frequent_word(args: &HashMap<String, Value>) -> Result<Value> {
+    let filepath_opt: Option<String> = parse_arg(args, "path")?;
+    let filepath: String = filepath_opt.ok_or_else(|| missing_arg("path"))?;
+
+    let cache_key: String = format!("random_frequent_word:{filepath}");
+    let words: Arc<WeightedValues> = read_weighted_file(&filepath, cache_key, |_, line| {
+        parse_word_frequency_line(line)
+            .ok_or_else(|| parse_line_error(filepath.clone(), line.to_string()))
+    })?;
+
+    let json_value: Value = to_value(words.sample())?;
+    quote_if_requested(args, json_value)
+}
 
-    let possible_values_ref: Ref<String, Vec<String>> = read_all_file_lines(filepath)?;
-    let possible_values: &Vec<String> = possible_values_ref.value();
+/// A Tera function to sample a random value from a file of weighted values, in proportion to each
+/// value's weight. The filepath should be passed in as an argument to the `path` parameter.
+///
+/// Each line of the file should contain a non-negative weight followed by its value, separated by
+/// the `delimiter` parameter, which defaults to a tab. For example, with `delimiter=","`:
+/// ```text
+/// 90,GET
+/// 9,POST
+/// 1,DELETE
+/// ```
+/// Only the first delimiter on a line separates the weight from the value, so a value may itself
+/// contain the delimiter. Blank lines are skipped, a line without the delimiter results in an
+/// error, and a weight which is not a number results in an error when the file is loaded.
+///
+/// Note that the file is read and parsed only once, and the parsed values and their weights are
+/// cached.
+///
+/// Pass `quote=true` to render the value wrapped in JSON quotation marks.
+///
+/// # Example usage
+///
+/// ```edition2021
+/// use tera::{Context, Tera};
+/// use tera_rand::weighted_from_file;
+///
+/// let mut tera: Tera = Tera::default();
+/// tera.register_function("weighted_from_file", weighted_from_file);
+/// let context: Context = Context::new();
+///
+/// let rendered: String = tera
+///     .render_str(
+///         r#"{{ weighted_from_file(path="resources/test/http_methods.tsv") }}"#,
+///         &context
+///     )
+///     .unwrap();
+/// ```
+pub fn weighted_from_file(args: &HashMap<String, Value>) -> Result<Value> {
+    let filepath_opt: Option<String> = parse_arg(args, "path")?;
+    let filepath: String = filepath_opt.ok_or_else(|| missing_arg("path"))?;
+    let delimiter: String = parse_arg(args, "delimiter")?.unwrap_or_else(|| String::from("\t"));
+    if delimiter.is_empty() {
+        return Err(unsupported_arg("delimiter", delimiter));
+    }
+
+    let cache_key: String = format!("weighted_from_file:{delimiter}:{filepath}");
+    let values: Arc<WeightedValues> =
+        read_weighted_file(&filepath, cache_key, |line_num, line| {
+            let (weight, value): (&str, &str) = line
+                .split_once(delimiter.as_str())
+                .ok_or_else(|| parse_line_error(filepath.clone(), line.to_string()))?;
+            let weight: f64 = weight.trim().parse().map_err(|_| {
+                parse_weight_error(
+                    filepath.clone(),
+                    line_num,
+                    line.to_string(),
+                    weight.to_string(),
+                )
+            })?;
+            Ok((value.to_string(), weight))
+        })?;
+
+    let json_value: Value = to_value(values.sample())?;
+    quote_if_requested(args, json_value)
+}
+
+/// A Tera function to sample a random element from a JSON file containing an array. The filepath
+/// should be passed in as an argument to the `path` parameter.
+///
+/// The sampled element keeps its JSON type, so numbers stay numbers and objects stay objects. To
+/// sample from an array nested inside the file, pass a [JSON Pointer] such as `/data/items` to the
+/// optional `pointer` parameter. It is an error for the file to not be valid JSON, for the pointer
+/// to not refer to an array, or for the array to be empty.
+///
+/// Note that the file is read and parsed only once, and the selected array is cached.
+///
+/// # Example usage
+///
+/// ```edition2021
+/// use tera::{Context, Tera};
+/// use tera_rand::random_from_json_file;
+///
+/// let mut tera: Tera = Tera::default();
+/// tera.register_function("random_from_json_file", random_from_json_file);
+/// let context: Context = Context::new();
+///
+/// let rendered: String = tera
+///     .render_str(
+///         r#"{{ random_from_json_file(path="resources/test/servers.json", pointer="/data/items") | json_encode() }}"#,
+///         &context
+///     )
+///     .unwrap();
+/// ```
+///
+/// [JSON Pointer]: https://datatracker.ietf.org/doc/html/rfc6901
+pub fn random_from_json_file(args: &HashMap<String, Value>) -> Result<Value> {
+    let filepath_opt: Option<String> = parse_arg(args, "path")?;
+    let filepath: String = filepath_opt.ok_or_else(|| missing_arg("path"))?;
+    let pointer: String = parse_arg(args, "pointer")?.unwrap_or_default();
 
-    let index_to_sample: usize = thread_rng().gen_range(0usize..possible_values.len());
-    convert_line_to_json_value(possible_values_ref.key(), possible_values, index_to_sample)
+    let elements: Arc<Vec<Value>> = read_json_array_file(&filepath, &pointer)?;
+    let index: usize = with_rng(|rng| rng.gen_range(0..elements.len()));
+    Ok(elements[index].clone())
 }
 
 /// A Tera function to sample a specific value from a line-delimited file of strings. The filepath
 /// should be passed in as an argument to the `path` parameter. The 0-indexed line number should
 /// be passed in as an argument to the `line_num` parameter.
 ///
-/// Note that the contents of the filepath is read only once and cached.
+/// Note that the contents of the filepath is read only once and cached. Pass `cache=false` to read
+/// the file again on every call, such as when it changes while the program runs.
+///
+/// Pass `quote=true` to render the line as a quoted JSON string literal with any special characters
+/// escaped.
 ///
 /// # Example usage
 ///
@@ -70,83 +522,1240 @@ pub fn line_from_file(args: &HashMap<String, Value>) -> Result<Value> {
 
     let line_num: Option<usize> = parse_arg(args, "line_num")?;
     let line_num: usize = line_num.ok_or_else(|| missing_arg("line_num"))?;
+    let cache: bool = parse_arg(args, "cache")?.unwrap_or(true);
 
-    let possible_values_ref = read_all_file_lines(filepath)?;
-    let possible_values: &Vec<String> = possible_values_ref.value();
+    let possible_values: Arc<Vec<String>> = if cache {
+        read_all_file_lines(&filepath)?
+    } else {
+        Arc::new(read_file_lines(&filepath)?)
+    };
 
-    convert_line_to_json_value(possible_values_ref.key(), possible_values, line_num)
+    let json_value: Value = convert_line_to_json_value(&filepath, &possible_values, line_num)?;
+    quote_if_requested(args, json_value)
 }
 
-fn convert_line_to_json_value(
-    filename: &String,
-    possible_values: &Vec<String>,
-    line_num: usize
-) -> Result<Value> {
-    match possible_values.get(line_num) {
-        Some(sampled_value) => {
-            let json_value = to_value(sampled_value)?;
-            Ok(json_value)
+/// A Tera function to sample a random row from a delimited file such as a CSV file. The filepath
+/// should be passed in as an argument to the `path` parameter.
+///
+/// The first line of the file is treated as a header which names the columns. Every other line is
+/// a row. Fields are separated by the `delimiter` parameter, which defaults to `,`. Quoted fields
+/// are not supported, so a field cannot contain the delimiter.
+///
+/// By default, the sampled row is returned as an object mapping each column name to its value.
+///
+/// If the `columns` parameter is passed an array of column names, then only those columns of the
+/// sampled row are returned, joined together into a single String by the `join` parameter, which
+/// defaults to `,`. Because every column comes from the same row, this is useful for composite
+/// values such as `WA-98101`, which could not be built from two independent samples.
+///
+/// Note that the contents of the filepath is read only once and cached.
+///
+/// When `columns` is passed, `quote=true` renders the joined String as a quoted JSON string literal
+/// with any special characters escaped. It has no effect on the object returned otherwise.
+///
+/// # Example usage
+///
+/// ```edition2021
+/// use tera::{Context, Tera};
+/// use tera_rand::random_from_csv;
+///
+/// let mut tera: Tera = Tera::default();
+/// tera.register_function("random_from_csv", random_from_csv);
+/// let context: Context = Context::new();
+///
+/// // sample a whole row
+/// let rendered: String = tera
+///     .render_str(
+///         r#"{% set row = random_from_csv(path="resources/test/zip_codes.csv") %}{{ row.city }}"#,
+///         &context
+///     )
+///     .unwrap();
+/// // join two columns of the same row
+/// let rendered: String = tera
+///     .render_str(
+///         r#"{{ random_from_csv(path="resources/test/zip_codes.csv", columns=["state", "zip"], join="-") }}"#,
+///         &context
+///     )
+///     .unwrap();
+/// ```
+pub fn random_from_csv(args: &HashMap<String, Value>) -> Result<Value> {
+    let filepath_opt: Option<String> = parse_arg(args, "path")?;
+    let filepath: String = filepath_opt.ok_or_else(|| missing_arg("path"))?;
+    let delimiter: String = parse_arg(args, "delimiter")?.unwrap_or_else(|| String::from(","));
+    let columns_opt: Option<Vec<String>> = parse_arg(args, "columns")?;
+    let join: String = parse_arg(args, "join")?.unwrap_or_else(|| String::from(","));
+
+    let lines: Arc<Vec<String>> = read_all_file_lines(&filepath)?;
+    if lines.len() < 2 {
+        return Err(empty_file(filepath));
+    }
+
+    let header: Vec<&str> = lines[0].split(delimiter.as_str()).collect();
+    let row_line: &String = &lines[with_rng(|rng| rng.gen_range(1usize..lines.len()))];
+    let row: Vec<&str> = row_line.split(delimiter.as_str()).collect();
+    if row.len() != header.len() {
+        return Err(parse_line_error(filepath, row_line.clone()));
+    }
+
+    match columns_opt {
+        Some(columns) => {
+            let selected_fields: Vec<&str> = columns
+                .into_iter()
+                .map(
+                    |column| match header.iter().position(|name| *name == column) {
+                        Some(column_index) => Ok(row[column_index]),
+                        None => Err(unsupported_arg("columns", column)),
+                    },
+                )
+                .collect::<Result<Vec<&str>>>()?;
+            let json_value: Value = to_value(selected_fields.join(join.as_str()))?;
+            quote_if_requested(args, json_value)
         }
         None => {
-            Err(internal_error(format!(
-                "Unable to sample value with line number {} for file at path {}",
-                line_num, filename
-            )))
-        },
+            let row_object: Map<String, Value> = header
+                .iter()
+                .zip(row)
+                .map(|(name, field)| (name.to_string(), Value::from(field)))
+                .collect();
+            Ok(Value::Object(row_object))
+        }
     }
 }
 
-// Read the entire file in and store the individual lines if we haven't seen it before.
-// Otherwise, return the existing lines.
-fn read_all_file_lines<'a>(filepath: String) -> Result<Ref<'a, String, Vec<String>>> {
-    if !FILE_CACHE.contains_key(&filepath) {
-        let input_file: File =
-            File::open(&filepath).map_err(|source| read_file_error(filepath.clone(), source))?;
-        let buf_reader: BufReader<File> = BufReader::new(input_file);
+// Sample the index of a random line whose value is neither in `except` nor in `blocklist_opt`.
+fn sample_index_except(
+    possible_values: &[String],
+    except: &[String],
+    blocklist_opt: Option<&HashSet<String>>,
+) -> Result<usize> {
+    if except.is_empty() && blocklist_opt.is_none() {
+        return Ok(with_rng(|rng| rng.gen_range(0usize..possible_values.len())));
+    }
 
-        let mut file_values: Vec<String> = Vec::new();
-        for line_result in buf_reader.lines() {
-            let line: String =
-                line_result.map_err(|source| read_file_error(filepath.clone(), source))?;
-            file_values.push(line);
-        }
+    let is_excluded = |value: &String| {
+        except.contains(value) || blocklist_opt.is_some_and(|blocklist| blocklist.contains(value))
+    };
+    let candidate_indices: Vec<usize> = (0usize..possible_values.len())
+        .filter(|index| !is_excluded(&possible_values[*index]))
+        .collect();
+    if candidate_indices.is_empty() {
+        return Err(no_values_to_sample("except"));
+    }
+    Ok(candidate_indices[with_rng(|rng| rng.gen_range(0usize..candidate_indices.len()))])
+}
 
-        if file_values.is_empty() {
-            return Err(empty_file(filepath));
-        }
-        FILE_CACHE.insert(filepath.clone(), file_values);
+// Like `sample_index_except`, but weigh each line by `weight_of_index`, which is passed the line's
+// 0-indexed line number.
+fn sample_recent_index_except<F>(
+    possible_values: &[String],
+    except: &[String],
+    blocklist_opt: Option<&HashSet<String>>,
+    weight_of_index: F,
+) -> Result<usize>
+where
+    F: Fn(usize) -> f64,
+{
+    let is_excluded = |value: &String| {
+        except.contains(value) || blocklist_opt.is_some_and(|blocklist| blocklist.contains(value))
+    };
+    let candidate_indices: Vec<usize> = (0usize..possible_values.len())
+        .filter(|index| !is_excluded(&possible_values[*index]))
+        .collect();
+    if candidate_indices.is_empty() {
+        return Err(no_values_to_sample("except"));
     }
-    FILE_CACHE.get(&filepath)
-        .ok_or_else(|| internal_error(
-            format!("File cache did not contain an entry for file {filepath}")
-        ))
+
+    let weighted_index: WeightedIndex<f64> = WeightedIndex::new(
+        candidate_indices
+            .iter()
+            .map(|index| weight_of_index(*index)),
+    )
+    .map_err(|source| invalid_weights(source.to_string()))?;
+    Ok(candidate_indices[with_rng(|rng| weighted_index.sample(rng))])
 }
 
-#[cfg(test)]
-mod tests {
-    use crate::common::tests::{test_tera_rand_function, test_tera_rand_function_returns_error};
-    use crate::file::*;
-    use tracing_test::traced_test;
+// The weight of a line which decays exponentially with its distance from the last line.
+fn exponential_recency_weight(index: usize, line_count: usize, decay: f64) -> f64 {
+    let distance_from_last_line: i32 = (line_count - 1 - index).try_into().unwrap_or(i32::MAX);
+    decay.powi(distance_from_last_line)
+}
 
-    #[test]
-    #[traced_test]
-    fn test_random_from_file() {
-        test_tera_rand_function(
-            random_from_file,
-            "random_from_file",
-            r#"{ "some_field": "{{ random_from_file(path="resources/test/days.txt") }}" }"#,
-            r#"\{ "some_field": "(Monday|Tuesday|Wednesday|Thursday|Friday|Saturday|Sunday)" }"#,
+// Return the index over exponential recency weights for every line of the file, computing and
+// caching it if it is not already cached.
+fn read_recency_index(
+    filepath: &str,
+    line_count: usize,
+    decay: f64,
+) -> Result<Arc<WeightedIndex<f64>>> {
+    // an index built for a different line count, such as before the file grew and its cache entry
+    // expired, is replaced rather than kept alongside the new one
+    let cache_key: String = format!("{decay}:{filepath}");
+    if let Some(entry) = RECENCY_INDEXES.get(&cache_key) {
+        let (indexed_line_count, recency_index) = entry.value();
+        if *indexed_line_count == line_count {
+            return Ok(recency_index.clone());
+        }
+    }
+
+    let recency_index: Arc<WeightedIndex<f64>> = Arc::new(
+        WeightedIndex::new(
+            (0..line_count).map(|index| exponential_recency_weight(index, line_count, decay)),
         )
+        .map_err(|source| invalid_weights(source.to_string()))?,
+    );
+    RECENCY_INDEXES.insert(cache_key, (line_count, recency_index.clone()));
+    Ok(recency_index)
+}
+
+fn convert_line_to_json_value(
+    filename: &str,
+    possible_values: &[String],
+    line_num: usize,
+) -> Result<Value> {
+    let json_value = to_value(get_line(filename, possible_values, line_num)?)?;
+    Ok(json_value)
+}
+
+fn get_line<'a>(filename: &str, possible_values: &'a [String], line_num: usize) -> Result<&'a str> {
+    match possible_values.get(line_num) {
+        Some(sampled_value) => Ok(sampled_value),
+        None => Err(internal_error(format!(
+            "Unable to sample value with line number {} for file at path {}",
+            line_num, filename
+        ))),
     }
+}
 
-    #[test]
-    #[traced_test]
-    fn test_with_file_with_one_item() {
-        test_tera_rand_function(
-            random_from_file,
-            "random_from_file",
-            r#"{ "some_field": "{{ random_from_file(path="resources/test/file_with_one_item.txt") }}" }"#,
-            r#"\{ "some_field": "item" }"#,
+// The values of a weighted file along with an index over their cumulative weights.
+#[derive(Debug)]
+pub(crate) struct WeightedValues {
+    values: Vec<String>,
+    weighted_index: WeightedIndex<f64>,
+}
+
+impl WeightedValues {
+    // Sample a value with probability proportional to its weight.
+    pub(crate) fn sample(&self) -> &str {
+        &self.values[with_rng(|rng| self.weighted_index.sample(rng))]
+    }
+}
+
+// Read a file of weighted values, splitting each non-blank line into a value and its weight with
+// `parse_line`, which is also passed the line's number, counting from 1. The parsed values are
+// cached under `cache_key`. If `cache_key` is already cached, the file is not read again. The
+// first error returned by `parse_line` is returned as is.
+pub(crate) fn read_weighted_file<F>(
+    filepath: &str,
+    cache_key: String,
+    parse_line: F,
+) -> Result<Arc<WeightedValues>>
+where
+    F: Fn(usize, &str) -> Result<(String, f64)>,
+{
+    if let Some(weighted_values) = WEIGHTED_FILES.get(&cache_key) {
+        return Ok(weighted_values.clone());
+    }
+
+    let lines: Arc<Vec<String>> = read_all_file_lines(filepath)?;
+    let mut values: Vec<String> = Vec::with_capacity(lines.len());
+    let mut weights: Vec<f64> = Vec::with_capacity(lines.len());
+    for (line_index, line) in lines.iter().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let (value, weight): (String, f64) = parse_line(line_index + 1, line)?;
+        values.push(value);
+        weights.push(weight);
+    }
+    if values.is_empty() {
+        return Err(empty_file(filepath.to_string()));
+    }
+
+    let weighted_index: WeightedIndex<f64> =
+        WeightedIndex::new(weights).map_err(|source| invalid_weights(source.to_string()))?;
+    let weighted_values: Arc<WeightedValues> = Arc::new(WeightedValues {
+        values,
+        weighted_index,
+    });
+    WEIGHTED_FILES.insert(cache_key, weighted_values.clone());
+    Ok(weighted_values)
+}
+
+// Parse a line of the form `word frequency`.
+fn parse_word_frequency_line(line: &str) -> Option<(String, f64)> {
+    let mut fields = line.split_whitespace();
+    let word: &str = fields.next()?;
+    let frequency: f64 = fields.next()?.parse().ok()?;
+    if fields.next().is_some() {
+        return None;
+    }
+    Some((word.to_string(), frequency))
+}
+
+// Forget every value parsed from the contents of a file, for `clear_file_cache`.
+pub(crate) fn clear_parsed_file_caches() {
+    WEIGHTED_FILES.clear();
+    BLOCKLISTS.clear();
+    RECENCY_INDEXES.clear();
+    JSON_ARRAYS.clear();
+    clear_geo_ip_files();
+    #[cfg(feature = "schema")]
+    JSON_DOCUMENTS.clear();
+}
+
+// Read and parse a JSON file, and cache the document. If the document is already cached, the file
+// is not read again.
+#[cfg(feature = "schema")]
+pub(crate) fn read_json_document(filepath: &str) -> Result<Arc<Value>> {
+    if let Some(document) = JSON_DOCUMENTS.get(filepath) {
+        return Ok(document.clone());
+    }
+
+    let document: Arc<Value> = Arc::new(parse_json_file(filepath)?);
+    JSON_DOCUMENTS.insert(filepath.to_string(), document.clone());
+    Ok(document)
+}
+
+fn parse_json_file(filepath: &str) -> Result<Value> {
+    let contents: String = std::fs::read_to_string(filepath)
+        .map_err(|source| read_file_error(filepath.to_string(), source))?;
+    contents
+        .parse()
+        .map_err(|source| parse_json_file_error(filepath.to_string(), source))
+}
+
+// Read and parse a JSON file, and cache the non-empty array which `pointer` refers to within it.
+// If the array is already cached, the file is not read again.
+fn read_json_array_file(filepath: &str, pointer: &str) -> Result<Arc<Vec<Value>>> {
+    let cache_key: String = format!("{pointer}:{filepath}");
+    if let Some(elements) = JSON_ARRAYS.get(&cache_key) {
+        return Ok(elements.clone());
+    }
+
+    let document: Value = parse_json_file(filepath)?;
+    let elements: Vec<Value> = match document.pointer(pointer) {
+        Some(Value::Array(elements)) => elements.clone(),
+        _ => return Err(not_a_json_array(filepath.to_string(), pointer.to_string())),
+    };
+    if elements.is_empty() {
+        return Err(empty_file(filepath.to_string()));
+    }
+
+    let elements: Arc<Vec<Value>> = Arc::new(elements);
+    JSON_ARRAYS.insert(cache_key, elements.clone());
+    Ok(elements)
+}
+
+// Read a line-delimited file of values which should never be generated, caching the values as a
+// set so that generated values can be checked against it quickly.
+pub(crate) fn read_blocklist(filepath: &str) -> Result<Arc<HashSet<String>>> {
+    if let Some(blocklist) = BLOCKLISTS.get(filepath) {
+        return Ok(blocklist.clone());
+    }
+
+    let lines: Arc<Vec<String>> = read_all_file_lines(filepath)?;
+    let blocklist: Arc<HashSet<String>> = Arc::new(lines.iter().cloned().collect());
+    BLOCKLISTS.insert(filepath.to_string(), blocklist.clone());
+    Ok(blocklist)
+}
+
+// Which lines of a file to skip as it is loaded, so that they are never sampled.
+#[derive(Debug)]
+struct LineFilter {
+    skip_empty: bool,
+    comment_prefix: Option<String>,
+}
+
+impl LineFilter {
+    fn from_args(args: &HashMap<String, Value>) -> Result<Self> {
+        let skip_empty: bool = parse_arg(args, "skip_empty")?.unwrap_or(false);
+        let comment_prefix: Option<String> = parse_arg(args, "comment_prefix")?;
+        if let Some(comment_prefix) = comment_prefix.as_ref().filter(|prefix| prefix.is_empty()) {
+            return Err(unsupported_arg("comment_prefix", comment_prefix.clone()));
+        }
+        Ok(LineFilter {
+            skip_empty,
+            comment_prefix,
+        })
+    }
+
+    fn keeps_all(&self) -> bool {
+        !self.skip_empty && self.comment_prefix.is_none()
+    }
+
+    fn keeps(&self, line: &str) -> bool {
+        let is_empty: bool = self.skip_empty && line.trim().is_empty();
+        let is_comment: bool = self
+            .comment_prefix
+            .as_ref()
+            .is_some_and(|prefix| line.trim_start().starts_with(prefix.as_str()));
+        !is_empty && !is_comment
+    }
+
+    // The key to cache the filtered lines of `filepath` under. Lines filtered differently are
+    // cached separately, and a NUL byte, which cannot appear in a path, separates the options
+    // from the path.
+    fn cache_key(&self, filepath: &str) -> String {
+        if self.keeps_all() {
+            return filepath.to_string();
+        }
+        format!(
+            "{filepath}\0skip_empty={}\0comment_prefix={:?}",
+            self.skip_empty, self.comment_prefix
+        )
+    }
+}
+
+// Read the lines of the file which `line_filter` keeps, consulting and updating the cache only if
+// `cache` is true. Cached lines older than `cache_ttl` are read again. It is an error for the
+// filter to skip every line of the file.
+fn read_filtered_file_lines(
+    filepath: &str,
+    line_filter: &LineFilter,
+    cache: bool,
+    cache_ttl: Option<Duration>,
+) -> Result<Arc<Vec<String>>> {
+    let cache_key: String = line_filter.cache_key(filepath);
+    if cache {
+        if let Some(lines) = get_cached_lines(&cache_key, cache_ttl)? {
+            return Ok(lines);
+        }
+    }
+    let lines: Vec<String> = read_possibly_empty_file_lines(filepath)?
+        .into_iter()
+        .filter(|line| line_filter.keeps(line))
+        .collect();
+    if lines.is_empty() {
+        return Err(empty_file(filepath.to_string()));
+    }
+    if cache {
+        cache_lines(cache_key, lines)
+    } else {
+        Ok(Arc::new(lines))
+    }
+}
+
+// Read the entire file in and store the individual lines if they are not already cached.
+// Otherwise, return the cached lines.
+pub(crate) fn read_all_file_lines(filepath: &str) -> Result<Arc<Vec<String>>> {
+    if let Some(file_values) = get_cached_lines(filepath, None)? {
+        return Ok(file_values);
+    }
+
+    let file_values: Vec<String> = read_file_lines(filepath)?;
+    cache_lines(filepath.to_string(), file_values)
+}
+
+// Read the entire file in without consulting or updating the cache.
+fn read_file_lines(filepath: &str) -> Result<Vec<String>> {
+    let file_values: Vec<String> = read_possibly_empty_file_lines(filepath)?;
+    if file_values.is_empty() {
+        return Err(empty_file(filepath.to_string()));
+    }
+    Ok(file_values)
+}
+
+fn read_possibly_empty_file_lines(filepath: &str) -> Result<Vec<String>> {
+    let input_file: File =
+        File::open(filepath).map_err(|source| read_file_error(filepath.to_string(), source))?;
+    let buf_reader: BufReader<File> = BufReader::new(input_file);
+
+    let mut file_values: Vec<String> = Vec::new();
+    for line_result in buf_reader.lines() {
+        let line: String =
+            line_result.map_err(|source| read_file_error(filepath.to_string(), source))?;
+        file_values.push(line);
+    }
+    Ok(file_values)
+}
+
+// Read the lines of every file under `root`, or only the files with the given extension. Files
+// are read in order of their paths, so that sampling is reproducible under a fixed seed.
+fn read_tree_lines(root: &str, extension_opt: Option<&str>) -> Result<Vec<String>> {
+    let mut filepaths: Vec<PathBuf> = Vec::new();
+    collect_filepaths(Path::new(root), &mut filepaths)?;
+    filepaths.sort();
+
+    let mut tree_values: Vec<String> = Vec::new();
+    for filepath in filepaths {
+        let has_extension: bool = match extension_opt {
+            Some(extension) => filepath
+                .extension()
+                .is_some_and(|file_extension| file_extension == extension),
+            None => true,
+        };
+        if has_extension {
+            tree_values.extend(read_possibly_empty_file_lines(&filepath.to_string_lossy())?);
+        }
+    }
+
+    if tree_values.is_empty() {
+        return Err(empty_file(root.to_string()));
+    }
+    Ok(tree_values)
+}
+
+// Recursively collect the paths of the files under `directory`.
+fn collect_filepaths(directory: &Path, filepaths: &mut Vec<PathBuf>) -> Result<()> {
+    let read_error =
+        |source: std::io::Error| read_file_error(directory.display().to_string(), source);
+    for entry_result in std::fs::read_dir(directory).map_err(read_error)? {
+        let entry: DirEntry = entry_result.map_err(read_error)?;
+        let path: PathBuf = entry.path();
+        if entry.file_type().map_err(read_error)?.is_dir() {
+            collect_filepaths(&path, filepaths)?;
+        } else if path.is_file() {
+            filepaths.push(path);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::tests::{test_tera_rand_function, test_tera_rand_function_returns_error};
+    use crate::file::*;
+    use crate::file_cache::clear_file_cache;
+    use crate::rng::{clear_seed, set_seed};
+    use tracing_test::traced_test;
+
+    #[test]
+    #[traced_test]
+    fn test_random_from_file() {
+        test_tera_rand_function(
+            random_from_file,
+            "random_from_file",
+            r#"{ "some_field": "{{ random_from_file(path="resources/test/days.txt") }}" }"#,
+            r#"\{ "some_field": "(Monday|Tuesday|Wednesday|Thursday|Friday|Saturday|Sunday)" }"#,
+        )
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_from_file_with_except() {
+        for _ in 0..100 {
+            test_tera_rand_function(
+                random_from_file,
+                "random_from_file",
+                r#"{ "some_field": "{{ random_from_file(path="resources/test/days.txt", except=["Saturday", "Sunday"]) }}" }"#,
+                r#"\{ "some_field": "(Monday|Tuesday|Wednesday|Thursday|Friday)" }"#,
+            )
+        }
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_from_file_with_every_line_excluded_returns_error() {
+        test_tera_rand_function_returns_error(
+            random_from_file,
+            "random_from_file",
+            r#"{{ random_from_file(path="resources/test/file_with_one_item.txt", except="item") }}"#,
+        )
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_line_from_file_with_quote_escapes_special_chars() {
+        test_tera_rand_function(
+            line_from_file,
+            "line_from_file",
+            r#"{ "some_field": {{ line_from_file(path="resources/test/special_chars.txt", line_num=0, quote=true) }} }"#,
+            r#"^\{ "some_field": "say \\"hi\\" \\\\ bye" \}$"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_from_file_with_blocklist() {
+        let args: HashMap<String, Value> = HashMap::from([
+            (String::from("path"), Value::from("resources/test/days.txt")),
+            (
+                String::from("blocklist_path"),
+                Value::from("resources/test/blocklist_weekend.txt"),
+            ),
+        ]);
+        for _ in 0..100 {
+            let day: Value = random_from_file(&args).unwrap();
+            assert!(day != "Saturday" && day != "Sunday");
+        }
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_from_file_with_every_line_blocklisted_returns_error() {
+        test_tera_rand_function_returns_error(
+            random_from_file,
+            "random_from_file",
+            r#"{{ random_from_file(path="resources/test/days.txt", blocklist_path="resources/test/days.txt") }}"#,
+        )
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_from_file_with_tail_selects_appended_lines() {
+        let filepath: std::path::PathBuf =
+            std::env::temp_dir().join(format!("tera_rand_test_tail_{}.txt", std::process::id()));
+        let old_lines: String = (0..10).map(|i| format!("old-{i}\n")).collect();
+        std::fs::write(&filepath, old_lines).unwrap();
+
+        let args: HashMap<String, Value> = HashMap::from([
+            (
+                String::from("path"),
+                Value::from(filepath.to_str().unwrap()),
+            ),
+            (String::from("tail"), Value::from(true)),
+            (String::from("cache"), Value::from(false)),
+        ]);
+        assert!(random_from_file(&args)
+            .unwrap()
+            .as_str()
+            .unwrap()
+            .starts_with("old-"));
+
+        let mut file: File = std::fs::OpenOptions::new()
+            .append(true)
+            .open(&filepath)
+            .unwrap();
+        std::io::Write::write_all(&mut file, b"new-0\n").unwrap();
+
+        let mut sampled_counts: HashMap<String, usize> = HashMap::new();
+        for _ in 0..2000 {
+            let sampled_value: Value = random_from_file(&args).unwrap();
+            *sampled_counts
+                .entry(sampled_value.as_str().unwrap().to_string())
+                .or_default() += 1;
+        }
+        std::fs::remove_file(&filepath).unwrap();
+
+        let newest_count: usize = sampled_counts.get("new-0").copied().unwrap_or_default();
+        let oldest_count: usize = sampled_counts.get("old-0").copied().unwrap_or_default();
+        assert!(newest_count > 0);
+        assert!(newest_count > oldest_count);
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_file_functions_with_cache_false_read_changed_file() {
+        let filepath: std::path::PathBuf =
+            std::env::temp_dir().join(format!("tera_rand_test_cache_{}.txt", std::process::id()));
+        std::fs::write(&filepath, "before\n").unwrap();
+        let path: Value = Value::from(filepath.to_str().unwrap());
+
+        let cached_args: HashMap<String, Value> = HashMap::from([
+            (String::from("path"), path.clone()),
+            (String::from("line_num"), Value::from(0)),
+        ]);
+        let mut uncached_args: HashMap<String, Value> = cached_args.clone();
+        uncached_args.insert(String::from("cache"), Value::from(false));
+        assert_eq!(line_from_file(&cached_args).unwrap(), "before");
+        assert_eq!(random_from_file(&cached_args).unwrap(), "before");
+
+        std::fs::write(&filepath, "after\n").unwrap();
+
+        // the default still samples from the cached contents
+        assert_eq!(line_from_file(&cached_args).unwrap(), "before");
+        assert_eq!(random_from_file(&cached_args).unwrap(), "before");
+        assert_eq!(line_from_file(&uncached_args).unwrap(), "after");
+        assert_eq!(random_from_file(&uncached_args).unwrap(), "after");
+
+        // clearing the cache makes the default read the file again
+        clear_file_cache();
+        assert_eq!(line_from_file(&cached_args).unwrap(), "after");
+        assert_eq!(random_from_file(&cached_args).unwrap(), "after");
+        std::fs::remove_file(&filepath).unwrap();
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_from_file_with_column() {
+        for (column, expected_regex) in [
+            (0, r"^(web-01|web-02|db-01|cache-01)$"),
+            (1, r"^10\.0\.\d\.\d$"),
+            (2, r"^[a-z]{2}-[a-z]+-\d$"),
+        ] {
+            test_tera_rand_function(
+                random_from_file,
+                "random_from_file",
+                &format!(
+                    r#"{{{{ random_from_file(path="resources/test/hosts.tsv", column={column}) }}}}"#
+                ),
+                expected_regex,
+            );
+        }
+    }
+
+    // Sample many lines from commented_days.txt with the given arguments and return the distinct
+    // sampled lines.
+    fn sample_commented_days(extra_args: &[(&str, Value)]) -> HashSet<String> {
+        let mut args: HashMap<String, Value> = HashMap::from([(
+            String::from("path"),
+            Value::from("resources/test/commented_days.txt"),
+        )]);
+        for (parameter, argument) in extra_args {
+            args.insert(parameter.to_string(), argument.clone());
+        }
+        (0..500)
+            .map(|_| {
+                random_from_file(&args)
+                    .unwrap()
+                    .as_str()
+                    .unwrap()
+                    .to_string()
+            })
+            .collect()
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_from_file_with_skip_empty_and_comment_prefix() {
+        let days: HashSet<String> = sample_commented_days(&[
+            ("skip_empty", Value::from(true)),
+            ("comment_prefix", Value::from("#")),
+        ]);
+        assert_eq!(
+            days,
+            HashSet::from(["Monday", "Tuesday", "Wednesday"].map(String::from))
+        );
+
+        // the lines cached with filtering must not be reused without it, and vice versa
+        let days: HashSet<String> = sample_commented_days(&[]);
+        assert!(days.contains(""));
+        assert!(days.contains("# weekdays"));
+
+        let days: HashSet<String> = sample_commented_days(&[("skip_empty", Value::from(true))]);
+        assert!(!days.contains(""));
+        assert!(days.contains("  # midweek"));
+
+        let days: HashSet<String> = sample_commented_days(&[
+            ("comment_prefix", Value::from("#")),
+            ("cache", Value::from(false)),
+        ]);
+        assert!(days.contains(""));
+        assert!(!days.iter().any(|day| day.contains('#')));
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_from_file_with_skip_empty_and_decay() {
+        let days: HashSet<String> = sample_commented_days(&[
+            ("skip_empty", Value::from(true)),
+            ("comment_prefix", Value::from("#")),
+            ("decay", Value::from(0.5)),
+        ]);
+        assert!(days.contains("Wednesday"));
+        assert!(days.len() <= 3);
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_from_file_with_cache_ttl_reads_modified_file() {
+        let filepath: std::path::PathBuf =
+            std::env::temp_dir().join(format!("tera_rand_test_ttl_{}.txt", std::process::id()));
+        std::fs::write(&filepath, "old\n").unwrap();
+
+        let args: HashMap<String, Value> = HashMap::from([
+            (
+                String::from("path"),
+                Value::from(filepath.to_str().unwrap()),
+            ),
+            (String::from("cache_ttl_secs"), Value::from(0.2)),
+        ]);
+        assert_eq!(random_from_file(&args).unwrap(), Value::from("old"));
+
+        // the cached contents are used until they are older than the TTL
+        std::fs::write(&filepath, "new\n").unwrap();
+        assert_eq!(random_from_file(&args).unwrap(), Value::from("old"));
+
+        std::thread::sleep(Duration::from_millis(300));
+        let sampled_value: Value = random_from_file(&args).unwrap();
+        std::fs::remove_file(&filepath).unwrap();
+        assert_eq!(sampled_value, Value::from("new"));
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_from_file_with_invalid_cache_ttl_returns_error() {
+        for input_str in [
+            r#"{{ random_from_file(path="resources/test/days.txt", cache_ttl_secs=-1) }}"#,
+            r#"{{ random_from_file(path="resources/test/days.txt", cache_ttl_secs=1, cache=false) }}"#,
+        ] {
+            test_tera_rand_function_returns_error(random_from_file, "random_from_file", input_str)
+        }
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_from_file_with_invalid_line_filter_returns_error() {
+        for input_str in [
+            r#"{{ random_from_file(path="resources/test/days.txt", comment_prefix="") }}"#,
+            r#"{{ random_from_file(path="resources/test/file_with_one_item.txt", comment_prefix="item") }}"#,
+        ] {
+            test_tera_rand_function_returns_error(random_from_file, "random_from_file", input_str)
+        }
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_from_file_with_column_and_delimiter() {
+        test_tera_rand_function(
+            random_from_file,
+            "random_from_file",
+            r#"{{ random_from_file(path="resources/test/zip_codes.csv", delimiter=",", column=2) }}"#,
+            // the header line is sampled like any other line
+            r"^(zip|\d{5})$",
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_from_file_with_invalid_column_args_returns_error() {
+        for template in [
+            r#"{{ random_from_file(path="resources/test/hosts.tsv", column=3) }}"#,
+            r#"{{ random_from_file(path="resources/test/hosts.tsv", delimiter=",") }}"#,
+            r#"{{ random_from_file(path="resources/test/hosts.tsv", delimiter="", column=0) }}"#,
+        ] {
+            test_tera_rand_function_returns_error(random_from_file, "random_from_file", template);
+        }
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_from_file_with_tail_and_cache_returns_error() {
+        test_tera_rand_function_returns_error(
+            random_from_file,
+            "random_from_file",
+            r#"{{ random_from_file(path="resources/test/days.txt", tail=true) }}"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_from_file_with_decay_favors_end_of_file() {
+        let args: HashMap<String, Value> = HashMap::from([
+            (
+                String::from("path"),
+                Value::from("resources/test/events.txt"),
+            ),
+            (String::from("decay"), Value::from(0.5)),
+        ]);
+
+        let sample_count: usize = 2000;
+        let mut last_five_count: usize = 0;
+        let mut last_line_count: usize = 0;
+        for _ in 0..sample_count {
+            let sampled_value: Value = random_from_file(&args).unwrap();
+            let event_num: usize = sampled_value
+                .as_str()
+                .unwrap()
+                .strip_prefix("event-")
+                .unwrap()
+                .parse()
+                .unwrap();
+            if event_num > 15 {
+                last_five_count += 1;
+            }
+            if event_num == 20 {
+                last_line_count += 1;
+            }
+        }
+
+        // with a decay of 0.5, the last line should be sampled about 1/2 of the time, and the last
+        // five lines should be sampled about 31/32 of the time
+        assert!(last_five_count as f64 / sample_count as f64 > 0.9);
+        assert!((last_line_count as f64 / sample_count as f64 - 0.5).abs() < 0.05);
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_recency_index_is_replaced_when_line_count_changes() {
+        let filepath: &str = "resources/test/growing_recency_file.txt";
+        for line_count in [10, 20, 30] {
+            let recency_index: Arc<WeightedIndex<f64>> =
+                read_recency_index(filepath, line_count, 0.5).unwrap();
+            let sampled_line_num: usize = with_rng(|rng| recency_index.sample(rng));
+            assert!(sampled_line_num < line_count);
+        }
+
+        // another test may clear the caches concurrently, but no index for an old line count may
+        // remain
+        let cached_line_counts: Vec<usize> = RECENCY_INDEXES
+            .iter()
+            .filter(|entry| entry.key().ends_with(filepath))
+            .map(|entry| entry.value().0)
+            .collect();
+        assert!(cached_line_counts.len() <= 1);
+        assert!(cached_line_counts
+            .iter()
+            .all(|line_count| *line_count == 30));
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_from_file_with_decay_and_except() {
+        for _ in 0..100 {
+            test_tera_rand_function(
+                random_from_file,
+                "random_from_file",
+                r#"{ "some_field": "{{ random_from_file(path="resources/test/events.txt", decay=0.5, except="event-20") }}" }"#,
+                r#"\{ "some_field": "event-([1-9]|1\d)" }"#,
+            );
+        }
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_from_file_with_invalid_decay_returns_error() {
+        test_tera_rand_function_returns_error(
+            random_from_file,
+            "random_from_file",
+            r#"{{ random_from_file(path="resources/test/events.txt", decay=1.5) }}"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_from_file_with_expand_ranges() {
+        let args: HashMap<String, Value> = HashMap::from([
+            (
+                String::from("path"),
+                Value::from("resources/test/ranges.txt"),
+            ),
+            (String::from("expand_ranges"), Value::from(true)),
+        ]);
+
+        let mut sampled_literals: HashSet<String> = HashSet::new();
+        let mut sampled_integer_count: usize = 0;
+        for _ in 0..500 {
+            match random_from_file(&args).unwrap() {
+                Value::Number(number) => {
+                    let number: u64 = number.as_u64().unwrap();
+                    assert!(
+                        (10..=20).contains(&number) || (300..=305).contains(&number) || number == 7
+                    );
+                    sampled_integer_count += 1;
+                }
+                Value::String(literal) => {
+                    sampled_literals.insert(literal);
+                }
+                value => panic!("unexpected value {value}"),
+            }
+        }
+        assert!(sampled_integer_count > 0);
+        assert_eq!(
+            sampled_literals,
+            HashSet::from([String::from("literal"), String::from("not-a-range")])
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_from_file_without_expand_ranges_returns_literal_lines() {
+        test_tera_rand_function(
+            random_from_file,
+            "random_from_file",
+            r#"{ "some_field": "{{ random_from_file(path="resources/test/descending_range.txt") }}" }"#,
+            r#"\{ "some_field": "20-10" }"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_from_file_with_descending_range_returns_error() {
+        test_tera_rand_function_returns_error(
+            random_from_file,
+            "random_from_file",
+            r#"{{ random_from_file(path="resources/test/descending_range.txt", expand_ranges=true) }}"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_parse_integer_range() {
+        assert_eq!(parse_integer_range("10-20"), Some((10, 20)));
+        assert_eq!(parse_integer_range(" 7-7 "), Some((7, 7)));
+        assert_eq!(parse_integer_range("-5-3"), None);
+        assert_eq!(parse_integer_range("10-"), None);
+        assert_eq!(parse_integer_range("a-b"), None);
+        assert_eq!(parse_integer_range("1-2-3"), None);
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_from_tree_samples_from_every_level() {
+        let args: HashMap<String, Value> =
+            HashMap::from([(String::from("root"), Value::from("resources/test/tree"))]);
+        let mut sampled_values: HashSet<String> = HashSet::new();
+        for _ in 0..500 {
+            let sampled_value: Value = random_from_tree(&args).unwrap();
+            sampled_values.insert(sampled_value.as_str().unwrap().to_string());
+        }
+
+        let expected_values: HashSet<String> = [
+            "top-1",
+            "top-2",
+            "nested-1",
+            "nested-2",
+            "deepest-1",
+            "deepest-2",
+            "nested,csv",
+        ]
+        .into_iter()
+        .map(String::from)
+        .collect();
+        assert_eq!(sampled_values, expected_values);
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_from_tree_with_extension() {
+        for _ in 0..100 {
+            test_tera_rand_function(
+                random_from_tree,
+                "random_from_tree",
+                r#"{ "some_field": "{{ random_from_tree(root="resources/test/tree", extension=".txt") }}" }"#,
+                r#"\{ "some_field": "(top|nested|deepest)-(1|2)" }"#,
+            );
+        }
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_from_tree_with_missing_root_returns_error() {
+        test_tera_rand_function_returns_error(
+            random_from_tree,
+            "random_from_tree",
+            r#"{{ random_from_tree(root="resources/test/this-directory-does-not-exist") }}"#,
+        );
+        test_tera_rand_function_returns_error(
+            random_from_tree,
+            "random_from_tree",
+            r#"{{ random_from_tree(root="resources/test/tree", extension="json") }}"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_sample_from_file() {
+        test_tera_rand_function(
+            random_sample_from_file,
+            "random_sample_from_file",
+            r#"{ "some_field": "{{ random_sample_from_file(path="resources/test/days.txt", count=7) | sort | join(sep=",") }}" }"#,
+            r#"\{ "some_field": "Friday,Monday,Saturday,Sunday,Thursday,Tuesday,Wednesday" }"#,
+        )
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_sample_from_file_is_reproducible_under_fixed_seed() {
+        let args: HashMap<String, Value> = HashMap::from([
+            (String::from("path"), Value::from("resources/test/days.txt")),
+            (String::from("count"), Value::from(4)),
+        ]);
+
+        set_seed(23);
+        let first: Vec<Value> = (0..5)
+            .map(|_| random_sample_from_file(&args).unwrap())
+            .collect();
+        set_seed(23);
+        let second: Vec<Value> = (0..5)
+            .map(|_| random_sample_from_file(&args).unwrap())
+            .collect();
+        clear_seed();
+
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_sample_from_file_with_count_larger_than_file_returns_error() {
+        test_tera_rand_function_returns_error(
+            random_sample_from_file,
+            "random_sample_from_file",
+            r#"{{ random_sample_from_file(path="resources/test/days.txt", count=8) }}"#,
+        )
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_frequent_word() {
+        let args: HashMap<String, Value> = HashMap::from([(
+            String::from("path"),
+            Value::from("resources/test/word_frequencies.txt"),
+        )]);
+
+        let mut word_counts: HashMap<String, usize> = HashMap::new();
+        for _ in 0..1000 {
+            let word: Value = random_frequent_word(&args).unwrap();
+            *word_counts
+                .entry(word.as_str().unwrap().to_string())
+                .or_default() += 1;
+        }
+
+        assert!(word_counts
+            .keys()
+            .all(|word| ["the", "of", "quixotic"].contains(&word.as_str())));
+        assert!(word_counts["the"] > 900usize);
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_frequent_word_with_malformed_line_returns_error() {
+        test_tera_rand_function_returns_error(
+            random_frequent_word,
+            "random_frequent_word",
+            r#"{{ random_frequent_word(path="resources/test/word_frequencies_malformed.txt") }}"#,
+        )
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_weighted_from_file() {
+        let args: HashMap<String, Value> = HashMap::from([(
+            String::from("path"),
+            Value::from("resources/test/http_methods.tsv"),
+        )]);
+
+        let mut method_counts: HashMap<String, usize> = HashMap::new();
+        for _ in 0..1000 {
+            let method: Value = weighted_from_file(&args).unwrap();
+            *method_counts
+                .entry(method.as_str().unwrap().to_string())
+                .or_default() += 1;
+        }
+
+        assert!(method_counts
+            .keys()
+            .all(|method| ["GET", "POST", "DELETE"].contains(&method.as_str())));
+        assert!(method_counts["GET"] > 800usize);
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_weighted_from_file_with_delimiter() {
+        test_tera_rand_function(
+            weighted_from_file,
+            "weighted_from_file",
+            r#"{{ weighted_from_file(path="resources/test/http_requests.csv", delimiter=",") }}"#,
+            r#"(GET,/index\.html|POST,/login)"#,
+        )
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_from_json_file_with_strings() {
+        test_tera_rand_function(
+            random_from_json_file,
+            "random_from_json_file",
+            r#"{{ random_from_json_file(path="resources/test/fruits.json") }}"#,
+            r#"(apple|banana|cherry)"#,
+        )
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_from_json_file_with_numbers() {
+        let args: HashMap<String, Value> = HashMap::from([(
+            String::from("path"),
+            Value::from("resources/test/ports.json"),
+        )]);
+        for _ in 0..100 {
+            let port: Value = random_from_json_file(&args).unwrap();
+            assert!([80, 443, 8080].contains(&port.as_u64().unwrap()));
+        }
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_from_json_file_with_objects_and_pointer() {
+        let args: HashMap<String, Value> = HashMap::from([
+            (
+                String::from("path"),
+                Value::from("resources/test/servers.json"),
+            ),
+            (String::from("pointer"), Value::from("/data/items")),
+        ]);
+        for _ in 0..100 {
+            let server: Value = random_from_json_file(&args).unwrap();
+            let server: &Map<String, Value> = server.as_object().unwrap();
+            assert!(["web-1", "db-1"].contains(&server["name"].as_str().unwrap()));
+            assert!(server["port"].is_u64());
+        }
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_from_json_file_with_invalid_args_returns_error() {
+        for input_str in [
+            r#"{{ random_from_json_file(path="resources/test/days.txt") }}"#,
+            r#"{{ random_from_json_file(path="resources/test/servers.json") }}"#,
+            r#"{{ random_from_json_file(path="resources/test/servers.json", pointer="/data/missing") }}"#,
+            r#"{{ random_from_json_file(path="resources/test/servers.json", pointer="/data/items/0") }}"#,
+        ] {
+            test_tera_rand_function_returns_error(
+                random_from_json_file,
+                "random_from_json_file",
+                input_str,
+            )
+        }
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_weighted_from_file_with_malformed_weight_returns_error() {
+        let args: HashMap<String, Value> = HashMap::from([(
+            String::from("path"),
+            Value::from("resources/test/http_methods_malformed.tsv"),
+        )]);
+
+        let error_message: String = weighted_from_file(&args).unwrap_err().to_string();
+        assert!(
+            error_message.contains("Unable to parse weight `many` on line 2, `many\tPOST`"),
+            "{error_message}"
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_with_file_with_one_item() {
+        test_tera_rand_function(
+            random_from_file,
+            "random_from_file",
+            r#"{ "some_field": "{{ random_from_file(path="resources/test/file_with_one_item.txt") }}" }"#,
+            r#"\{ "some_field": "item" }"#,
+        )
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_from_csv() {
+        test_tera_rand_function(
+            random_from_csv,
+            "random_from_csv",
+            r#"{% set row = random_from_csv(path="resources/test/zip_codes.csv") %}{ "some_field": "{{ row.city }}, {{ row.state }}" }"#,
+            r#"\{ "some_field": "(Seattle, WA|Portland, OR|Fresno, CA)" }"#,
+        )
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_from_csv_with_columns_joined_from_single_row() {
+        for _ in 0..100 {
+            test_tera_rand_function(
+                random_from_csv,
+                "random_from_csv",
+                r#"{ "some_field": "{{ random_from_csv(path="resources/test/zip_codes.csv", columns=["state", "zip"], join="-") }}" }"#,
+                r#"^\{ "some_field": "(WA-98101|OR-97201|CA-93650)" }$"#,
+            )
+        }
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_from_csv_with_unknown_column_returns_error() {
+        test_tera_rand_function_returns_error(
+            random_from_csv,
+            "random_from_csv",
+            r#"{{ random_from_csv(path="resources/test/zip_codes.csv", columns=["country"]) }}"#,
         )
     }
 
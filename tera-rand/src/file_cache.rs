@@ -0,0 +1,316 @@
+use crate::error::internal_error;
+use crate::file::clear_parsed_file_caches;
+use lazy_static::lazy_static;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+use tera::Result;
+
+lazy_static! {
+    static ref FILE_CACHE: Mutex<FileCache> = Mutex::new(FileCache::new(None));
+}
+
+/// Limit the memory used to cache the contents of files read by functions such as
+/// [`random_from_file`].
+///
+/// When the cached files add up to more than `max_bytes`, the least recently used files are
+/// evicted until the cache fits again. An evicted file is simply read again the next time it is
+/// used. Passing `None` removes the limit, which is the default.
+///
+/// The size of a file is approximated by the total length of its lines in bytes.
+///
+/// # Example usage
+///
+/// ```edition2021
+/// use tera_rand::set_file_cache_max_bytes;
+///
+/// // cache at most 64 MiB of file contents
+/// set_file_cache_max_bytes(Some(64 * 1024 * 1024));
+/// ```
+///
+/// [`random_from_file`]: crate::random_from_file
+pub fn set_file_cache_max_bytes(max_bytes: Option<usize>) {
+    if let Ok(mut file_cache) = FILE_CACHE.lock() {
+        file_cache.set_max_bytes(max_bytes);
+    }
+}
+
+/// Set how long the cached contents of a file are used before the file is read again, for files
+/// read by functions such as [`random_from_file`]. This is useful in a long-lived process which
+/// reads files that are regularly updated.
+///
+/// A `cache_ttl_secs` argument passed to [`random_from_file`] takes precedence over this default.
+/// Passing `None` keeps cached contents until they are evicted or cleared, which is the default.
+/// Values derived from file contents, such as the weights read by [`random_frequent_word`], do not
+/// expire; use [`clear_file_cache`] to forget them.
+///
+/// # Example usage
+///
+/// ```edition2021
+/// use std::time::Duration;
+/// use tera_rand::set_file_cache_ttl;
+///
+/// // read each file again at most once a minute
+/// set_file_cache_ttl(Some(Duration::from_secs(60)));
+/// ```
+///
+/// [`random_from_file`]: crate::random_from_file
+/// [`random_frequent_word`]: crate::random_frequent_word
+pub fn set_file_cache_ttl(ttl: Option<Duration>) {
+    if let Ok(mut file_cache) = FILE_CACHE.lock() {
+        file_cache.default_ttl = ttl;
+    }
+}
+
+/// Forget the contents of every file cached by functions such as [`random_from_file`], so that
+/// each file is read again the next time it is used. This is useful in a long-lived process
+/// whose files change between renders.
+///
+/// Values derived from file contents, such as the weights read by [`random_frequent_word`] and
+/// the blocklists read by `blocklist_path` parameters, are forgotten too.
+///
+/// # Example usage
+///
+/// ```edition2021
+/// use tera_rand::clear_file_cache;
+///
+/// // the files have been regenerated, so read them again
+/// clear_file_cache();
+/// ```
+///
+/// [`random_from_file`]: crate::random_from_file
+/// [`random_frequent_word`]: crate::random_frequent_word
+pub fn clear_file_cache() {
+    if let Ok(mut file_cache) = FILE_CACHE.lock() {
+        file_cache.clear();
+    }
+    clear_parsed_file_caches();
+}
+
+// Return the cached lines for `filepath` if there are any which were cached less than `ttl` ago,
+// marking them as recently used. If `ttl` is `None`, the default TTL applies.
+pub(crate) fn get_cached_lines(
+    filepath: &str,
+    ttl: Option<Duration>,
+) -> Result<Option<Arc<Vec<String>>>> {
+    Ok(lock_file_cache()?.get(filepath, ttl))
+}
+
+// Cache the lines for `filepath`, possibly evicting other files, and return the cached lines.
+pub(crate) fn cache_lines(filepath: String, lines: Vec<String>) -> Result<Arc<Vec<String>>> {
+    Ok(lock_file_cache()?.insert(filepath, lines))
+}
+
+fn lock_file_cache<'a>() -> Result<MutexGuard<'a, FileCache>> {
+    FILE_CACHE
+        .lock()
+        .map_err(|_| internal_error(String::from("The file cache lock was poisoned")))
+}
+
+#[derive(Debug)]
+struct FileCacheEntry {
+    lines: Arc<Vec<String>>,
+    bytes: usize,
+    last_used: u64,
+    cached_at: Instant,
+}
+
+// A cache of file lines keyed by filepath which evicts the least recently used entries once the
+// total size of its entries exceeds `max_bytes`. Entries older than their TTL are treated as
+// missing, so that the file is read again.
+//
+// Eviction scans every entry, which is fine for the handful of files a template typically uses.
+#[derive(Debug)]
+struct FileCache {
+    entries: HashMap<String, FileCacheEntry>,
+    total_bytes: usize,
+    max_bytes: Option<usize>,
+    default_ttl: Option<Duration>,
+    // incremented on every access so that entries can be ordered by recency
+    clock: u64,
+}
+
+impl FileCache {
+    fn new(max_bytes: Option<usize>) -> Self {
+        FileCache {
+            entries: HashMap::new(),
+            total_bytes: 0usize,
+            max_bytes,
+            default_ttl: None,
+            clock: 0u64,
+        }
+    }
+
+    fn get(&mut self, filepath: &str, ttl: Option<Duration>) -> Option<Arc<Vec<String>>> {
+        self.clock += 1;
+        let clock: u64 = self.clock;
+        let ttl: Option<Duration> = ttl.or(self.default_ttl);
+
+        let entry: &mut FileCacheEntry = self.entries.get_mut(filepath)?;
+        if ttl.is_some_and(|ttl| entry.cached_at.elapsed() >= ttl) {
+            if let Some(expired_entry) = self.entries.remove(filepath) {
+                self.total_bytes -= expired_entry.bytes;
+            }
+            return None;
+        }
+        entry.last_used = clock;
+        Some(entry.lines.clone())
+    }
+
+    fn insert(&mut self, filepath: String, lines: Vec<String>) -> Arc<Vec<String>> {
+        self.clock += 1;
+        let bytes: usize = lines.iter().map(String::len).sum();
+        let lines: Arc<Vec<String>> = Arc::new(lines);
+
+        let entry: FileCacheEntry = FileCacheEntry {
+            lines: lines.clone(),
+            bytes,
+            last_used: self.clock,
+            cached_at: Instant::now(),
+        };
+        if let Some(old_entry) = self.entries.insert(filepath, entry) {
+            self.total_bytes -= old_entry.bytes;
+        }
+        self.total_bytes += bytes;
+
+        self.evict_until_within_budget();
+        lines
+    }
+
+    fn clear(&mut self) {
+        self.entries.clear();
+        self.total_bytes = 0usize;
+    }
+
+    fn set_max_bytes(&mut self, max_bytes: Option<usize>) {
+        self.max_bytes = max_bytes;
+        self.evict_until_within_budget();
+    }
+
+    fn evict_until_within_budget(&mut self) {
+        let Some(max_bytes) = self.max_bytes else {
+            return;
+        };
+        while self.total_bytes > max_bytes {
+            let least_recently_used: Option<String> = self
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(filepath, _)| filepath.clone());
+
+            match least_recently_used.and_then(|filepath| self.entries.remove(&filepath)) {
+                Some(evicted_entry) => self.total_bytes -= evicted_entry.bytes,
+                None => break,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::file_cache::*;
+    use tracing_test::traced_test;
+
+    fn lines_of_bytes(bytes: usize) -> Vec<String> {
+        vec!["x".repeat(bytes)]
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_file_cache_evicts_least_recently_used_entry() {
+        let mut file_cache: FileCache = FileCache::new(Some(250usize));
+        file_cache.insert(String::from("a"), lines_of_bytes(100));
+        file_cache.insert(String::from("b"), lines_of_bytes(100));
+
+        // use "a" so that "b" becomes the least recently used entry
+        assert!(file_cache.get("a", None).is_some());
+        file_cache.insert(String::from("c"), lines_of_bytes(100));
+
+        assert!(file_cache.get("a", None).is_some());
+        assert!(file_cache.get("b", None).is_none());
+        assert!(file_cache.get("c", None).is_some());
+        assert_eq!(file_cache.total_bytes, 200usize);
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_file_cache_without_budget_never_evicts() {
+        let mut file_cache: FileCache = FileCache::new(None);
+        for filepath in ["a", "b", "c"] {
+            file_cache.insert(String::from(filepath), lines_of_bytes(1_000_000));
+        }
+
+        assert!(["a", "b", "c"]
+            .iter()
+            .all(|filepath| file_cache.get(filepath, None).is_some()));
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_file_cache_evicts_when_budget_is_lowered() {
+        let mut file_cache: FileCache = FileCache::new(None);
+        file_cache.insert(String::from("a"), lines_of_bytes(100));
+        file_cache.insert(String::from("b"), lines_of_bytes(100));
+
+        file_cache.set_max_bytes(Some(150usize));
+
+        assert!(file_cache.get("a", None).is_none());
+        assert!(file_cache.get("b", None).is_some());
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_file_cache_clear() {
+        let mut file_cache: FileCache = FileCache::new(None);
+        file_cache.insert(String::from("a"), lines_of_bytes(100));
+        file_cache.insert(String::from("b"), lines_of_bytes(100));
+
+        file_cache.clear();
+
+        assert!(file_cache.get("a", None).is_none());
+        assert!(file_cache.get("b", None).is_none());
+        assert_eq!(file_cache.total_bytes, 0usize);
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_file_cache_does_not_keep_entry_larger_than_budget() {
+        let mut file_cache: FileCache = FileCache::new(Some(50usize));
+        let lines: Arc<Vec<String>> = file_cache.insert(String::from("a"), lines_of_bytes(100));
+
+        assert_eq!(lines.len(), 1usize);
+        assert!(file_cache.get("a", None).is_none());
+        assert_eq!(file_cache.total_bytes, 0usize);
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_file_cache_expires_entry_older_than_ttl() {
+        let mut file_cache: FileCache = FileCache::new(None);
+        file_cache.insert(String::from("a"), lines_of_bytes(100));
+
+        assert!(file_cache.get("a", Some(Duration::from_secs(60))).is_some());
+        std::thread::sleep(Duration::from_millis(20));
+        assert!(file_cache
+            .get("a", Some(Duration::from_millis(10)))
+            .is_none());
+
+        // the expired entry is removed rather than kept around
+        assert!(file_cache.get("a", None).is_none());
+        assert_eq!(file_cache.total_bytes, 0usize);
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_file_cache_default_ttl() {
+        let mut file_cache: FileCache = FileCache::new(None);
+        file_cache.default_ttl = Some(Duration::from_millis(10));
+        file_cache.insert(String::from("a"), lines_of_bytes(100));
+        file_cache.insert(String::from("b"), lines_of_bytes(100));
+        std::thread::sleep(Duration::from_millis(20));
+
+        // an explicit TTL takes precedence over the default
+        assert!(file_cache.get("a", Some(Duration::from_secs(60))).is_some());
+        assert!(file_cache.get("b", None).is_none());
+    }
+}
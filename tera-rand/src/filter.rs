@@ -0,0 +1,104 @@
+use std::collections::HashMap;
+use tera::{Filter, Result, Value};
+
+/// The signature shared by every tera-rand function, such as [`random_string`].
+///
+/// [`random_string`]: crate::random_string
+pub type TeraRandFunction = fn(&HashMap<String, Value>) -> Result<Value>;
+
+/// An adapter which registers a tera-rand function as a Tera filter, so that generated values can
+/// be piped through Tera's built-in filters, as in `{{ "" | random_string(length=5) | upper }}`.
+///
+/// A filter is always applied to an input value, but a generator has nothing to transform, so the
+/// input value is ignored, and every application generates a fresh value exactly as the function
+/// would. The filter accepts the same parameters as the function.
+///
+/// # Example usage
+///
+/// ```edition2021
+/// use tera::{Context, Tera};
+/// use tera_rand::{random_string, GeneratorFilter};
+///
+/// let mut tera: Tera = Tera::default();
+/// tera.register_filter("random_string", GeneratorFilter::new(random_string));
+/// let context: Context = Context::new();
+///
+/// let rendered: String = tera
+///     .render_str(r#"{{ "" | random_string(length=5) | upper }}"#, &context)
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct GeneratorFilter {
+    function: TeraRandFunction,
+}
+
+impl GeneratorFilter {
+    /// Wrap `function` in a filter which ignores its input value.
+    pub fn new(function: TeraRandFunction) -> Self {
+        Self { function }
+    }
+}
+
+impl Filter for GeneratorFilter {
+    fn filter(&self, _value: &Value, args: &HashMap<String, Value>) -> Result<Value> {
+        (self.function)(args)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::filter::*;
+    use crate::{random_string, random_uint32};
+    use regex::Regex;
+    use tera::{Context, Tera};
+    use tracing_test::traced_test;
+
+    fn render_with_filter(
+        name: &str,
+        function: TeraRandFunction,
+        template: &str,
+    ) -> Result<String> {
+        let mut tera: Tera = Tera::default();
+        tera.register_filter(name, GeneratorFilter::new(function));
+        tera.render_str(template, &Context::new())
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_generator_filter_chained_with_built_in_filter() {
+        let rendered: String = render_with_filter(
+            "random_string",
+            random_string,
+            r#"{{ "ignored" | random_string(length=5) | upper }}"#,
+        )
+        .unwrap();
+
+        let expected_regex: Regex = Regex::new(r"^[A-Z\d]{5}$").unwrap();
+        assert!(expected_regex.is_match(&rendered));
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_generator_filter_passes_args_to_function() {
+        for _ in 0..100 {
+            let rendered: String = render_with_filter(
+                "random_uint32",
+                random_uint32,
+                r#"{{ 0 | random_uint32(start=10, end=99) | as_str | length }}"#,
+            )
+            .unwrap();
+            assert_eq!(rendered, "2");
+        }
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_generator_filter_with_invalid_arg_returns_error() {
+        let result: Result<String> = render_with_filter(
+            "random_string",
+            random_string,
+            r#"{{ "" | random_string(length="five") }}"#,
+        );
+        assert!(result.is_err());
+    }
+}
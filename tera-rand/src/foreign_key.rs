@@ -0,0 +1,178 @@
+use crate::common::{parse_arg, quote_if_requested};
+use crate::error::{missing_arg, no_values_to_sample};
+use crate::rng::with_rng;
+use dashmap::DashMap;
+use lazy_static::lazy_static;
+use rand::Rng;
+use std::collections::HashMap;
+use tera::{Result, Value};
+
+lazy_static! {
+    // the IDs recorded in each pool by `register_id`, shared by every thread
+    static ref ID_POOLS: DashMap<String, Vec<Value>> = DashMap::new();
+}
+
+/// A Tera function to record a generated ID in a named pool, so that records rendered later can
+/// refer to it with [`random_fk`]. The ID is returned unchanged, so the call can be placed wherever
+/// the ID is rendered.
+///
+/// The `pool` parameter takes the name of the pool, such as the name of the table the ID belongs
+/// to. The `value` parameter takes the ID to record, which is usually the result of another
+/// function.
+///
+/// Pools are shared by every thread and last until [`clear_id_pool`] is called, so IDs recorded
+/// while rendering one template can be referred to while rendering another.
+///
+/// Pass `quote=true` to render the ID wrapped in JSON quotation marks.
+///
+/// # Example usage
+///
+/// ```edition2021
+/// use tera::{Context, Tera};
+/// use tera_rand::{random_fk, random_uint32, register_id};
+///
+/// let mut tera: Tera = Tera::default();
+/// tera.register_function("register_id", register_id);
+/// tera.register_function("random_fk", random_fk);
+/// tera.register_function("random_uint32", random_uint32);
+/// let context: Context = Context::new();
+///
+/// let user: String = tera
+///     .render_str(
+///         r#"{"id": {{ register_id(pool="users", value=random_uint32()) }}}"#,
+///         &context,
+///     )
+///     .unwrap();
+/// // every order refers to a user rendered before it
+/// let order: String = tera
+///     .render_str(r#"{"user_id": {{ random_fk(pool="users") }}}"#, &context)
+///     .unwrap();
+/// ```
+pub fn register_id(args: &HashMap<String, Value>) -> Result<Value> {
+    let pool: String = parse_pool_arg(args)?;
+    let value_opt: Option<Value> = parse_arg(args, "value")?;
+    let value: Value = value_opt.ok_or_else(|| missing_arg("value"))?;
+
+    ID_POOLS.entry(pool).or_default().push(value.clone());
+    quote_if_requested(args, value)
+}
+
+/// A Tera function to sample an ID which was recorded in a named pool by [`register_id`], so that
+/// foreign keys always refer to a record which exists.
+///
+/// The `pool` parameter takes the name of the pool to sample from. Sampling from a pool in which no
+/// IDs have been recorded returns an error, which usually means that the referring records are
+/// being rendered before the records they refer to.
+///
+/// Pass `quote=true` to render the ID wrapped in JSON quotation marks.
+///
+/// See [`register_id`] for an example.
+pub fn random_fk(args: &HashMap<String, Value>) -> Result<Value> {
+    let pool: String = parse_pool_arg(args)?;
+
+    let value: Value = match ID_POOLS.get(&pool) {
+        Some(values) if !values.is_empty() => {
+            values[with_rng(|rng| rng.gen_range(0usize..values.len()))].clone()
+        }
+        _ => return Err(no_values_to_sample("pool")),
+    };
+    quote_if_requested(args, value)
+}
+
+/// Forget every ID recorded in `pool` by [`register_id`], such as before generating a new,
+/// unrelated data set.
+pub fn clear_id_pool(pool: &str) {
+    ID_POOLS.remove(pool);
+}
+
+fn parse_pool_arg(args: &HashMap<String, Value>) -> Result<String> {
+    let pool_opt: Option<String> = parse_arg(args, "pool")?;
+    pool_opt.ok_or_else(|| missing_arg("pool"))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::tests::test_tera_rand_function_returns_error;
+    use crate::foreign_key::*;
+    use crate::random_uint32;
+    use std::collections::HashSet;
+    use tera::{Context, Tera};
+    use tracing_test::traced_test;
+
+    #[test]
+    #[traced_test]
+    fn test_random_fk_refers_to_registered_ids() {
+        let mut tera: Tera = Tera::default();
+        tera.register_function("register_id", register_id);
+        tera.register_function("random_fk", random_fk);
+        tera.register_function("random_uint32", random_uint32);
+        let context: Context = Context::new();
+
+        let user_ids: HashSet<u64> = (0..20)
+            .map(|_| {
+                let user: String = tera
+                    .render_str(
+                        r#"{"id": {{ register_id(pool="fk_test_users", value=random_uint32()) }}}"#,
+                        &context,
+                    )
+                    .unwrap();
+                let user: Value = serde_json::from_str(&user).unwrap();
+                user["id"].as_u64().unwrap()
+            })
+            .collect();
+
+        for _ in 0..200 {
+            let order: String = tera
+                .render_str(
+                    r#"{"user_id": {{ random_fk(pool="fk_test_users") }}}"#,
+                    &context,
+                )
+                .unwrap();
+            let order: Value = serde_json::from_str(&order).unwrap();
+            assert!(user_ids.contains(&order["user_id"].as_u64().unwrap()));
+        }
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_register_id_with_quote() {
+        let args: HashMap<String, Value> = HashMap::from([
+            (String::from("pool"), Value::from("fk_test_quoted")),
+            (String::from("value"), Value::from("a\"b")),
+            (String::from("quote"), Value::from(true)),
+        ]);
+        assert_eq!(register_id(&args).unwrap(), r#""a\"b""#);
+        assert_eq!(random_fk(&args).unwrap(), r#""a\"b""#);
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_clear_id_pool() {
+        let args: HashMap<String, Value> = HashMap::from([
+            (String::from("pool"), Value::from("fk_test_cleared")),
+            (String::from("value"), Value::from(7)),
+        ]);
+        register_id(&args).unwrap();
+        assert_eq!(random_fk(&args).unwrap(), 7);
+
+        clear_id_pool("fk_test_cleared");
+        assert!(random_fk(&args).is_err());
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_foreign_key_functions_with_invalid_args_returns_error() {
+        for template in [
+            r#"{{ register_id(value=1) }}"#,
+            r#"{{ register_id(pool="fk_test_invalid") }}"#,
+        ] {
+            test_tera_rand_function_returns_error(register_id, "register_id", template);
+        }
+        for template in [
+            r#"{{ random_fk() }}"#,
+            r#"{{ random_fk(pool="fk_test_never_registered") }}"#,
+        ] {
+            test_tera_rand_function_returns_error(random_fk, "random_fk", template);
+        }
+    }
+}
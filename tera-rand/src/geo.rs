@@ -0,0 +1,317 @@
+use crate::common::parse_arg;
+use crate::error::parse_line_error;
+use crate::file::read_all_file_lines;
+use crate::rng::with_rng;
+use dashmap::DashMap;
+use lazy_static::lazy_static;
+use rand::Rng;
+use serde::Serialize;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::net::Ipv4Addr;
+use std::str::FromStr;
+use std::sync::Arc;
+use tera::{to_value, Result, Value};
+
+lazy_static! {
+    // the ranges parsed from each file passed to `random_geo_ip`
+    static ref GEO_IP_FILES: DashMap<String, Arc<Vec<GeoIpRange>>> = DashMap::new();
+}
+
+// A contiguous, inclusive range of IPv4 addresses and the location it maps to.
+#[derive(Debug)]
+pub(crate) struct GeoIpRange {
+    pub(crate) start: Ipv4Addr,
+    pub(crate) end: Ipv4Addr,
+    pub(crate) country: Cow<'static, str>,
+    pub(crate) city: Cow<'static, str>,
+    pub(crate) lat: f64,
+    pub(crate) lon: f64,
+}
+
+// A small, illustrative table of address ranges. This is not a real geolocation database; it only
+// exists so that the generated fields agree with each other.
+pub(crate) const GEO_IP_RANGES: &[GeoIpRange] = &[
+    GeoIpRange {
+        start: Ipv4Addr::new(3, 0, 0, 0),
+        end: Ipv4Addr::new(3, 127, 255, 255),
+        country: Cow::Borrowed("US"),
+        city: Cow::Borrowed("New York"),
+        lat: 40.7128,
+        lon: -74.0060,
+    },
+    GeoIpRange {
+        start: Ipv4Addr::new(3, 128, 0, 0),
+        end: Ipv4Addr::new(3, 255, 255, 255),
+        country: Cow::Borrowed("US"),
+        city: Cow::Borrowed("San Francisco"),
+        lat: 37.7749,
+        lon: -122.4194,
+    },
+    GeoIpRange {
+        start: Ipv4Addr::new(24, 48, 0, 0),
+        end: Ipv4Addr::new(24, 55, 255, 255),
+        country: Cow::Borrowed("CA"),
+        city: Cow::Borrowed("Toronto"),
+        lat: 43.6532,
+        lon: -79.3832,
+    },
+    GeoIpRange {
+        start: Ipv4Addr::new(31, 48, 0, 0),
+        end: Ipv4Addr::new(31, 55, 255, 255),
+        country: Cow::Borrowed("GB"),
+        city: Cow::Borrowed("London"),
+        lat: 51.5074,
+        lon: -0.1278,
+    },
+    GeoIpRange {
+        start: Ipv4Addr::new(46, 16, 0, 0),
+        end: Ipv4Addr::new(46, 23, 255, 255),
+        country: Cow::Borrowed("DE"),
+        city: Cow::Borrowed("Berlin"),
+        lat: 52.5200,
+        lon: 13.4050,
+    },
+    GeoIpRange {
+        start: Ipv4Addr::new(90, 0, 0, 0),
+        end: Ipv4Addr::new(90, 63, 255, 255),
+        country: Cow::Borrowed("FR"),
+        city: Cow::Borrowed("Paris"),
+        lat: 48.8566,
+        lon: 2.3522,
+    },
+    GeoIpRange {
+        start: Ipv4Addr::new(126, 0, 0, 0),
+        end: Ipv4Addr::new(126, 255, 255, 255),
+        country: Cow::Borrowed("JP"),
+        city: Cow::Borrowed("Tokyo"),
+        lat: 35.6762,
+        lon: 139.6503,
+    },
+    GeoIpRange {
+        start: Ipv4Addr::new(177, 0, 0, 0),
+        end: Ipv4Addr::new(177, 63, 255, 255),
+        country: Cow::Borrowed("BR"),
+        city: Cow::Borrowed("São Paulo"),
+        lat: -23.5505,
+        lon: -46.6333,
+    },
+    GeoIpRange {
+        start: Ipv4Addr::new(1, 120, 0, 0),
+        end: Ipv4Addr::new(1, 127, 255, 255),
+        country: Cow::Borrowed("AU"),
+        city: Cow::Borrowed("Sydney"),
+        lat: -33.8688,
+        lon: 151.2093,
+    },
+    GeoIpRange {
+        start: Ipv4Addr::new(117, 192, 0, 0),
+        end: Ipv4Addr::new(117, 255, 255, 255),
+        country: Cow::Borrowed("IN"),
+        city: Cow::Borrowed("Mumbai"),
+        lat: 19.0760,
+        lon: 72.8777,
+    },
+];
+
+#[derive(Debug, Serialize)]
+struct GeoIp<'a> {
+    ip: Ipv4Addr,
+    country: &'a str,
+    city: &'a str,
+    lat: f64,
+    lon: f64,
+}
+
+/// A Tera function to generate a random IPv4 address along with the location it belongs to.
+///
+/// The result is an object with the fields `ip`, `country`, `city`, `lat`, and `lon`. Unlike
+/// generating each field independently, the fields are guaranteed to agree with each other: a
+/// range is picked from a table, and the address is sampled from within that range.
+///
+/// By default, a small built-in table is used. This table is illustrative only and should not be
+/// mistaken for a real geolocation database. To use your own table, pass a filepath to the `path`
+/// parameter. Each line of the file should have the form `start,end,country,city,lat,lon`, where
+/// `start` and `end` are the inclusive bounds of an IPv4 range, for example:
+/// ```text
+/// 10.0.0.0,10.0.255.255,US,Seattle,47.6062,-122.3321
+/// ```
+///
+/// Note that the contents of the filepath is read only once and cached. Every line is checked when
+/// the file is first read, so a malformed line is reported on the first call rather than only
+/// when it happens to be sampled.
+///
+/// # Example usage
+///
+/// ```edition2021
+/// use tera::{Context, Tera};
+/// use tera_rand::random_geo_ip;
+///
+/// let mut tera: Tera = Tera::default();
+/// tera.register_function("random_geo_ip", random_geo_ip);
+/// let context: Context = Context::new();
+///
+/// // use the built-in table
+/// let rendered: String = tera
+///     .render_str(
+///         r#"{% set geo = random_geo_ip() %}{{ geo.ip }} is in {{ geo.city }}, {{ geo.country }}"#,
+///         &context
+///     )
+///     .unwrap();
+/// // use a table from a file
+/// let rendered: String = tera
+///     .render_str(
+///         r#"{{ random_geo_ip(path="resources/test/geo_ip_ranges.csv") | json_encode() }}"#,
+///         &context
+///     )
+///     .unwrap();
+/// ```
+pub fn random_geo_ip(args: &HashMap<String, Value>) -> Result<Value> {
+    let filepath_opt: Option<String> = parse_arg(args, "path")?;
+
+    match filepath_opt {
+        Some(filepath) => {
+            let geo_ip_ranges: Arc<Vec<GeoIpRange>> = read_geo_ip_file(&filepath)?;
+            let geo_ip_range: &GeoIpRange =
+                &geo_ip_ranges[with_rng(|rng| rng.gen_range(0usize..geo_ip_ranges.len()))];
+            gen_geo_ip(geo_ip_range)
+        }
+        None => {
+            let geo_ip_range: &GeoIpRange =
+                &GEO_IP_RANGES[with_rng(|rng| rng.gen_range(0usize..GEO_IP_RANGES.len()))];
+            gen_geo_ip(geo_ip_range)
+        }
+    }
+}
+
+fn gen_geo_ip(geo_ip_range: &GeoIpRange) -> Result<Value> {
+    let start: u32 = geo_ip_range.start.into();
+    let end: u32 = geo_ip_range.end.into();
+    let ip: Ipv4Addr = with_rng(|rng| rng.gen_range(start..=end)).into();
+
+    let geo_ip: GeoIp = GeoIp {
+        ip,
+        country: &geo_ip_range.country,
+        city: &geo_ip_range.city,
+        lat: geo_ip_range.lat,
+        lon: geo_ip_range.lon,
+    };
+    let json_value: Value = to_value(geo_ip)?;
+    Ok(json_value)
+}
+
+// Parse every line of a file of geo-IP ranges, and cache the ranges. If the ranges are already
+// cached, the file is not read again.
+fn read_geo_ip_file(filepath: &str) -> Result<Arc<Vec<GeoIpRange>>> {
+    if let Some(geo_ip_ranges) = GEO_IP_FILES.get(filepath) {
+        return Ok(geo_ip_ranges.clone());
+    }
+
+    let lines: Arc<Vec<String>> = read_all_file_lines(filepath)?;
+    let geo_ip_ranges: Arc<Vec<GeoIpRange>> = Arc::new(
+        lines
+            .iter()
+            .map(|line| {
+                parse_geo_ip_range(line)
+                    .ok_or_else(|| parse_line_error(filepath.to_string(), line.clone()))
+            })
+            .collect::<Result<Vec<GeoIpRange>>>()?,
+    );
+    GEO_IP_FILES.insert(filepath.to_string(), geo_ip_ranges.clone());
+    Ok(geo_ip_ranges)
+}
+
+// Forget the ranges parsed from every file, for `clear_file_cache`.
+pub(crate) fn clear_geo_ip_files() {
+    GEO_IP_FILES.clear();
+}
+
+// Parse a line of the form `start,end,country,city,lat,lon`. Returns `None` if the line is
+// malformed or if `start` comes after `end`.
+fn parse_geo_ip_range(line: &str) -> Option<GeoIpRange> {
+    let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+    match fields.as_slice() {
+        [start, end, country, city, lat, lon] => {
+            let geo_ip_range: GeoIpRange = GeoIpRange {
+                start: Ipv4Addr::from_str(start).ok()?,
+                end: Ipv4Addr::from_str(end).ok()?,
+                country: Cow::Owned(country.to_string()),
+                city: Cow::Owned(city.to_string()),
+                lat: lat.parse().ok()?,
+                lon: lon.parse().ok()?,
+            };
+            (geo_ip_range.start <= geo_ip_range.end).then_some(geo_ip_range)
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::tests::test_tera_rand_function_returns_error;
+    use crate::geo::*;
+    use tracing_test::traced_test;
+
+    fn assert_geo_ip_within_range(geo_ip: &Value, ranges: &[GeoIpRange]) {
+        let ip: Ipv4Addr = geo_ip["ip"].as_str().unwrap().parse().unwrap();
+        let country: &str = geo_ip["country"].as_str().unwrap();
+        let city: &str = geo_ip["city"].as_str().unwrap();
+
+        let matching_range: &GeoIpRange = ranges
+            .iter()
+            .find(|range| range.country == country && range.city == city)
+            .unwrap_or_else(|| panic!("No range found for {city}, {country}"));
+        assert!(matching_range.start <= ip && ip <= matching_range.end);
+        assert_eq!(geo_ip["lat"].as_f64().unwrap(), matching_range.lat);
+        assert_eq!(geo_ip["lon"].as_f64().unwrap(), matching_range.lon);
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_geo_ip() {
+        for _ in 0..100 {
+            let geo_ip: Value = random_geo_ip(&HashMap::new()).unwrap();
+            assert_geo_ip_within_range(&geo_ip, GEO_IP_RANGES);
+        }
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_geo_ip_from_file() {
+        let path: &str = "resources/test/geo_ip_ranges.csv";
+        let file_ranges: Vec<GeoIpRange> = vec![
+            parse_geo_ip_range("10.0.0.0,10.0.255.255,US,Seattle,47.6062,-122.3321").unwrap(),
+            parse_geo_ip_range("10.1.0.0,10.1.0.255,NL,Amsterdam,52.3676,4.9041").unwrap(),
+        ];
+
+        let args: HashMap<String, Value> =
+            HashMap::from([(String::from("path"), to_value(path).unwrap())]);
+        for _ in 0..100 {
+            let geo_ip: Value = random_geo_ip(&args).unwrap();
+            assert_geo_ip_within_range(&geo_ip, &file_ranges);
+        }
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_geo_ip_from_malformed_file_returns_error() {
+        test_tera_rand_function_returns_error(
+            random_geo_ip,
+            "random_geo_ip",
+            r#"{{ random_geo_ip(path="resources/test/days.txt") }}"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_geo_ip_from_file_with_one_malformed_line_always_returns_error() {
+        let args: HashMap<String, Value> = HashMap::from([(
+            String::from("path"),
+            Value::from("resources/test/geo_ip_ranges_malformed.csv"),
+        )]);
+        // only one of the lines is malformed, so a lazily validated file would sometimes render
+        for _ in 0..100 {
+            assert!(random_geo_ip(&args).is_err());
+        }
+    }
+}
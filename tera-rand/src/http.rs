@@ -0,0 +1,142 @@
+use crate::common::parse_arg;
+use crate::rng::with_rng;
+use rand::Rng;
+use std::collections::HashMap;
+use tera::{to_value, Map, Result, Value};
+
+// Commonly used HTTP status codes along with their standard reason phrases, as registered with
+// IANA.
+const HTTP_STATUSES: &[(u16, &str)] = &[
+    (100, "Continue"),
+    (101, "Switching Protocols"),
+    (200, "OK"),
+    (201, "Created"),
+    (202, "Accepted"),
+    (204, "No Content"),
+    (206, "Partial Content"),
+    (301, "Moved Permanently"),
+    (302, "Found"),
+    (303, "See Other"),
+    (304, "Not Modified"),
+    (307, "Temporary Redirect"),
+    (308, "Permanent Redirect"),
+    (400, "Bad Request"),
+    (401, "Unauthorized"),
+    (403, "Forbidden"),
+    (404, "Not Found"),
+    (405, "Method Not Allowed"),
+    (406, "Not Acceptable"),
+    (408, "Request Timeout"),
+    (409, "Conflict"),
+    (410, "Gone"),
+    (411, "Length Required"),
+    (412, "Precondition Failed"),
+    (413, "Content Too Large"),
+    (414, "URI Too Long"),
+    (415, "Unsupported Media Type"),
+    (416, "Range Not Satisfiable"),
+    (422, "Unprocessable Content"),
+    (429, "Too Many Requests"),
+    (500, "Internal Server Error"),
+    (501, "Not Implemented"),
+    (502, "Bad Gateway"),
+    (503, "Service Unavailable"),
+    (504, "Gateway Timeout"),
+];
+
+/// A Tera function to generate a random HTTP status code, such as `404`, sampled from a built-in
+/// table of commonly used status codes.
+///
+/// Passing `with_message=true` returns an object with the status `code` and its standard reason
+/// phrase as the `message`, such as `{"code": 404, "message": "Not Found"}`, so that a template can
+/// render a status and a matching message.
+///
+/// # Example usage
+///
+/// ```edition2021
+/// use tera::{Context, Tera};
+/// use tera_rand::random_http_status;
+///
+/// let mut tera: Tera = Tera::default();
+/// tera.register_function("random_http_status", random_http_status);
+/// let context: Context = Context::new();
+///
+/// let rendered: String = tera
+///     .render_str("{{ random_http_status() }}", &context)
+///     .unwrap();
+/// // render a status code along with its reason phrase
+/// let rendered: String = tera
+///     .render_str(
+///         r#"{% set status = random_http_status(with_message=true) -%}
+///            {"status": {{ status.code }}, "message": "{{ status.message }}"}"#,
+///         &context
+///     )
+///     .unwrap();
+/// ```
+pub fn random_http_status(args: &HashMap<String, Value>) -> Result<Value> {
+    let with_message: bool = parse_arg(args, "with_message")?.unwrap_or(false);
+
+    let (code, message): (u16, &str) =
+        HTTP_STATUSES[with_rng(|rng| rng.gen_range(0usize..HTTP_STATUSES.len()))];
+    if !with_message {
+        let json_value: Value = to_value(code)?;
+        return Ok(json_value);
+    }
+
+    let mut status: Map<String, Value> = Map::new();
+    status.insert(String::from("code"), to_value(code)?);
+    status.insert(String::from("message"), to_value(message)?);
+    Ok(Value::Object(status))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::tests::{test_tera_rand_function, test_tera_rand_function_returns_error};
+    use crate::http::*;
+    use tracing_test::traced_test;
+
+    #[test]
+    #[traced_test]
+    fn test_random_http_status() {
+        test_tera_rand_function(
+            random_http_status,
+            "random_http_status",
+            r#"{ "some_field": {{ random_http_status() }} }"#,
+            r#"\{ "some_field": [1-5]\d\d }"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_http_status_with_message_matches_code() {
+        let args: HashMap<String, Value> =
+            HashMap::from([(String::from("with_message"), Value::from(true))]);
+        for _ in 0..100 {
+            let status: Value = random_http_status(&args).unwrap();
+            let code: u64 = status["code"].as_u64().unwrap();
+            let message: &str = status["message"].as_str().unwrap();
+
+            let expected_message: &str = match code {
+                200 => "OK",
+                404 => "Not Found",
+                500 => "Internal Server Error",
+                _ => HTTP_STATUSES
+                    .iter()
+                    .find(|(table_code, _)| u64::from(*table_code) == code)
+                    .map(|(_, table_message)| *table_message)
+                    .unwrap(),
+            };
+            assert_eq!(message, expected_message);
+        }
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_http_status_with_invalid_with_message_returns_error() {
+        test_tera_rand_function_returns_error(
+            random_http_status,
+            "random_http_status",
+            r#"{{ random_http_status(with_message="yes") }}"#,
+        );
+    }
+}
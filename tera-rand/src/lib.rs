@@ -33,6 +33,12 @@
 //! {"hostname": "VtNCOgwH", "subnet": "196.119.240.0/23"}
 //! ```
 //!
+//! To register every function at once, each under its own name, call [`register_all`] instead:
+//! ```edition2021
+//! let mut tera: tera::Tera = tera::Tera::default();
+//! tera_rand::register_all(&mut tera);
+//! ```
+//!
 //! Some functions provide customization parameters. For example, [`random_string`] provides a
 //! `length` parameter to specify the length of the generated String, and [`random_ipv4_cidr`]
 //! provides `length_start` and `length_end` parameters to limit the possible prefix lengths. The
@@ -51,11 +57,58 @@
 //! }
 //! ```
 //!
+//! # Reproducible output
+//! By default, every function draws from a thread-local generator seeded by the operating system,
+//! so each render produces different data. To render the same data every time, for example in
+//! regression tests, call [`set_seed`] before rendering.
+//!
+//! # Quoting
+//! Functions which produce Strings, such as [`random_string`] and [`random_from_file`], return them
+//! without quotation marks, so a JSON template has to add the quotes itself, like
+//! `"{{ random_string() }}"`. This breaks if a value contains a quotation mark or a backslash, and
+//! it is easy to end up with doubled quotes when some fields are quoted by hand and some are not.
+//!
+//! Instead, these functions accept `quote=true`, which renders the String as a JSON string literal:
+//! wrapped in quotation marks, with any special characters escaped. The template then omits the
+//! surrounding quotes:
+//! ```json
+//! {"hostname": {{ random_string(quote=true) }}, "motto": {{ random_from_file(path="mottos.txt", quote=true) }}}
+//! ```
+//! As a rule, either quote a function call in the template or pass `quote=true`, never both.
+//!
+//! # Integer parameters
+//! The integer functions, from [`random_uint8`] to [`random_int128`], share their parameters:
+//!
+//! - `start` and `end` bound the range, and both are inclusive. Either or both may be omitted, in
+//!   which case they default to the minimum and maximum of the integer type.
+//! - `exclusive=true`, or its alias `exclusive_end=true`, excludes `end` from the range, like a
+//!   half-open range such as `0..len`. It is safe to use at the very bounds of the integer type.
+//! - `step` takes a positive integer and only generates values of the form `start + k * step`,
+//!   like 0, 5, 10, and so on for `step=5`.
+//! - `multiple_of` takes a positive integer and only generates multiples of it, like port numbers
+//!   aligned to 8 or timestamps rounded to 5-minute buckets. Unlike `step`, which counts from
+//!   `start`, `multiple_of` counts from zero, so the two cannot be combined. It is an error for
+//!   `multiple_of` to be larger than the distance between `start` and `end`.
+//! - `number_mode` takes `"auto"`, `"string"`, or `"number"` to choose whether the integer is
+//!   rendered as a string or a number. It defaults to the mode set by [`set_number_mode`]. See
+//!   [`NumberMode`] for details.
+//!
+//! It is an error for the range to be empty, for example because `start` is greater than `end`, or
+//! because `start` equals `end` and `end` is excluded.
+//!
+//! The 128-bit functions do not support `step` or `multiple_of`. Tera cannot parse integer literals
+//! beyond the range of a signed 64-bit integer, so they also accept bounds as strings of decimal
+//! digits, like `end="18446744073709551616"`, and always render integers which do not fit in 64
+//! bits as strings.
+//!
 //! [Tera]: https://github.com/Keats/tera
 //! [tera-rand-cli]: https://docs.rs/tera-rand-cli
 //! [`Tera::register_function`]: https://docs.rs/tera/latest/tera/struct.Tera.html#method.register_function
 //! [`random_string`]: crate::random_string
 //! [`random_ipv4_cidr`]: crate::random_ipv4_cidr
+//! [`register_all`]: crate::register_all
+//! [`set_seed`]: crate::set_seed
+//! [`random_from_file`]: crate::random_from_file
 #![warn(missing_debug_implementations, missing_docs)]
 
 mod common;
@@ -64,17 +117,86 @@ mod error;
 // public functions live in separate modules for maintainability,
 // but expose them in the root module for searchability
 
+mod choice;
+pub use choice::*;
+
+mod cron;
+pub use crate::cron::*;
+
+mod currency;
+pub use currency::*;
+
+mod email;
+pub use email::*;
+
+mod emoji;
+pub use emoji::*;
+
+mod event;
+pub use event::*;
+
 mod file;
 pub use file::*;
 
+mod file_cache;
+pub use file_cache::*;
+
+mod filter;
+pub use filter::*;
+
+mod foreign_key;
+pub use foreign_key::*;
+
+mod geo;
+pub use geo::*;
+
+mod http;
+pub use http::*;
+
+mod log;
+pub use crate::log::*;
+
 mod net;
 pub use net::*;
 
+mod number;
+pub use number::*;
+
+mod once;
+pub use once::*;
+
+mod phone;
+pub use phone::*;
+
 mod primitives;
 pub use primitives::*;
 
+mod register;
+pub use register::*;
+
+mod rng;
+pub use rng::*;
+
+mod schema;
+pub use schema::*;
+
+mod size;
+pub use size::*;
+
+mod sqlite;
+pub use sqlite::*;
+
 mod string;
 pub use string::*;
 
+mod text;
+pub use text::*;
+
+mod time;
+pub use crate::time::*;
+
+mod ulid;
+pub use crate::ulid::*;
+
 mod uuid;
 pub use crate::uuid::*;
@@ -0,0 +1,128 @@
+use crate::common::{parse_arg, quote_if_requested};
+use crate::error::invalid_weights;
+use crate::rng::with_rng;
+use rand::distributions::{Distribution, WeightedIndex};
+use std::collections::HashMap;
+use tera::{to_value, Result, Value};
+
+// Each log level along with the parameter which overrides its weight and its default weight. Most
+// log lines of a healthy service are INFO, some are WARN, and few are ERROR. DEBUG and TRACE are
+// usually disabled in production, so they are not generated unless their weights are passed in.
+const LOG_LEVELS: &[(&str, &str, f64)] = &[
+    ("TRACE", "trace_weight", 0.0),
+    ("DEBUG", "debug_weight", 0.0),
+    ("INFO", "info_weight", 80.0),
+    ("WARN", "warn_weight", 15.0),
+    ("ERROR", "error_weight", 5.0),
+];
+
+/// A Tera function to generate a random log level, such as `INFO`, weighted like the log lines of
+/// a typical service.
+///
+/// By default, about 80% of the levels are `INFO`, 15% are `WARN`, and 5% are `ERROR`. `DEBUG` and
+/// `TRACE` are not generated by default.
+///
+/// The `trace_weight`, `debug_weight`, `info_weight`, `warn_weight`, and `error_weight` parameters
+/// take non-negative numbers which override the default weight of each level. The weights are
+/// relative, so they do not need to sum to any particular value. It is an error for every weight to
+/// be zero.
+///
+/// Pass `quote=true` to render the level wrapped in JSON quotation marks. To render the level in
+/// lowercase, pipe it through Tera's built-in `lower` filter.
+///
+/// # Example usage
+///
+/// ```edition2021
+/// use tera::{Context, Tera};
+/// use tera_rand::random_log_level;
+///
+/// let mut tera: Tera = Tera::default();
+/// tera.register_function("random_log_level", random_log_level);
+/// let context: Context = Context::new();
+///
+/// let rendered: String = tera
+///     .render_str("{{ random_log_level() }}", &context)
+///     .unwrap();
+/// // include some DEBUG lines, and make errors more frequent
+/// let rendered: String = tera
+///     .render_str(
+///         "{{ random_log_level(debug_weight=20, error_weight=10) | lower }}",
+///         &context,
+///     )
+///     .unwrap();
+/// ```
+pub fn random_log_level(args: &HashMap<String, Value>) -> Result<Value> {
+    let weights: Vec<f64> = LOG_LEVELS
+        .iter()
+        .map(|(_, parameter, default_weight)| {
+            Ok(parse_arg(args, parameter)?.unwrap_or(*default_weight))
+        })
+        .collect::<Result<Vec<f64>>>()?;
+    let weighted_index: WeightedIndex<f64> =
+        WeightedIndex::new(weights).map_err(|source| invalid_weights(source.to_string()))?;
+
+    let (level, _, _): (&str, &str, f64) = LOG_LEVELS[with_rng(|rng| weighted_index.sample(rng))];
+    let json_value: Value = to_value(level)?;
+    quote_if_requested(args, json_value)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::tests::{test_tera_rand_function, test_tera_rand_function_returns_error};
+    use crate::log::*;
+    use tracing_test::traced_test;
+
+    #[test]
+    #[traced_test]
+    fn test_random_log_level() {
+        test_tera_rand_function(
+            random_log_level,
+            "random_log_level",
+            r#"{ "some_field": "{{ random_log_level() }}" }"#,
+            r#"\{ "some_field": "(INFO|WARN|ERROR)" }"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_log_level_is_mostly_info_by_default() {
+        let sample_count: usize = 10_000;
+        let mut level_counts: HashMap<String, usize> = HashMap::new();
+        for _ in 0..sample_count {
+            let level: Value = random_log_level(&HashMap::new()).unwrap();
+            *level_counts
+                .entry(level.as_str().unwrap().to_string())
+                .or_default() += 1;
+        }
+
+        let frequency_of = |level: &str| {
+            level_counts.get(level).copied().unwrap_or(0) as f64 / sample_count as f64
+        };
+        assert!(frequency_of("INFO") > 0.75);
+        assert!(frequency_of("WARN") > frequency_of("ERROR"));
+        assert!(frequency_of("ERROR") > 0.0 && frequency_of("ERROR") < 0.1);
+        assert_eq!(frequency_of("DEBUG") + frequency_of("TRACE"), 0.0);
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_log_level_with_overridden_weights() {
+        test_tera_rand_function(
+            random_log_level,
+            "random_log_level",
+            r#"{ "some_field": "{{ random_log_level(trace_weight=1, info_weight=0, warn_weight=0, error_weight=0) }}" }"#,
+            r#"\{ "some_field": "TRACE" }"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_log_level_with_invalid_weights_returns_error() {
+        for template in [
+            "{{ random_log_level(info_weight=0, warn_weight=0, error_weight=0) }}",
+            "{{ random_log_level(debug_weight=-1) }}",
+        ] {
+            test_tera_rand_function_returns_error(random_log_level, "random_log_level", template);
+        }
+    }
+}
@@ -1,9 +1,51 @@
-use crate::common::{gen_value_in_range, parse_arg};
-use crate::error::cidr_prefix_length_out_of_bounds;
-use rand::{thread_rng, Rng};
+use crate::common::{gen_value_in_range, parse_arg, parse_one_or_many_arg, quote_if_requested};
+use crate::error::{
+    arg_parse_error, cidr_prefix_length_out_of_bounds, conflicting_args,
+    exclude_attempts_exhausted, internal_error, missing_arg, no_values_to_sample, unsupported_arg,
+};
+use crate::rng::with_rng;
+use anyhow::anyhow;
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::Rng;
 use std::collections::HashMap;
-use std::net::{Ipv4Addr, Ipv6Addr};
-use tera::{to_value, Result, Value};
+use std::net::{AddrParseError, Ipv4Addr, Ipv6Addr};
+use std::str::FromStr;
+use tera::{to_value, Map, Result, Value};
+
+// Common services with their canonical port and transport protocol, and a weight reflecting how
+// often each appears in typical service-mesh traffic.
+const SERVICES: &[(&str, u16, &str, f64)] = &[
+    ("http", 80, "tcp", 20.0),
+    ("https", 443, "tcp", 30.0),
+    ("dns", 53, "udp", 10.0),
+    ("ssh", 22, "tcp", 3.0),
+    ("smtp", 25, "tcp", 2.0),
+    ("ntp", 123, "udp", 2.0),
+    ("syslog", 514, "udp", 2.0),
+    ("mysql", 3306, "tcp", 4.0),
+    ("postgres", 5432, "tcp", 6.0),
+    ("redis", 6379, "tcp", 6.0),
+    ("memcached", 11211, "tcp", 2.0),
+    ("mongodb", 27017, "tcp", 3.0),
+    ("elasticsearch", 9200, "tcp", 3.0),
+    ("kafka", 9092, "tcp", 5.0),
+    ("amqp", 5672, "tcp", 2.0),
+];
+
+// The number of addresses `random_ipv4` samples before giving up on finding one which is not
+// excluded.
+const MAX_EXCLUDE_ATTEMPTS: usize = 1000;
+
+// A check for whether an IPv4 address is in a special-purpose range.
+type Ipv4RangeCheck = fn(&Ipv4Addr) -> bool;
+
+// The special-purpose IPv4 ranges which can be excluded from `random_ipv4`.
+const IPV4_EXCLUDABLE_RANGES: &[(&str, Ipv4RangeCheck)] = &[
+    ("loopback", Ipv4Addr::is_loopback),
+    ("private", Ipv4Addr::is_private),
+    ("multicast", Ipv4Addr::is_multicast),
+    ("broadcast", Ipv4Addr::is_broadcast),
+];
 
 /// A Tera function to generate a random IPv4 address.
 ///
@@ -16,6 +58,24 @@ use tera::{to_value, Result, Value};
 ///
 /// It is possible to pass in both `start` and `end`, just one of them, or neither.
 ///
+/// The optional `exclude` parameter takes an array of special-purpose ranges which the address
+/// must not be in, to simulate public traffic:
+/// - `"loopback"`: `127.0.0.0/8`
+/// - `"private"`: `10.0.0.0/8`, `172.16.0.0/12`, and `192.168.0.0/16`
+/// - `"multicast"`: `224.0.0.0/4`
+/// - `"broadcast"`: `255.255.255.255`
+///
+/// An excluded address is discarded and a new one is sampled from the same range, so the remaining
+/// addresses stay equally likely. If no address outside the excluded ranges is found after 1000
+/// attempts, for example because the excluded ranges cover all of `start` to `end`, an error is
+/// returned.
+///
+/// The `format` parameter chooses how the address is written: `"dotted"` (the default) for
+/// dotted-decimal notation like `192.168.1.1`, or `"integer"` for the address as an unsigned 32-bit
+/// JSON number like `3232235777`, as some databases store it.
+///
+/// Pass `quote=true` to render the address wrapped in JSON quotation marks.
+///
 /// # Example usage
 ///
 /// ```edition2021
@@ -42,17 +102,118 @@ use tera::{to_value, Result, Value};
 /// let rendered: String = tera
 ///     .render_str(r#"{{ random_ipv4() }}"#, &context)
 ///     .unwrap();
+/// // a plausible public address
+/// let rendered: String = tera
+///     .render_str(
+///         r#"{{ random_ipv4(exclude=["loopback", "private", "multicast", "broadcast"]) }}"#,
+///         &context,
+///     )
+///     .unwrap();
+/// // as an integer
+/// let rendered: String = tera
+///     .render_str(r#"{{ random_ipv4(format="integer") }}"#, &context)
+///     .unwrap();
 /// ```
 pub fn random_ipv4(args: &HashMap<String, Value>) -> Result<Value> {
     let start_opt: Option<u32> = parse_arg(args, "start")?.map(|start: Ipv4Addr| start.into());
 
     let end_opt: Option<u32> = parse_arg(args, "end")?.map(|end: Ipv4Addr| end.into());
 
-    let random_ipv4: u32 = gen_value_in_range(start_opt, end_opt, u32::MIN, u32::MAX);
-    let random_ipv4: Ipv4Addr = random_ipv4.into();
+    let excluded_ranges: Vec<Ipv4RangeCheck> = parse_one_or_many_arg::<String>(args, "exclude")?
+        .unwrap_or_default()
+        .into_iter()
+        .map(|range_name| {
+            IPV4_EXCLUDABLE_RANGES
+                .iter()
+                .find(|(name, _)| *name == range_name)
+                .map(|(_, is_in_range)| *is_in_range)
+                .ok_or_else(|| unsupported_arg("exclude", range_name))
+        })
+        .collect::<Result<Vec<Ipv4RangeCheck>>>()?;
+    let format: String = parse_arg(args, "format")?.unwrap_or_else(|| String::from("dotted"));
+    if format != "dotted" && format != "integer" {
+        return Err(unsupported_arg("format", format));
+    }
+
+    let mut random_ipv4_opt: Option<Ipv4Addr> = None;
+    for _ in 0..MAX_EXCLUDE_ATTEMPTS {
+        let random_ipv4: u32 = gen_value_in_range(start_opt, end_opt, u32::MIN, u32::MAX)?;
+        let random_ipv4: Ipv4Addr = random_ipv4.into();
+        if !excluded_ranges
+            .iter()
+            .any(|is_in_range| is_in_range(&random_ipv4))
+        {
+            random_ipv4_opt = Some(random_ipv4);
+            break;
+        }
+    }
+    let random_ipv4: Ipv4Addr =
+        random_ipv4_opt.ok_or_else(|| exclude_attempts_exhausted(MAX_EXCLUDE_ATTEMPTS))?;
 
-    let json_value: Value = to_value(random_ipv4)?;
-    Ok(json_value)
+    let json_value: Value = match format.as_str() {
+        "integer" => Value::from(u32::from(random_ipv4)),
+        _ => to_value(random_ipv4)?,
+    };
+    quote_if_requested(args, json_value)
+}
+
+/// A Tera function to generate a random, contiguous range of IPv4 addresses, such as
+/// `10.0.0.5-10.0.0.37`.
+///
+/// The `addr_start` and `addr_end` parameters take IPv4 addresses which bound the whole range
+/// (inclusive). They default to `0.0.0.0` and `255.255.255.255`, respectively.
+///
+/// The `max_size` parameter takes the maximum number of addresses in the range, which must be at
+/// least 1. It defaults to 256. The start of the range is random, and the range then holds a
+/// random number of addresses between 1 and `max_size`, cut short if it would extend past
+/// `addr_end`. So, the end of the range is never before its start.
+///
+/// Pass `quote=true` to render the range wrapped in JSON quotation marks.
+///
+/// # Example usage
+///
+/// ```edition2021
+/// use tera::{Context, Tera};
+/// use tera_rand::random_ipv4_range;
+///
+/// let mut tera: Tera = Tera::default();
+/// tera.register_function("random_ipv4_range", random_ipv4_range);
+/// let context: Context = Context::new();
+///
+/// let rendered: String = tera
+///     .render_str("{{ random_ipv4_range() }}", &context)
+///     .unwrap();
+/// // a range of at most 32 addresses within 10.0.0.0/24
+/// let rendered: String = tera
+///     .render_str(
+///         r#"{{ random_ipv4_range(addr_start="10.0.0.0", addr_end="10.0.0.255", max_size=32) }}"#,
+///         &context,
+///     )
+///     .unwrap();
+/// ```
+pub fn random_ipv4_range(args: &HashMap<String, Value>) -> Result<Value> {
+    let addr_start_opt: Option<u32> =
+        parse_arg(args, "addr_start")?.map(|addr_start: Ipv4Addr| addr_start.into());
+    let addr_end_opt: Option<u32> =
+        parse_arg(args, "addr_end")?.map(|addr_end: Ipv4Addr| addr_end.into());
+    let max_size: u32 = parse_arg(args, "max_size")?.unwrap_or(256u32);
+    if max_size == 0 {
+        return Err(unsupported_arg("max_size", max_size.to_string()));
+    }
+
+    let addr_end: u32 = addr_end_opt.unwrap_or(u32::MAX);
+    let range_start: u32 = gen_value_in_range(addr_start_opt, Some(addr_end), u32::MIN, u32::MAX)?;
+    // the number of addresses after `range_start`, which is one less than the size of the range
+    let max_extra_addresses: u32 = (max_size - 1).min(addr_end - range_start);
+    let range_end: u32 = range_start + with_rng(|rng| rng.gen_range(0..=max_extra_addresses));
+
+    let random_range: String = format!(
+        "{}-{}",
+        Ipv4Addr::from(range_start),
+        Ipv4Addr::from(range_end)
+    );
+    let json_value: Value = to_value(random_range)?;
+    quote_if_requested(args, json_value)
 }
 
 /// A Tera function to generate a random IPv6 address.
@@ -67,6 +228,13 @@ pub fn random_ipv4(args: &HashMap<String, Value>) -> Result<Value> {
 ///
 /// It is possible to pass in both `start` and `end`, just one of them, or neither.
 ///
+/// The `format` parameter chooses how the address is written: `"colon"` (the default) for the
+/// standard colon-hexadecimal notation like `fd00::1`, or `"integer"` for the address as an
+/// unsigned 128-bit integer. Since JSON numbers cannot hold every 128-bit integer exactly, the
+/// integer is written as a decimal string, like `"336294682933583715844663186250927177729"`.
+///
+/// Pass `quote=true` to render the address wrapped in JSON quotation marks.
+///
 /// # Example usage
 ///
 /// ```edition2021
@@ -93,6 +261,10 @@ pub fn random_ipv4(args: &HashMap<String, Value>) -> Result<Value> {
 /// let rendered: String = tera
 ///     .render_str("{{ random_ipv6() }}", &context)
 ///     .unwrap();
+/// // as a decimal integer string
+/// let rendered: String = tera
+///     .render_str(r#"{{ random_ipv6(format="integer") }}"#, &context)
+///     .unwrap();
 /// ```
 pub fn random_ipv6(args: &HashMap<String, Value>) -> Result<Value> {
     let start_opt: Option<u128> =
@@ -100,11 +272,18 @@ pub fn random_ipv6(args: &HashMap<String, Value>) -> Result<Value> {
 
     let end_opt: Option<u128> = parse_arg(args, "end")?.map(|end_ipv6: Ipv6Addr| end_ipv6.into());
 
-    let random_ipv6: u128 = gen_value_in_range(start_opt, end_opt, u128::MIN, u128::MAX);
-    let random_ipv6: Ipv6Addr = random_ipv6.into();
+    let format: String = parse_arg(args, "format")?.unwrap_or_else(|| String::from("colon"));
+    if format != "colon" && format != "integer" {
+        return Err(unsupported_arg("format", format));
+    }
+
+    let random_ipv6: u128 = gen_value_in_range(start_opt, end_opt, u128::MIN, u128::MAX)?;
 
-    let json_value: Value = to_value(random_ipv6)?;
-    Ok(json_value)
+    let json_value: Value = match format.as_str() {
+        "integer" => Value::from(random_ipv6.to_string()),
+        _ => to_value(Ipv6Addr::from(random_ipv6))?,
+    };
+    quote_if_requested(args, json_value)
 }
 
 /// A Tera function to generate a random IPv4 CIDR address.
@@ -127,6 +306,8 @@ pub fn random_ipv6(args: &HashMap<String, Value>) -> Result<Value> {
 ///
 /// All of these parameters are optional, and it is possible to use any combination.
 ///
+/// Pass `quote=true` to render the CIDR block wrapped in JSON quotation marks.
+///
 /// # Example usage
 ///
 /// ```edition2021
@@ -187,7 +368,7 @@ pub fn random_ipv4_cidr(args: &HashMap<String, Value>) -> Result<Value> {
     let addr_end_opt: Option<u32> =
         parse_arg(args, "addr_end")?.map(|addr_end: Ipv4Addr| addr_end.into());
 
-    let random_addr: u32 = gen_value_in_range(addr_start_opt, addr_end_opt, u32::MIN, u32::MAX);
+    let random_addr: u32 = gen_value_in_range(addr_start_opt, addr_end_opt, u32::MIN, u32::MAX)?;
 
     let length_start: u32 =
         parse_cidr_prefix_length_and_check_bounds(args, "length_start", 0u32, u32::BITS)?
@@ -196,7 +377,7 @@ pub fn random_ipv4_cidr(args: &HashMap<String, Value>) -> Result<Value> {
         parse_cidr_prefix_length_and_check_bounds(args, "length_end", 0u32, u32::BITS)?
             .unwrap_or(u32::BITS);
 
-    let random_prefix_length: u32 = thread_rng().gen_range(length_start..=length_end);
+    let random_prefix_length: u32 = with_rng(|rng| rng.gen_range(length_start..=length_end));
     let bits_to_shift: u32 = u32::BITS - random_prefix_length;
 
     let random_prefix: u32 = match bits_to_shift {
@@ -205,9 +386,10 @@ pub fn random_ipv4_cidr(args: &HashMap<String, Value>) -> Result<Value> {
     };
     let random_prefix: Ipv4Addr = random_prefix.into();
 
+    #[allow(clippy::to_string_in_format_args)]
     let random_cidr: String = format!("{}/{}", random_prefix.to_string(), random_prefix_length);
     let json_value: Value = to_value(random_cidr)?;
-    Ok(json_value)
+    quote_if_requested(args, json_value)
 }
 
 /// A Tera function to generate a random IPv6 CIDR address.
@@ -230,6 +412,8 @@ pub fn random_ipv4_cidr(args: &HashMap<String, Value>) -> Result<Value> {
 ///
 /// All of these parameters are optional, and it is possible to use any combination.
 ///
+/// Pass `quote=true` to render the CIDR block wrapped in JSON quotation marks.
+///
 /// # Example usage
 ///
 /// ```edition2021
@@ -290,7 +474,7 @@ pub fn random_ipv6_cidr(args: &HashMap<String, Value>) -> Result<Value> {
     let addr_end_opt: Option<u128> =
         parse_arg(args, "addr_end")?.map(|addr_end: Ipv6Addr| addr_end.into());
 
-    let random_addr: u128 = gen_value_in_range(addr_start_opt, addr_end_opt, u128::MIN, u128::MAX);
+    let random_addr: u128 = gen_value_in_range(addr_start_opt, addr_end_opt, u128::MIN, u128::MAX)?;
 
     let length_start: u32 =
         parse_cidr_prefix_length_and_check_bounds(args, "length_start", 0u32, u128::BITS)?
@@ -299,7 +483,7 @@ pub fn random_ipv6_cidr(args: &HashMap<String, Value>) -> Result<Value> {
         parse_cidr_prefix_length_and_check_bounds(args, "length_end", 0u32, u128::BITS)?
             .unwrap_or(u128::BITS);
 
-    let random_prefix_length: u32 = thread_rng().gen_range(length_start..=length_end);
+    let random_prefix_length: u32 = with_rng(|rng| rng.gen_range(length_start..=length_end));
     let bits_to_shift: u32 = u128::BITS - random_prefix_length;
 
     let random_prefix: u128 = match bits_to_shift {
@@ -308,9 +492,379 @@ pub fn random_ipv6_cidr(args: &HashMap<String, Value>) -> Result<Value> {
     };
     let random_prefix: Ipv6Addr = random_prefix.into();
 
+    #[allow(clippy::to_string_in_format_args)]
     let random_cidr: String = format!("{}/{}", random_prefix.to_string(), random_prefix_length);
     let json_value: Value = to_value(random_cidr)?;
-    Ok(json_value)
+    quote_if_requested(args, json_value)
+}
+
+/// A Tera function to generate a random host address within an IPv4 subnet, such as `10.0.0.17`
+/// within `10.0.0.0/24`.
+///
+/// The required `cidr` parameter takes the subnet in CIDR notation, like `"10.0.0.0/24"`. Any
+/// host bits set in the address are ignored, so `"10.0.0.5/24"` is the same subnet.
+///
+/// By default, the network address (the first address, like `10.0.0.0`) and the broadcast address
+/// (the last address, like `10.0.0.255`) are not generated, since they cannot be assigned to a
+/// host. Pass `include_network=true` or `include_broadcast=true` to allow them. In `/31` and `/32`
+/// subnets, every address is a host, so both flags are ignored.
+///
+/// Pass `quote=true` to render the address wrapped in JSON quotation marks.
+///
+/// # Example usage
+///
+/// ```edition2021
+/// use tera::{Context, Tera};
+/// use tera_rand::random_ipv4_host;
+///
+/// let mut tera: Tera = Tera::default();
+/// tera.register_function("random_ipv4_host", random_ipv4_host);
+/// let context: Context = Context::new();
+///
+/// // one of 10.0.0.1 through 10.0.0.254
+/// let rendered: String = tera
+///     .render_str(r#"{{ random_ipv4_host(cidr="10.0.0.0/24") }}"#, &context)
+///     .unwrap();
+/// ```
+pub fn random_ipv4_host(args: &HashMap<String, Value>) -> Result<Value> {
+    let (network, prefix_length): (Ipv4Addr, u32) = parse_cidr_arg(args, u32::BITS)?;
+    let include_network: bool = parse_arg(args, "include_network")?.unwrap_or(false);
+    let include_broadcast: bool = parse_arg(args, "include_broadcast")?.unwrap_or(false);
+
+    let random_host: u128 = gen_host_in_subnet(
+        u32::from(network).into(),
+        prefix_length,
+        u32::BITS,
+        include_network,
+        include_broadcast,
+    )?;
+    let random_host: Ipv4Addr = u32::try_from(random_host)
+        .map_err(|source| internal_error(source.to_string()))?
+        .into();
+
+    let json_value: Value = to_value(random_host)?;
+    quote_if_requested(args, json_value)
+}
+
+/// A Tera function to generate a random host address within an IPv6 subnet, such as
+/// `2001:db8::4f2a` within `2001:db8::/64`.
+///
+/// The required `cidr` parameter takes the subnet in CIDR notation, like `"2001:db8::/64"`. Any
+/// host bits set in the address are ignored.
+///
+/// By default, the first address of the subnet, which is reserved as the Subnet-Router anycast
+/// address, is not generated. Pass `include_network=true` to allow it. IPv6 has no broadcast
+/// address, so every other address may be generated. In `/127` and `/128` subnets, every address
+/// is a host, so `include_network` is ignored.
+///
+/// Pass `quote=true` to render the address wrapped in JSON quotation marks.
+///
+/// # Example usage
+///
+/// ```edition2021
+/// use tera::{Context, Tera};
+/// use tera_rand::random_ipv6_host;
+///
+/// let mut tera: Tera = Tera::default();
+/// tera.register_function("random_ipv6_host", random_ipv6_host);
+/// let context: Context = Context::new();
+///
+/// let rendered: String = tera
+///     .render_str(r#"{{ random_ipv6_host(cidr="2001:db8::/64") }}"#, &context)
+///     .unwrap();
+/// ```
+pub fn random_ipv6_host(args: &HashMap<String, Value>) -> Result<Value> {
+    let (network, prefix_length): (Ipv6Addr, u32) = parse_cidr_arg(args, u128::BITS)?;
+    let include_network: bool = parse_arg(args, "include_network")?.unwrap_or(false);
+
+    let random_host: u128 = gen_host_in_subnet(
+        network.into(),
+        prefix_length,
+        u128::BITS,
+        include_network,
+        true,
+    )?;
+    let random_host: Ipv6Addr = random_host.into();
+
+    let json_value: Value = to_value(random_host)?;
+    quote_if_requested(args, json_value)
+}
+
+// Parse the `cidr` argument, like `10.0.0.0/24`, into an address and a prefix length of at most
+// `max_prefix_length`.
+fn parse_cidr_arg<A>(args: &HashMap<String, Value>, max_prefix_length: u32) -> Result<(A, u32)>
+where
+    A: FromStr<Err = AddrParseError>,
+{
+    let cidr_opt: Option<String> = parse_arg(args, "cidr")?;
+    let cidr: String = cidr_opt.ok_or_else(|| missing_arg("cidr"))?;
+
+    let (addr, prefix_length) = cidr.split_once('/').ok_or_else(|| {
+        arg_parse_error(
+            "cidr",
+            anyhow!("expected an address and a prefix length like 10.0.0.0/24, but found `{cidr}`"),
+        )
+    })?;
+    let addr: A = addr
+        .parse()
+        .map_err(|source| arg_parse_error("cidr", source))?;
+    let prefix_length: u32 = prefix_length
+        .parse()
+        .map_err(|source| arg_parse_error("cidr", source))?;
+    if prefix_length > max_prefix_length {
+        return Err(arg_parse_error(
+            "cidr",
+            anyhow!("the prefix length {prefix_length} is larger than {max_prefix_length}"),
+        ));
+    }
+    Ok((addr, prefix_length))
+}
+
+// Generate an address within the subnet of `addr` with a prefix of `prefix_length` bits, where an
+// address is `addr_bits` bits long. The first and last addresses are only generated if requested,
+// unless the subnet is too small to spare them.
+fn gen_host_in_subnet(
+    addr: u128,
+    prefix_length: u32,
+    addr_bits: u32,
+    include_network: bool,
+    include_broadcast: bool,
+) -> Result<u128> {
+    let host_bits: u32 = addr_bits - prefix_length;
+    let host_mask: u128 = match host_bits {
+        u128::BITS => u128::MAX,
+        host_bits => (1u128 << host_bits) - 1,
+    };
+    let mut first_host: u128 = addr & !host_mask;
+    let mut last_host: u128 = first_host | host_mask;
+    if host_bits >= 2 {
+        if !include_network {
+            first_host += 1;
+        }
+        if !include_broadcast {
+            last_host -= 1;
+        }
+    }
+    gen_value_in_range(Some(first_host), Some(last_host), u128::MIN, u128::MAX)
+}
+
+/// A Tera function to generate a random IPv4 netmask in dotted-decimal notation, such as
+/// `255.255.255.0`.
+///
+/// The `length_start` parameter takes an integer between 0 and 32 (inclusive) to indicate the
+/// prefix length of the generated netmask should be at least `length_start`. If `length_start` is
+/// not passed in, it defaults to 0.
+///
+/// The `length_end` parameter takes an integer between 0 and 32 (inclusive) to indicate the
+/// prefix length of the generated netmask should be at most `length_end`. If `length_end` is not
+/// passed in, it defaults to 32.
+///
+/// Pass `quote=true` to render the netmask wrapped in JSON quotation marks.
+///
+/// # Example usage
+///
+/// ```edition2021
+/// use tera::{Context, Tera};
+/// use tera_rand::random_netmask;
+///
+/// let mut tera: Tera = Tera::default();
+/// tera.register_function("random_netmask", random_netmask);
+/// let context: Context = Context::new();
+///
+/// // any prefix length
+/// let rendered: String = tera
+///     .render_str("{{ random_netmask() }}", &context)
+///     .unwrap();
+/// // prefix length bound by start and end lengths
+/// let rendered: String = tera
+///     .render_str("{{ random_netmask(length_start=16, length_end=24) }}", &context)
+///     .unwrap();
+/// ```
+pub fn random_netmask(args: &HashMap<String, Value>) -> Result<Value> {
+    let length_start: u32 =
+        parse_cidr_prefix_length_and_check_bounds(args, "length_start", 0u32, u32::BITS)?
+            .unwrap_or(0u32);
+    let length_end: u32 =
+        parse_cidr_prefix_length_and_check_bounds(args, "length_end", 0u32, u32::BITS)?
+            .unwrap_or(u32::BITS);
+
+    let random_prefix_length: u32 = with_rng(|rng| rng.gen_range(length_start..=length_end));
+    let random_netmask: u32 = u32::MAX
+        .checked_shl(u32::BITS - random_prefix_length)
+        .unwrap_or(0u32);
+    let random_netmask: Ipv4Addr = random_netmask.into();
+
+    let json_value: Value = to_value(random_netmask)?;
+    quote_if_requested(args, json_value)
+}
+
+/// A Tera function to generate a random, internally consistent service endpoint: an object with
+/// an `ip`, the `port` and transport `protocol` of a `service`, such as
+/// `{"ip": "10.4.0.17", "port": 5432, "protocol": "tcp", "service": "postgres"}`.
+///
+/// The service is sampled from a built-in table of common services, such as `https`, `dns`, and
+/// `postgres`, weighted by how often each typically appears, and the port and protocol are always
+/// the canonical ones for that service. The `services` parameter takes an array of service names
+/// to restrict the table to, keeping their relative weights.
+///
+/// The IP address comes from a pool. The `ips` parameter takes an array of IPv4 addresses to
+/// sample from. Alternatively, the `addr_start` and `addr_end` parameters take IPv4 addresses which
+/// bound the pool (inclusive). They default to `10.0.0.0` and `10.255.255.255`, respectively, the
+/// private `10.0.0.0/8` block.
+///
+/// Pass `quote=true` to render the endpoint as JSON.
+///
+/// # Example usage
+///
+/// ```edition2021
+/// use tera::{Context, Tera};
+/// use tera_rand::random_endpoint;
+///
+/// let mut tera: Tera = Tera::default();
+/// tera.register_function("random_endpoint", random_endpoint);
+/// let context: Context = Context::new();
+///
+/// let rendered: String = tera
+///     .render_str(
+///         r#"{% set endpoint = random_endpoint() %}{{ endpoint.service }} on {{ endpoint.ip }}:{{ endpoint.port }}"#,
+///         &context,
+///     )
+///     .unwrap();
+/// // only databases, on one of two hosts
+/// let rendered: String = tera
+///     .render_str(
+///         r#"{{ random_endpoint(services=["mysql", "postgres"], ips=["10.0.0.5", "10.0.0.6"], quote=true) }}"#,
+///         &context,
+///     )
+///     .unwrap();
+/// ```
+pub fn random_endpoint(args: &HashMap<String, Value>) -> Result<Value> {
+    let services: Vec<&(&str, u16, &str, f64)> = match parse_arg::<Vec<String>>(args, "services")? {
+        Some(service_names) => service_names
+            .iter()
+            .map(|service_name| {
+                SERVICES
+                    .iter()
+                    .find(|(name, ..)| name == service_name)
+                    .ok_or_else(|| unsupported_arg("services", service_name.clone()))
+            })
+            .collect::<Result<Vec<&(&str, u16, &str, f64)>>>()?,
+        None => SERVICES.iter().collect(),
+    };
+    if services.is_empty() {
+        return Err(no_values_to_sample("services"));
+    }
+    let ips_opt: Option<Vec<Ipv4Addr>> = parse_arg(args, "ips")?;
+    let addr_start_opt: Option<u32> =
+        parse_arg(args, "addr_start")?.map(|addr_start: Ipv4Addr| addr_start.into());
+    let addr_end_opt: Option<u32> =
+        parse_arg(args, "addr_end")?.map(|addr_end: Ipv4Addr| addr_end.into());
+
+    let ip: Ipv4Addr = match (ips_opt, addr_start_opt.or(addr_end_opt)) {
+        (Some(_), Some(_)) if addr_start_opt.is_some() => {
+            return Err(conflicting_args("ips", "addr_start"))
+        }
+        (Some(_), Some(_)) => return Err(conflicting_args("ips", "addr_end")),
+        (Some(ips), None) if ips.is_empty() => return Err(no_values_to_sample("ips")),
+        (Some(ips), None) => ips[with_rng(|rng| rng.gen_range(0usize..ips.len()))],
+        (None, _) => {
+            let default_start: u32 = Ipv4Addr::new(10, 0, 0, 0).into();
+            let default_end: u32 = Ipv4Addr::new(10, 255, 255, 255).into();
+            gen_value_in_range(
+                Some(addr_start_opt.unwrap_or(default_start)),
+                Some(addr_end_opt.unwrap_or(default_end)),
+                default_start,
+                default_end,
+            )?
+            .into()
+        }
+    };
+    // the weights are positive constants, so building the index cannot fail
+    let weighted_index: WeightedIndex<f64> =
+        WeightedIndex::new(services.iter().map(|(.., weight)| *weight))
+            .map_err(|source| internal_error(source.to_string()))?;
+    let (service, port, protocol, _) = services[with_rng(|rng| weighted_index.sample(rng))];
+
+    let mut endpoint: Map<String, Value> = Map::new();
+    endpoint.insert(String::from("ip"), to_value(ip)?);
+    endpoint.insert(String::from("port"), Value::from(*port));
+    endpoint.insert(String::from("protocol"), Value::from(*protocol));
+    endpoint.insert(String::from("service"), Value::from(*service));
+    quote_if_requested(args, Value::Object(endpoint))
+}
+
+/// A Tera function to generate a random MAC address, such as `3c:22:fb:0e:91:a7`.
+///
+/// The optional `oui` parameter takes the first three octets of the address, the Organizationally
+/// Unique Identifier which names the vendor, like `"00:1A:2B"`. The octets may be separated by
+/// colons or hyphens. Only the last three octets are then random.
+///
+/// The `format` parameter chooses how the address is written: `"colon"` (the default) for
+/// `3c:22:fb:0e:91:a7`, `"hyphen"` for `3c-22-fb-0e-91-a7`, or `"cisco"` for `3c22.fb0e.91a7`.
+/// The hex digits are always lowercase.
+///
+/// Pass `quote=true` to render the address wrapped in JSON quotation marks.
+///
+/// # Example usage
+///
+/// ```edition2021
+/// use tera::{Context, Tera};
+/// use tera_rand::random_mac;
+///
+/// let mut tera: Tera = Tera::default();
+/// tera.register_function("random_mac", random_mac);
+/// let context: Context = Context::new();
+///
+/// let rendered: String = tera
+///     .render_str("{{ random_mac() }}", &context)
+///     .unwrap();
+/// // a device from a single vendor, in Cisco notation
+/// let rendered: String = tera
+///     .render_str(r#"{{ random_mac(oui="00:1A:2B", format="cisco") }}"#, &context)
+///     .unwrap();
+/// ```
+pub fn random_mac(args: &HashMap<String, Value>) -> Result<Value> {
+    let oui_opt: Option<[u8; 3]> = parse_arg::<String>(args, "oui")?
+        .map(|oui| parse_oui(&oui))
+        .transpose()?;
+    let format: String = parse_arg(args, "format")?.unwrap_or_else(|| String::from("colon"));
+
+    let mut octets: [u8; 6] = with_rng(|rng| rng.gen());
+    if let Some(oui) = oui_opt {
+        octets[..3].copy_from_slice(&oui);
+    }
+
+    let hex_octets: Vec<String> = octets.iter().map(|octet| format!("{octet:02x}")).collect();
+    let random_mac: String = match format.as_str() {
+        "colon" => hex_octets.join(":"),
+        "hyphen" => hex_octets.join("-"),
+        "cisco" => hex_octets
+            .chunks(2)
+            .map(|pair| pair.concat())
+            .collect::<Vec<String>>()
+            .join("."),
+        _ => return Err(unsupported_arg("format", format)),
+    };
+    let json_value: Value = to_value(random_mac)?;
+    quote_if_requested(args, json_value)
+}
+
+// Parse three hex octets separated by colons or hyphens, like `00:1A:2B`.
+fn parse_oui(oui: &str) -> Result<[u8; 3]> {
+    let parts: Vec<&str> = oui.split([':', '-']).collect();
+    let is_hex_octet = |part: &&str| part.len() == 2 && part.chars().all(|c| c.is_ascii_hexdigit());
+    if parts.len() != 3 || !parts.iter().all(is_hex_octet) {
+        return Err(arg_parse_error(
+            "oui",
+            anyhow!("expected three hex octets like 00:1A:2B, but found `{oui}`"),
+        ));
+    }
+
+    let mut octets: [u8; 3] = [0u8; 3];
+    for (octet, part) in octets.iter_mut().zip(parts) {
+        *octet = u8::from_str_radix(part, 16).map_err(|source| arg_parse_error("oui", source))?;
+    }
+    Ok(octets)
 }
 
 fn parse_cidr_prefix_length_and_check_bounds(
@@ -338,6 +892,7 @@ fn parse_cidr_prefix_length_and_check_bounds(
 mod tests {
     use crate::common::tests::{test_tera_rand_function, test_tera_rand_function_returns_error};
     use crate::net::*;
+    use std::collections::HashSet;
     use tracing_test::traced_test;
 
     // ipv4 address
@@ -352,6 +907,67 @@ mod tests {
         );
     }
 
+    #[test]
+    #[traced_test]
+    fn test_random_ipv4_with_exclude_never_returns_loopback() {
+        let args: HashMap<String, Value> = HashMap::from([
+            (String::from("start"), Value::from("127.255.255.0")),
+            (String::from("end"), Value::from("128.0.0.255")),
+            (String::from("exclude"), Value::from(vec!["loopback"])),
+        ]);
+        for _ in 0..500 {
+            let ipv4: Value = random_ipv4(&args).unwrap();
+            let ipv4: Ipv4Addr = ipv4.as_str().unwrap().parse().unwrap();
+            assert!(!ipv4.is_loopback(), "{ipv4} is a loopback address");
+        }
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_ipv4_with_every_exclusion() {
+        let args: HashMap<String, Value> = HashMap::from([(
+            String::from("exclude"),
+            Value::from(vec!["loopback", "private", "multicast", "broadcast"]),
+        )]);
+        for _ in 0..500 {
+            let ipv4: Value = random_ipv4(&args).unwrap();
+            let ipv4: Ipv4Addr = ipv4.as_str().unwrap().parse().unwrap();
+            assert!(
+                !(ipv4.is_loopback()
+                    || ipv4.is_private()
+                    || ipv4.is_multicast()
+                    || ipv4.is_broadcast()),
+                "{ipv4} should be excluded"
+            );
+        }
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_ipv4_with_invalid_exclude_returns_error() {
+        test_tera_rand_function_returns_error(
+            random_ipv4,
+            "random_ipv4",
+            r#"{{ random_ipv4(exclude=["reserved"]) }}"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_ipv4_with_every_address_excluded_returns_error() {
+        let args: HashMap<String, Value> = HashMap::from([
+            (String::from("start"), Value::from("127.0.0.0")),
+            (String::from("end"), Value::from("127.0.0.255")),
+            (String::from("exclude"), Value::from(vec!["loopback"])),
+        ]);
+
+        let error_message: String = random_ipv4(&args).unwrap_err().to_string();
+        assert!(
+            error_message.contains("not excluded by the `exclude` argument after 1000 attempts"),
+            "{error_message}"
+        );
+    }
+
     #[test]
     #[traced_test]
     fn test_random_ipv4_with_both_start_and_end() {
@@ -407,6 +1023,62 @@ mod tests {
         );
     }
 
+    // ipv4 range
+    #[test]
+    #[traced_test]
+    fn test_random_ipv4_range() {
+        test_tera_rand_function(
+            random_ipv4_range,
+            "random_ipv4_range",
+            r#"{ "some_field": "{{ random_ipv4_range() }}" }"#,
+            r#"\{ "some_field": "\d+\.\d+\.\d+\.\d+-\d+\.\d+\.\d+\.\d+" }"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_ipv4_range_stays_within_bounds_and_max_size() {
+        let args: HashMap<String, Value> = HashMap::from([
+            (String::from("addr_start"), Value::from("10.0.0.0")),
+            (String::from("addr_end"), Value::from("10.0.0.40")),
+            (String::from("max_size"), Value::from(8)),
+        ]);
+
+        for _ in 0..1000 {
+            let range: Value = random_ipv4_range(&args).unwrap();
+            let (start, end): (&str, &str) = range.as_str().unwrap().split_once('-').unwrap();
+            let start: u32 = start.parse::<Ipv4Addr>().unwrap().into();
+            let end: u32 = end.parse::<Ipv4Addr>().unwrap().into();
+
+            assert!(start <= end);
+            assert!(end - start < 8);
+            assert!(start >= Ipv4Addr::new(10, 0, 0, 0).into());
+            assert!(end <= Ipv4Addr::new(10, 0, 0, 40).into());
+        }
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_ipv4_range_near_max() {
+        test_tera_rand_function(
+            random_ipv4_range,
+            "random_ipv4_range",
+            r#"{ "some_field": "{{ random_ipv4_range(addr_start="255.255.255.255") }}" }"#,
+            r#"\{ "some_field": "255\.255\.255\.255-255\.255\.255\.255" }"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_ipv4_range_with_invalid_args_returns_error() {
+        for template in [
+            "{{ random_ipv4_range(max_size=0) }}",
+            r#"{{ random_ipv4_range(addr_start="10.0.0.2", addr_end="10.0.0.1") }}"#,
+        ] {
+            test_tera_rand_function_returns_error(random_ipv4_range, "random_ipv4_range", template);
+        }
+    }
+
     // ipv6 address
     #[test]
     #[traced_test]
@@ -561,6 +1233,61 @@ mod tests {
         );
     }
 
+    // netmask
+    #[test]
+    #[traced_test]
+    fn test_random_netmask_is_contiguous() {
+        for _ in 0..1000 {
+            let netmask: Value = random_netmask(&HashMap::new()).unwrap();
+            let netmask: Ipv4Addr = netmask.as_str().unwrap().parse().unwrap();
+            let netmask: u32 = netmask.into();
+            assert_eq!(netmask.leading_ones() + netmask.trailing_zeros(), u32::BITS);
+        }
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_netmask_with_prefix_length_start_and_end() {
+        test_tera_rand_function(
+            random_netmask,
+            "random_netmask",
+            r#"{ "some_field": "{{ random_netmask(length_start=23, length_end=25) }}" }"#,
+            r#"\{ "some_field": "255\.255\.(254\.0|255\.0|255\.128)" }"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_netmask_with_32_bit_prefix() {
+        test_tera_rand_function(
+            random_netmask,
+            "random_netmask",
+            r#"{ "some_field": "{{ random_netmask(length_start=32) }}" }"#,
+            r#"\{ "some_field": "255\.255\.255\.255" }"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_netmask_with_0_bit_prefix() {
+        test_tera_rand_function(
+            random_netmask,
+            "random_netmask",
+            r#"{ "some_field": "{{ random_netmask(length_end=0) }}" }"#,
+            r#"\{ "some_field": "0\.0\.0\.0" }"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_netmask_with_too_large_prefix_length_returns_error() {
+        test_tera_rand_function_returns_error(
+            random_netmask,
+            "random_netmask",
+            r#"{{ random_netmask(length_end=33) }}"#,
+        );
+    }
+
     // ipv6 cidr
     #[test]
     #[traced_test]
@@ -647,4 +1374,328 @@ mod tests {
             r#"{ "some_field": "{{ random_ipv6_cidr(length_start=-1, length_end=16) }}" }"#,
         );
     }
+
+    // endpoint
+    #[test]
+    #[traced_test]
+    fn test_random_endpoint() {
+        test_tera_rand_function(
+            random_endpoint,
+            "random_endpoint",
+            r#"{{ random_endpoint(quote=true) }}"#,
+            r#"^\{"ip":"10\.\d+\.\d+\.\d+","port":\d+,"protocol":"(tcp|udp)","service":"[a-z]+"}$"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_endpoint_port_matches_service() {
+        let args: HashMap<String, Value> = HashMap::from([(
+            String::from("ips"),
+            Value::from(vec!["192.168.1.1", "192.168.1.2"]),
+        )]);
+
+        let mut sampled_services: Vec<String> = Vec::new();
+        for _ in 0..1000 {
+            let endpoint: Value = random_endpoint(&args).unwrap();
+            let service: &str = endpoint["service"].as_str().unwrap();
+            let (_, port, protocol, _) =
+                SERVICES.iter().find(|(name, ..)| *name == service).unwrap();
+            assert_eq!(endpoint["port"], *port);
+            assert_eq!(endpoint["protocol"], *protocol);
+            assert!(endpoint["ip"] == "192.168.1.1" || endpoint["ip"] == "192.168.1.2");
+            if !sampled_services.iter().any(|sampled| sampled == service) {
+                sampled_services.push(String::from(service));
+            }
+        }
+        assert!(sampled_services.len() > 5);
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_endpoint_with_services_and_addr_range() {
+        let args: HashMap<String, Value> = HashMap::from([
+            (String::from("services"), Value::from(vec!["dns"])),
+            (String::from("addr_start"), Value::from("172.16.0.0")),
+            (String::from("addr_end"), Value::from("172.16.0.3")),
+        ]);
+
+        for _ in 0..100 {
+            let endpoint: Value = random_endpoint(&args).unwrap();
+            assert_eq!(endpoint["service"], "dns");
+            assert_eq!(endpoint["port"], 53);
+            assert_eq!(endpoint["protocol"], "udp");
+            let ip: Ipv4Addr = endpoint["ip"].as_str().unwrap().parse().unwrap();
+            assert_eq!(ip.octets()[..3], [172, 16, 0]);
+            assert!(ip.octets()[3] <= 3);
+        }
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_endpoint_with_invalid_args_returns_error() {
+        for template in [
+            r#"{{ random_endpoint(services=["gopher"]) }}"#,
+            "{{ random_endpoint(services=[]) }}",
+            "{{ random_endpoint(ips=[]) }}",
+            r#"{{ random_endpoint(ips=["10.0.0.1"], addr_start="10.0.0.0") }}"#,
+            r#"{{ random_endpoint(addr_start="10.0.0.9", addr_end="10.0.0.1") }}"#,
+        ] {
+            test_tera_rand_function_returns_error(random_endpoint, "random_endpoint", template);
+        }
+    }
+
+    // integer format
+    #[test]
+    #[traced_test]
+    fn test_random_ipv4_with_integer_format_round_trips() {
+        test_tera_rand_function(
+            random_ipv4,
+            "random_ipv4",
+            r#"{ "some_field": {{ random_ipv4(start="192.168.1.1", end="192.168.1.1", format="integer") }} }"#,
+            r#"^\{ "some_field": 3232235777 }$"#,
+        );
+
+        let args: HashMap<String, Value> =
+            HashMap::from([(String::from("format"), Value::from("integer"))]);
+        for _ in 0..100 {
+            let integer: u32 =
+                u32::try_from(random_ipv4(&args).unwrap().as_u64().unwrap()).unwrap();
+            let dotted: Ipv4Addr = integer.into();
+            assert_eq!(
+                dotted.to_string().parse::<Ipv4Addr>().map(u32::from),
+                Ok(integer)
+            );
+        }
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_ipv6_with_integer_format_round_trips() {
+        let args: HashMap<String, Value> = HashMap::from([
+            (String::from("start"), Value::from("fd00::1")),
+            (String::from("end"), Value::from("fd00::1")),
+            (String::from("format"), Value::from("integer")),
+        ]);
+        let integer: Value = random_ipv6(&args).unwrap();
+        let integer: u128 = integer.as_str().unwrap().parse().unwrap();
+        assert_eq!(
+            Ipv6Addr::from(integer),
+            "fd00::1".parse::<Ipv6Addr>().unwrap()
+        );
+
+        let args: HashMap<String, Value> =
+            HashMap::from([(String::from("format"), Value::from("integer"))]);
+        for _ in 0..100 {
+            let integer: Value = random_ipv6(&args).unwrap();
+            let integer: u128 = integer.as_str().unwrap().parse().unwrap();
+            let colon: Ipv6Addr = integer.into();
+            assert_eq!(
+                colon.to_string().parse::<Ipv6Addr>().map(u128::from),
+                Ok(integer)
+            );
+        }
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_ip_with_unknown_format_returns_error() {
+        test_tera_rand_function_returns_error(
+            random_ipv4,
+            "random_ipv4",
+            r#"{{ random_ipv4(format="colon") }}"#,
+        );
+        test_tera_rand_function_returns_error(
+            random_ipv6,
+            "random_ipv6",
+            r#"{{ random_ipv6(format="dotted") }}"#,
+        );
+    }
+
+    // host within a subnet
+    #[test]
+    #[traced_test]
+    fn test_random_ipv4_host() {
+        for _ in 0..500 {
+            test_tera_rand_function(
+                random_ipv4_host,
+                "random_ipv4_host",
+                r#"{ "some_field": "{{ random_ipv4_host(cidr="10.0.0.0/30") }}" }"#,
+                r#"^\{ "some_field": "10\.0\.0\.(1|2)" }$"#,
+            );
+        }
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_ipv4_host_with_network_and_broadcast() {
+        let args: HashMap<String, Value> = HashMap::from([
+            (String::from("cidr"), Value::from("192.168.1.7/30")),
+            (String::from("include_network"), Value::from(true)),
+            (String::from("include_broadcast"), Value::from(true)),
+        ]);
+        let hosts: HashSet<String> = (0..500)
+            .map(|_| {
+                random_ipv4_host(&args)
+                    .unwrap()
+                    .as_str()
+                    .unwrap()
+                    .to_string()
+            })
+            .collect();
+        let expected_hosts: HashSet<String> =
+            (4..=7).map(|last| format!("192.168.1.{last}")).collect();
+        assert_eq!(hosts, expected_hosts);
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_ipv4_host_with_tiny_subnets() {
+        for (cidr, expected_regex) in [
+            ("10.0.0.8/31", r#"^\{ "some_field": "10\.0\.0\.(8|9)" }$"#),
+            ("10.0.0.8/32", r#"^\{ "some_field": "10\.0\.0\.8" }$"#),
+            ("0.0.0.0/0", r#"^\{ "some_field": "\d+\.\d+\.\d+\.\d+" }$"#),
+        ] {
+            test_tera_rand_function(
+                random_ipv4_host,
+                "random_ipv4_host",
+                &format!(r#"{{ "some_field": "{{{{ random_ipv4_host(cidr="{cidr}") }}}}" }}"#),
+                expected_regex,
+            );
+        }
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_ipv6_host() {
+        let args: HashMap<String, Value> =
+            HashMap::from([(String::from("cidr"), Value::from("2001:db8::/126"))]);
+        let hosts: HashSet<String> = (0..500)
+            .map(|_| {
+                random_ipv6_host(&args)
+                    .unwrap()
+                    .as_str()
+                    .unwrap()
+                    .to_string()
+            })
+            .collect();
+        let expected_hosts: HashSet<String> =
+            (1..=3).map(|last| format!("2001:db8::{last}")).collect();
+        assert_eq!(hosts, expected_hosts);
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_ipv6_host_within_whole_space() {
+        test_tera_rand_function(
+            random_ipv6_host,
+            "random_ipv6_host",
+            r#"{ "some_field": "{{ random_ipv6_host(cidr="::/0") }}" }"#,
+            r#"\{ "some_field": "[0-9a-f:.]+" }"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_host_with_malformed_cidr_returns_error() {
+        for template in [
+            "{{ random_ipv4_host() }}",
+            r#"{{ random_ipv4_host(cidr="10.0.0.0") }}"#,
+            r#"{{ random_ipv4_host(cidr="10.0.0/24") }}"#,
+            r#"{{ random_ipv4_host(cidr="10.0.0.0/33") }}"#,
+            r#"{{ random_ipv4_host(cidr="10.0.0.0/x") }}"#,
+            r#"{{ random_ipv4_host(cidr="2001:db8::/64") }}"#,
+        ] {
+            test_tera_rand_function_returns_error(random_ipv4_host, "random_ipv4_host", template);
+        }
+        for template in [
+            r#"{{ random_ipv6_host(cidr="2001:db8::/129") }}"#,
+            r#"{{ random_ipv6_host(cidr="10.0.0.0/24") }}"#,
+        ] {
+            test_tera_rand_function_returns_error(random_ipv6_host, "random_ipv6_host", template);
+        }
+        let error: tera::Error = parse_cidr_arg::<Ipv4Addr>(
+            &HashMap::from([(String::from("cidr"), Value::from("10.0.0.0/33"))]),
+            u32::BITS,
+        )
+        .unwrap_err();
+        assert!(
+            error.to_string().contains("Unable to parse argument"),
+            "{error}"
+        );
+    }
+
+    // mac address
+    #[test]
+    #[traced_test]
+    fn test_random_mac() {
+        test_tera_rand_function(
+            random_mac,
+            "random_mac",
+            r#"{ "some_field": "{{ random_mac() }}" }"#,
+            r#"^\{ "some_field": "[0-9a-f]{2}(:[0-9a-f]{2}){5}" }$"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_mac_with_oui() {
+        for oui in ["00:1A:2B", "00-1a-2b"] {
+            test_tera_rand_function(
+                random_mac,
+                "random_mac",
+                &format!(r#"{{ "some_field": "{{{{ random_mac(oui="{oui}") }}}}" }}"#),
+                r#"^\{ "some_field": "00:1a:2b(:[0-9a-f]{2}){3}" }$"#,
+            );
+        }
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_mac_with_format() {
+        for (format, expected_regex) in [
+            (
+                "hyphen",
+                r#"^\{ "some_field": "00-1a-2b(-[0-9a-f]{2}){3}" }$"#,
+            ),
+            (
+                "cisco",
+                r#"^\{ "some_field": "001a\.2b[0-9a-f]{2}\.[0-9a-f]{4}" }$"#,
+            ),
+        ] {
+            test_tera_rand_function(
+                random_mac,
+                "random_mac",
+                &format!(
+                    r#"{{ "some_field": "{{{{ random_mac(oui="00:1A:2B", format="{format}") }}}}" }}"#
+                ),
+                expected_regex,
+            );
+        }
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_mac_with_invalid_args_returns_error() {
+        for template in [
+            r#"{{ random_mac(oui="00:1A") }}"#,
+            r#"{{ random_mac(oui="00:1A:2B:3C") }}"#,
+            r#"{{ random_mac(oui="00:1A:ZZ") }}"#,
+            r#"{{ random_mac(oui="00:1A:+b") }}"#,
+            r#"{{ random_mac(oui="001A2B") }}"#,
+            r#"{{ random_mac(format="dotted") }}"#,
+        ] {
+            test_tera_rand_function_returns_error(random_mac, "random_mac", template);
+        }
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_parse_oui_returns_unable_to_parse_argument() {
+        let error: tera::Error = parse_oui("00:1A").unwrap_err();
+        assert!(
+            error.to_string().contains("Unable to parse argument"),
+            "{error}"
+        );
+    }
 }
@@ -0,0 +1,192 @@
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU8, Ordering};
+use tera::{to_value, Value};
+
+// The largest integer magnitude which a double-precision float, and therefore a typical JSON
+// parser, can represent exactly: 2^53 - 1.
+const MAX_SAFE_INTEGER: i128 = (1i128 << 53) - 1;
+
+static NUMBER_MODE: AtomicU8 = AtomicU8::new(NumberMode::Auto as u8);
+
+/// How the integer functions, such as [`random_uint64`], render the integers they generate.
+///
+/// Tera renders values as JSON, and many JSON consumers parse numbers as double-precision floats,
+/// which can only represent integers up to 2^53 - 1 exactly. Larger integers, such as `u64::MAX`,
+/// silently lose precision unless they are rendered as strings instead.
+///
+/// The mode can be set for every function with [`set_number_mode`], or for a single function call
+/// with the `number_mode` parameter, which takes `"auto"`, `"string"`, or `"number"`.
+///
+/// [`random_uint64`]: crate::random_uint64
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum NumberMode {
+    /// Render an integer as a number if its magnitude is at most 2^53 - 1, or as a string
+    /// otherwise. This is the default.
+    #[default]
+    Auto,
+    /// Always render integers as strings.
+    String,
+    /// Always render integers as numbers, even if a consumer may lose precision.
+    Number,
+}
+
+impl NumberMode {
+    fn from_u8(value: u8) -> Self {
+        match value {
+            value if value == NumberMode::String as u8 => NumberMode::String,
+            value if value == NumberMode::Number as u8 => NumberMode::Number,
+            _ => NumberMode::Auto,
+        }
+    }
+}
+
+/// Set the [`NumberMode`] used by the integer functions when a call does not pass a `number_mode`
+/// argument. Unlike [`set_seed`], this setting applies to every thread.
+///
+/// # Example usage
+///
+/// ```edition2021
+/// use tera::{Context, Tera};
+/// use tera_rand::{random_uint32, set_number_mode, NumberMode};
+///
+/// let mut tera: Tera = Tera::default();
+/// tera.register_function("random_uint32", random_uint32);
+/// let context: Context = Context::new();
+///
+/// set_number_mode(NumberMode::String);
+/// let rendered: String = tera
+///     .render_str("{{ random_uint32(start=7, end=7) | json_encode() }}", &context)
+///     .unwrap();
+/// assert_eq!(rendered, r#""7""#);
+/// ```
+///
+/// [`set_seed`]: crate::set_seed
+pub fn set_number_mode(number_mode: NumberMode) {
+    NUMBER_MODE.store(number_mode as u8, Ordering::Relaxed);
+}
+
+// The mode set by `set_number_mode`, or `NumberMode::Auto` if it was never called.
+pub(crate) fn number_mode() -> NumberMode {
+    NumberMode::from_u8(NUMBER_MODE.load(Ordering::Relaxed))
+}
+
+// Convert a generated integer into a value for Tera to render according to `number_mode`.
+pub(crate) fn integer_to_value<T>(integer: T, number_mode: NumberMode) -> Value
+where
+    T: Into<i128> + Into<Value> + ToString + Copy,
+{
+    let render_as_string: bool = match number_mode {
+        NumberMode::Auto => Into::<i128>::into(integer).abs() > MAX_SAFE_INTEGER,
+        NumberMode::String => true,
+        NumberMode::Number => false,
+    };
+    if render_as_string {
+        Value::String(integer.to_string())
+    } else {
+        integer.into()
+    }
+}
+
+// Like `integer_to_value`, but for integers which may not fit in 64 bits. Tera can only render
+// numbers up to 64 bits, so larger integers are rendered as strings even in `NumberMode::Number`.
+pub(crate) fn wide_integer_to_value<T>(integer: T, number_mode: NumberMode) -> Value
+where
+    T: TryInto<i64> + Serialize + ToString + Copy,
+{
+    let render_as_string: bool = match number_mode {
+        NumberMode::Auto => TryInto::<i64>::try_into(integer).map_or(true, |integer| {
+            i128::from(integer.unsigned_abs()) > MAX_SAFE_INTEGER
+        }),
+        NumberMode::String => true,
+        NumberMode::Number => false,
+    };
+    match to_value(integer) {
+        Ok(value) if !render_as_string => value,
+        _ => Value::String(integer.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::number::*;
+    use tracing_test::traced_test;
+
+    #[test]
+    #[traced_test]
+    fn test_auto_mode_keeps_safe_integers_as_numbers() {
+        let max_safe_integer: i64 = (1i64 << 53) - 1;
+        assert_eq!(
+            integer_to_value(max_safe_integer, NumberMode::Auto),
+            Value::from(max_safe_integer)
+        );
+        assert_eq!(
+            integer_to_value(-max_safe_integer, NumberMode::Auto),
+            Value::from(-max_safe_integer)
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_auto_mode_renders_unsafe_integers_as_strings() {
+        let two_to_the_53: i64 = 1i64 << 53;
+        assert_eq!(
+            integer_to_value(two_to_the_53, NumberMode::Auto),
+            Value::from("9007199254740992")
+        );
+        assert_eq!(
+            integer_to_value(-two_to_the_53, NumberMode::Auto),
+            Value::from("-9007199254740992")
+        );
+        assert_eq!(
+            integer_to_value(u64::MAX, NumberMode::Auto),
+            Value::from("18446744073709551615")
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_string_mode_always_renders_strings() {
+        assert_eq!(integer_to_value(0u32, NumberMode::String), Value::from("0"));
+        assert_eq!(
+            integer_to_value(u64::MAX, NumberMode::String),
+            Value::from("18446744073709551615")
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_number_mode_always_renders_numbers() {
+        assert_eq!(
+            integer_to_value(u64::MAX, NumberMode::Number),
+            Value::from(u64::MAX)
+        );
+        assert_eq!(
+            integer_to_value(1i64 << 53, NumberMode::Number),
+            Value::from(1i64 << 53)
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_wide_integers_beyond_64_bits_always_render_as_strings() {
+        assert_eq!(
+            wide_integer_to_value(7u128, NumberMode::Auto),
+            Value::from(7)
+        );
+        assert_eq!(
+            wide_integer_to_value(u128::from(u64::MAX), NumberMode::Number),
+            Value::from(u64::MAX)
+        );
+        for number_mode in [NumberMode::Auto, NumberMode::String, NumberMode::Number] {
+            assert_eq!(
+                wide_integer_to_value(u128::MAX, number_mode),
+                Value::from("340282366920938463463374607431768211455")
+            );
+            assert_eq!(
+                wide_integer_to_value(i128::MIN, number_mode),
+                Value::from("-170141183460469231731687303715884105728")
+            );
+        }
+    }
+}
@@ -0,0 +1,144 @@
+use crate::common::parse_arg;
+use crate::error::{conflicting_args, missing_arg, no_values_to_sample};
+use crate::rng::with_rng;
+use rand::Rng;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use tera::{Result, Value};
+
+thread_local! {
+    // the value remembered for each key by `random_once` since the last call to `clear_once_values`
+    static ONCE_VALUES: RefCell<HashMap<String, Value>> = RefCell::new(HashMap::new());
+}
+
+/// A Tera function to sample a random element once and return the same element on every later
+/// call with the same `key`, so that one random value can be reused in several places of a
+/// template without `set`.
+///
+/// The `key` parameter takes a string identifying the value. The `values` parameter takes an
+/// array to sample from on the first call for `key`. Alternatively, the `value` parameter takes a
+/// single value, such as the result of another function, to remember as-is. On later calls for the
+/// same `key`, `values` and `value` are ignored, so they may be left out.
+///
+/// Tera functions cannot tell when one render ends and the next begins, so the remembered values
+/// last until [`clear_once_values`] is called, and each value is shared by every template rendered
+/// in between. Call [`clear_once_values`] before each render to scope the values to a single
+/// render; `tera-rand-cli` does so before each record. The remembered values are thread-local.
+///
+/// # Example usage
+///
+/// ```edition2021
+/// use tera::{Context, Tera};
+/// use tera_rand::{clear_once_values, random_once, random_string};
+///
+/// let mut tera: Tera = Tera::default();
+/// tera.register_function("random_once", random_once);
+/// tera.register_function("random_string", random_string);
+/// let context: Context = Context::new();
+///
+/// clear_once_values();
+/// let rendered: String = tera
+///     .render_str(
+///         r#"{"region": "{{ random_once(key="region", values=["us", "eu"]) }}",
+///            "endpoint": "{{ random_once(key="region") }}.example.com",
+///            "trace_id": "{{ random_once(key="trace", value=random_string(length=16)) }}",
+///            "parent_trace_id": "{{ random_once(key="trace") }}"}"#,
+///         &context,
+///     )
+///     .unwrap();
+/// ```
+pub fn random_once(args: &HashMap<String, Value>) -> Result<Value> {
+    let key_opt: Option<String> = parse_arg(args, "key")?;
+    let key: String = key_opt.ok_or_else(|| missing_arg("key"))?;
+    if let Some(remembered_value) =
+        ONCE_VALUES.with(|once_values| once_values.borrow().get(&key).cloned())
+    {
+        return Ok(remembered_value);
+    }
+
+    let values_opt: Option<Vec<Value>> = parse_arg(args, "values")?;
+    let value_opt: Option<Value> = parse_arg(args, "value")?;
+    let value: Value = match (values_opt, value_opt) {
+        (Some(_), Some(_)) => return Err(conflicting_args("values", "value")),
+        (Some(values), None) if values.is_empty() => return Err(no_values_to_sample("values")),
+        (Some(mut values), None) => {
+            let index_to_sample: usize = with_rng(|rng| rng.gen_range(0usize..values.len()));
+            values.swap_remove(index_to_sample)
+        }
+        (None, Some(value)) => value,
+        (None, None) => return Err(missing_arg("values")),
+    };
+
+    ONCE_VALUES.with(|once_values| {
+        once_values.borrow_mut().insert(key, value.clone());
+    });
+    Ok(value)
+}
+
+/// Forget every value remembered by [`random_once`] on the current thread, so that the next call
+/// for each key samples a new value. Call this before each render to scope the values to a single
+/// render.
+pub fn clear_once_values() {
+    ONCE_VALUES.with(|once_values| once_values.borrow_mut().clear());
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::tests::test_tera_rand_function_returns_error;
+    use crate::once::*;
+    use crate::random_uint32;
+    use std::collections::HashSet;
+    use tera::{Context, Tera};
+    use tracing_test::traced_test;
+
+    fn render_with_random_once(template: &str) -> String {
+        let mut tera: Tera = Tera::default();
+        tera.register_function("random_once", random_once);
+        tera.register_function("random_uint32", random_uint32);
+        clear_once_values();
+        tera.render_str(template, &Context::new()).unwrap()
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_once_returns_same_value_within_render() {
+        for _ in 0..100 {
+            let rendered: String = render_with_random_once(
+                r#"{{ random_once(key="id", value=random_uint32()) }} {{ random_once(key="id", value=random_uint32()) }}"#,
+            );
+            let (first, second): (&str, &str) = rendered.split_once(' ').unwrap();
+            assert_eq!(first, second);
+        }
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_once_samples_from_values_once_per_key() {
+        let mut sampled_values: HashSet<String> = HashSet::new();
+        for _ in 0..100 {
+            let rendered: String = render_with_random_once(
+                r#"{{ random_once(key="a", values=[1, 2, 3]) }} {{ random_once(key="a") }} {{ random_once(key="b", values=[4]) }}"#,
+            );
+            let parts: Vec<&str> = rendered.split(' ').collect();
+            assert_eq!(parts[0], parts[1]);
+            assert_eq!(parts[2], "4");
+            sampled_values.insert(parts[0].to_string());
+        }
+        // clearing between renders lets later renders sample new values
+        assert_eq!(sampled_values.len(), 3);
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_once_with_invalid_args_returns_error() {
+        for template in [
+            r#"{{ random_once(values=[1]) }}"#,
+            r#"{{ random_once(key="missing") }}"#,
+            r#"{{ random_once(key="empty", values=[]) }}"#,
+            r#"{{ random_once(key="both", values=[1], value=2) }}"#,
+        ] {
+            clear_once_values();
+            test_tera_rand_function_returns_error(random_once, "random_once", template);
+        }
+    }
+}
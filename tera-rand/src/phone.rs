@@ -0,0 +1,222 @@
+use crate::common::{parse_arg, quote_if_requested};
+use crate::error::unsupported_arg;
+use crate::rng::with_rng;
+use rand::Rng;
+use std::collections::HashMap;
+use tera::{to_value, Result, Value};
+
+// A country's ISO 3166-1 alpha-2 code along with its international calling code and the number of
+// digits in a typical national (significant) number, excluding any trunk prefix.
+#[derive(Debug)]
+struct PhoneCountry {
+    code: &'static str,
+    calling_code: &'static str,
+    national_number_length: usize,
+}
+
+const PHONE_COUNTRIES: &[PhoneCountry] = &[
+    PhoneCountry {
+        code: "AU",
+        calling_code: "61",
+        national_number_length: 9,
+    },
+    PhoneCountry {
+        code: "BR",
+        calling_code: "55",
+        national_number_length: 11,
+    },
+    PhoneCountry {
+        code: "CA",
+        calling_code: "1",
+        national_number_length: 10,
+    },
+    PhoneCountry {
+        code: "CN",
+        calling_code: "86",
+        national_number_length: 11,
+    },
+    PhoneCountry {
+        code: "DE",
+        calling_code: "49",
+        national_number_length: 11,
+    },
+    PhoneCountry {
+        code: "ES",
+        calling_code: "34",
+        national_number_length: 9,
+    },
+    PhoneCountry {
+        code: "FR",
+        calling_code: "33",
+        national_number_length: 9,
+    },
+    PhoneCountry {
+        code: "GB",
+        calling_code: "44",
+        national_number_length: 10,
+    },
+    PhoneCountry {
+        code: "IN",
+        calling_code: "91",
+        national_number_length: 10,
+    },
+    PhoneCountry {
+        code: "IT",
+        calling_code: "39",
+        national_number_length: 10,
+    },
+    PhoneCountry {
+        code: "JP",
+        calling_code: "81",
+        national_number_length: 10,
+    },
+    PhoneCountry {
+        code: "KR",
+        calling_code: "82",
+        national_number_length: 10,
+    },
+    PhoneCountry {
+        code: "MX",
+        calling_code: "52",
+        national_number_length: 10,
+    },
+    PhoneCountry {
+        code: "NL",
+        calling_code: "31",
+        national_number_length: 9,
+    },
+    PhoneCountry {
+        code: "SE",
+        calling_code: "46",
+        national_number_length: 9,
+    },
+    PhoneCountry {
+        code: "SG",
+        calling_code: "65",
+        national_number_length: 8,
+    },
+    PhoneCountry {
+        code: "US",
+        calling_code: "1",
+        national_number_length: 10,
+    },
+    PhoneCountry {
+        code: "ZA",
+        calling_code: "27",
+        national_number_length: 9,
+    },
+];
+
+/// A Tera function to generate a random phone number in E.164 format, such as `+14155550123`:
+/// a `+`, followed by the country calling code, followed by the national number.
+///
+/// The `country` parameter takes the ISO 3166-1 alpha-2 code of the country, such as `US` or
+/// `GB`, which determines the calling code and the number of digits in the national number.
+/// Countries which are not in the built-in table of common countries result in an error. If
+/// `country` is not provided, a country is sampled from the table.
+///
+/// Only the calling code and the length of the number are realistic. The national number never
+/// starts with `0` or `1`, but its digits are otherwise random, so it may not belong to a real
+/// numbering plan.
+///
+/// Pass `quote=true` to render the phone number wrapped in JSON quotation marks.
+///
+/// # Example usage
+///
+/// ```edition2021
+/// use tera::{Context, Tera};
+/// use tera_rand::random_e164;
+///
+/// let mut tera: Tera = Tera::default();
+/// tera.register_function("random_e164", random_e164);
+/// let context: Context = Context::new();
+///
+/// let rendered: String = tera
+///     .render_str(r#"{{ random_e164(country="GB") }}"#, &context)
+///     .unwrap();
+/// ```
+pub fn random_e164(args: &HashMap<String, Value>) -> Result<Value> {
+    let country_opt: Option<String> = parse_arg(args, "country")?;
+
+    let country: &PhoneCountry = match country_opt {
+        Some(country_code) => PHONE_COUNTRIES
+            .iter()
+            .find(|country| country.code.eq_ignore_ascii_case(&country_code))
+            .ok_or_else(|| unsupported_arg("country", country_code))?,
+        None => &PHONE_COUNTRIES[with_rng(|rng| rng.gen_range(0usize..PHONE_COUNTRIES.len()))],
+    };
+
+    let national_number: String = with_rng(|rng| {
+        // a leading 0 or 1 is commonly reserved for trunk prefixes and special services
+        let first_digit: char = char::from(rng.gen_range(b'2'..=b'9'));
+        std::iter::once(first_digit)
+            .chain(
+                (1..country.national_number_length).map(|_| char::from(rng.gen_range(b'0'..=b'9'))),
+            )
+            .collect()
+    });
+    let json_value: Value = to_value(format!("+{}{}", country.calling_code, national_number))?;
+    quote_if_requested(args, json_value)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::tests::{test_tera_rand_function, test_tera_rand_function_returns_error};
+    use crate::phone::*;
+    use tracing_test::traced_test;
+
+    #[test]
+    #[traced_test]
+    fn test_random_e164() {
+        test_tera_rand_function(
+            random_e164,
+            "random_e164",
+            r#"{ "some_field": "{{ random_e164() }}" }"#,
+            r#"\{ "some_field": "\+\d{9,15}" }"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_e164_with_country() {
+        for (country, calling_code, national_number_length) in
+            [("US", "+1", 10usize), ("GB", "+44", 10), ("DE", "+49", 11)]
+        {
+            let args: HashMap<String, Value> =
+                HashMap::from([(String::from("country"), Value::from(country))]);
+            for _ in 0..100 {
+                let phone_number: Value = random_e164(&args).unwrap();
+                let national_number: &str = phone_number
+                    .as_str()
+                    .unwrap()
+                    .strip_prefix(calling_code)
+                    .unwrap();
+
+                assert_eq!(national_number.len(), national_number_length);
+                assert!(national_number.chars().all(|c| c.is_ascii_digit()));
+                assert!(!national_number.starts_with(['0', '1']));
+            }
+        }
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_e164_with_lowercase_country() {
+        test_tera_rand_function(
+            random_e164,
+            "random_e164",
+            r#"{ "some_field": {{ random_e164(country="fr", quote=true) }} }"#,
+            r#"\{ "some_field": "\+33[2-9]\d{8}" }"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_e164_with_unknown_country_returns_error() {
+        test_tera_rand_function_returns_error(
+            random_e164,
+            "random_e164",
+            r#"{{ random_e164(country="XX") }}"#,
+        );
+    }
+}
@@ -1,5 +1,19 @@
-use crate::common::parse_range_and_gen_value_in_range;
-use rand::random;
+use crate::common::{
+    gen_float_in_range, gen_json_safe_char, parse_arg, parse_range_and_gen_integer_in_range,
+    parse_range_and_gen_wide_integer_in_range, round_to_precision,
+};
+#[cfg(not(feature = "distributions"))]
+use crate::error::feature_not_enabled;
+use crate::error::{conflicting_args, invalid_weights, unsupported_arg};
+#[cfg(feature = "distributions")]
+use crate::error::{empty_range, missing_arg, no_values_to_sample};
+use crate::rng::with_rng;
+use rand::distributions::{Alphanumeric, Distribution, WeightedIndex};
+use rand::Rng;
+#[cfg(feature = "distributions")]
+use rand_distr::{Exp, LogNormal, Normal, Poisson};
+#[cfg(feature = "distributions")]
+use serde::Deserialize;
 use std::collections::HashMap;
 use tera::{to_value, Result, Value};
 
@@ -18,13 +32,73 @@ use tera::{to_value, Result, Value};
 /// let rendered: String = tera.render_str("{{ random_bool() }}", &context).unwrap();
 /// ```
 pub fn random_bool(_args: &HashMap<String, Value>) -> tera::Result<Value> {
-    let random_value: bool = random::<bool>();
+    let random_value: bool = with_rng(|rng| rng.gen::<bool>());
     let json_value: Value = to_value(random_value)?;
     Ok(json_value)
 }
 
+/// A Tera function to generate a random tri-state value: `true`, `false`, or `null`.
+///
+/// The `true_weight`, `false_weight`, and `null_weight` parameters take non-negative numbers which
+/// set the relative likelihood of each outcome. Each defaults to `1`, so that the three outcomes
+/// are equally likely when no weights are passed in. It is an error for every weight to be zero.
+///
+/// # Example usage
+///
+/// ```edition2021
+/// use tera::{Context, Tera};
+/// use tera_rand::random_tristate;
+///
+/// let mut tera: Tera = Tera::default();
+/// tera.register_function("random_tristate", random_tristate);
+/// let context: Context = Context::new();
+///
+/// let rendered: String = tera
+///     .render_str("{{ random_tristate() | json_encode() }}", &context)
+///     .unwrap();
+/// // mostly true, sometimes unknown, and rarely false
+/// let rendered: String = tera
+///     .render_str(
+///         "{{ random_tristate(true_weight=8, false_weight=1, null_weight=1) | json_encode() }}",
+///         &context,
+///     )
+///     .unwrap();
+/// ```
+pub fn random_tristate(args: &HashMap<String, Value>) -> Result<Value> {
+    let true_weight: f64 = parse_arg(args, "true_weight")?.unwrap_or(1.0);
+    let false_weight: f64 = parse_arg(args, "false_weight")?.unwrap_or(1.0);
+    let null_weight: f64 = parse_arg(args, "null_weight")?.unwrap_or(1.0);
+
+    let weighted_index: WeightedIndex<f64> =
+        WeightedIndex::new([true_weight, false_weight, null_weight])
+            .map_err(|source| invalid_weights(source.to_string()))?;
+    let json_value: Value = match with_rng(|rng| weighted_index.sample(rng)) {
+        0 => Value::Bool(true),
+        1 => Value::Bool(false),
+        _ => Value::Null,
+    };
+    Ok(json_value)
+}
+
 /// A Tera function to generate a random char.
 ///
+/// By default, the char is sampled uniformly from every Unicode scalar value except the quotation
+/// mark, the backslash, and the control characters from U+0000 to U+001F, which would need to be
+/// escaped inside a JSON string. The char can therefore be rendered directly inside a JSON string,
+/// as in `{ "x": "{{ random_char() }}" }`, though it is still most likely unassigned or
+/// unprintable.
+///
+/// Pass `safe=false` to sample from every Unicode scalar value instead, as earlier versions did.
+/// **Beware** that the char may then be a quotation mark, a backslash, or a control character,
+/// which corrupts JSON and other text formats when rendered unescaped.
+///
+/// To sample from a narrower set of chars, pass one of these to the `class` parameter:
+/// - `"ascii"`: any ASCII character, including control characters
+/// - `"printable"`: a printable ASCII character, from the space to `~`
+/// - `"alphanumeric"`: an ASCII letter or digit
+/// - `"alphabetic"`: an ASCII letter
+/// - `"digit"`: an ASCII digit
+///
 /// # Example usage
 ///
 /// ```edition2021
@@ -35,25 +109,116 @@ pub fn random_bool(_args: &HashMap<String, Value>) -> tera::Result<Value> {
 /// tera.register_function("random_char", random_char);
 ///
 /// let context: Context = Context::new();
-/// let rendered: String = tera.render_str("{{ random_char() }}", &context).unwrap();
+/// let rendered: String = tera
+///     .render_str(r#"{ "x": "{{ random_char() }}" }"#, &context)
+///     .unwrap();
+/// // only letters and digits
+/// let rendered: String = tera
+///     .render_str(r#"{{ random_char(class="alphanumeric") }}"#, &context)
+///     .unwrap();
 /// ```
-pub fn random_char(_args: &HashMap<String, Value>) -> Result<Value> {
-    let random_value: char = random::<char>();
+pub fn random_char(args: &HashMap<String, Value>) -> Result<Value> {
+    let class_opt: Option<String> = parse_arg(args, "class")?;
+    let safe_opt: Option<bool> = parse_arg(args, "safe")?;
+    if safe_opt.is_some() && class_opt.is_some() {
+        return Err(conflicting_args("safe", "class"));
+    }
+    let safe: bool = safe_opt.unwrap_or(true);
+
+    let random_value: char = match class_opt.as_deref() {
+        None if safe => with_rng(|rng| gen_json_safe_char(rng)),
+        None => with_rng(|rng| rng.gen::<char>()),
+        Some("ascii") => with_rng(|rng| char::from(rng.gen_range(0u8..=0x7F))),
+        Some("printable") => with_rng(|rng| char::from(rng.gen_range(b' '..=b'~'))),
+        Some("alphanumeric") => with_rng(|rng| char::from(rng.sample(Alphanumeric))),
+        Some("alphabetic") => with_rng(|rng| {
+            let letter: u8 = rng.gen_range(0u8..52);
+            char::from(if letter < 26 {
+                b'A' + letter
+            } else {
+                b'a' + letter - 26
+            })
+        }),
+        Some("digit") => with_rng(|rng| char::from(rng.gen_range(b'0'..=b'9'))),
+        Some(class) => return Err(unsupported_arg("class", class.to_string())),
+    };
     let json_value: Value = to_value(random_value)?;
     Ok(json_value)
 }
 
-/// A Tera function to generate a random unsigned 32-bit integer.
+/// A Tera function to generate a random unsigned 8-bit integer between `start` and `end`,
+/// which default to `u8::MIN` and `u8::MAX`. See [integer parameters](crate#integer-parameters)
+/// for these and its other parameters.
+///
+/// # Example usage
+///
+/// ```edition2021
+/// use tera::{Context, Tera};
+/// use tera_rand::random_uint8;
+///
+/// let mut tera: Tera = Tera::default();
+/// tera.register_function("random_uint8", random_uint8);
+/// let context: Context = Context::new();
+///
+/// // bound by both start and end
+/// let rendered: String = tera
+///     .render_str("{{ random_uint8(start=32, end=126) }}", &context)
+///     .unwrap();
+/// // bound by just start
+/// let rendered: String = tera
+///     .render_str("{{ random_uint8(start=250) }}", &context)
+///     .unwrap();
+/// // bound by just end
+/// let rendered: String = tera
+///     .render_str("{{ random_uint8(end=127) }}", &context)
+///     .unwrap();
+/// // bound by neither start nor end
+/// let rendered: String = tera
+///     .render_str("{{ random_uint8() }}", &context)
+///     .unwrap();
+/// ```
+pub fn random_uint8(args: &HashMap<String, Value>) -> Result<Value> {
+    parse_range_and_gen_integer_in_range(args, u8::MIN, u8::MAX)
+}
+
+/// A Tera function to generate a random unsigned 16-bit integer between `start` and `end`,
+/// which default to `u16::MIN` and `u16::MAX`. See [integer parameters](crate#integer-parameters)
+/// for these and its other parameters.
 ///
-/// The `start` parameter takes an unsigned 32-bit integer to indicate the beginning of the
-/// range (inclusive). If `start` is not passed in, it defaults to `u32::MIN`.
+/// # Example usage
+///
+/// ```edition2021
+/// use tera::{Context, Tera};
+/// use tera_rand::random_uint16;
 ///
-/// The `end` parameter also takes an unsigned 32-bit integer indicating the end of the range,
-/// which is also inclusive. An inclusive range allows `u32::MAX` to be sampled where an
-/// exclusive range does not. If `end` is not passed in, it defaults to `u32::MAX`.
+/// let mut tera: Tera = Tera::default();
+/// tera.register_function("random_uint16", random_uint16);
+/// let context: Context = Context::new();
 ///
-/// It is possible to pass in both `start` and `end`, just one of them, or neither in order to
-/// sample across the entire `u32` space.
+/// // bound by both start and end
+/// let rendered: String = tera
+///     .render_str("{{ random_uint16(start=1, end=4094) }}", &context)
+///     .unwrap();
+/// // bound by just start
+/// let rendered: String = tera
+///     .render_str("{{ random_uint16(start=65530) }}", &context)
+///     .unwrap();
+/// // bound by just end
+/// let rendered: String = tera
+///     .render_str("{{ random_uint16(end=1023) }}", &context)
+///     .unwrap();
+/// // bound by neither start nor end
+/// let rendered: String = tera
+///     .render_str("{{ random_uint16() }}", &context)
+///     .unwrap();
+/// ```
+pub fn random_uint16(args: &HashMap<String, Value>) -> Result<Value> {
+    parse_range_and_gen_integer_in_range(args, u16::MIN, u16::MAX)
+}
+
+/// A Tera function to generate a random unsigned 32-bit integer between `start` and `end`,
+/// which default to `u32::MIN` and `u32::MAX`. See [integer parameters](crate#integer-parameters)
+/// for these and its other parameters.
 ///
 /// # Example usage
 ///
@@ -83,20 +248,12 @@ pub fn random_char(_args: &HashMap<String, Value>) -> Result<Value> {
 ///     .unwrap();
 /// ```
 pub fn random_uint32(args: &HashMap<String, Value>) -> Result<Value> {
-    parse_range_and_gen_value_in_range(args, u32::MIN, u32::MAX)
+    parse_range_and_gen_integer_in_range(args, u32::MIN, u32::MAX)
 }
 
-/// A Tera function to generate a random unsigned 64-bit integer.
-///
-/// The `start` parameter takes an unsigned 64-bit integer to indicate the beginning of the
-/// range (inclusive). If `start` is not passed in, it defaults to `u64::MIN`.
-///
-/// The `end` parameter also takes an unsigned 64-bit integer indicating the end of the range,
-/// which is also inclusive. An inclusive range allows `u64::MAX` to be sampled where an
-/// exclusive range does not. If `end` is not passed in, it defaults to `u64::MAX`.
-///
-/// It is possible to pass in both `start` and `end`, just one of them, or neither in order to
-/// sample across the entire `u64` space.
+/// A Tera function to generate a random unsigned 64-bit integer between `start` and `end`,
+/// which default to `u64::MIN` and `u64::MAX`. See [integer parameters](crate#integer-parameters)
+/// for these and its other parameters.
 ///
 /// # Example usage
 ///
@@ -126,20 +283,117 @@ pub fn random_uint32(args: &HashMap<String, Value>) -> Result<Value> {
 ///     .unwrap();
 /// ```
 pub fn random_uint64(args: &HashMap<String, Value>) -> Result<Value> {
-    parse_range_and_gen_value_in_range(args, u64::MIN, u64::MAX)
+    parse_range_and_gen_integer_in_range(args, u64::MIN, u64::MAX)
+}
+
+/// A Tera function to generate a random unsigned 128-bit integer between `start` and `end`,
+/// which default to `u128::MIN` and `u128::MAX`. See [integer parameters](crate#integer-parameters)
+/// for these and its other parameters.
+///
+/// # Example usage
+///
+/// ```edition2021
+/// use tera::{Context, Tera};
+/// use tera_rand::random_uint128;
+///
+/// let mut tera: Tera = Tera::default();
+/// tera.register_function("random_uint128", random_uint128);
+/// let context: Context = Context::new();
+///
+/// // bound by both start and end
+/// let rendered: String = tera
+///     .render_str("{{ random_uint128(start=0, end=1000) }}", &context)
+///     .unwrap();
+/// // bound by just start, passed as a string since it is too large for Tera to parse
+/// let rendered: String = tera
+///     .render_str(r#"{{ random_uint128(start="18446744073709551616") }}"#, &context)
+///     .unwrap();
+/// // bound by just end
+/// let rendered: String = tera
+///     .render_str("{{ random_uint128(end=1000) }}", &context)
+///     .unwrap();
+/// // bound by neither start nor end
+/// let rendered: String = tera
+///     .render_str("{{ random_uint128() }}", &context)
+///     .unwrap();
+/// ```
+pub fn random_uint128(args: &HashMap<String, Value>) -> Result<Value> {
+    parse_range_and_gen_wide_integer_in_range(args, u128::MIN, u128::MAX)
+}
+
+/// A Tera function to generate a random signed 8-bit integer between `start` and `end`,
+/// which default to `i8::MIN` and `i8::MAX`. See [integer parameters](crate#integer-parameters)
+/// for these and its other parameters.
+///
+/// # Example usage
+///
+/// ```edition2021
+/// use tera::{Context, Tera};
+/// use tera_rand::random_int8;
+///
+/// let mut tera: Tera = Tera::default();
+/// tera.register_function("random_int8", random_int8);
+/// let context: Context = Context::new();
+///
+/// // bound by both start and end
+/// let rendered: String = tera
+///     .render_str("{{ random_int8(start=-10, end=10) }}", &context)
+///     .unwrap();
+/// // bound by just start
+/// let rendered: String = tera
+///     .render_str("{{ random_int8(start=1) }}", &context)
+///     .unwrap();
+/// // bound by just end
+/// let rendered: String = tera
+///     .render_str("{{ random_int8(end=-1) }}", &context)
+///     .unwrap();
+/// // bound by neither start nor end
+/// let rendered: String = tera
+///     .render_str("{{ random_int8() }}", &context)
+///     .unwrap();
+/// ```
+pub fn random_int8(args: &HashMap<String, Value>) -> Result<Value> {
+    parse_range_and_gen_integer_in_range(args, i8::MIN, i8::MAX)
 }
 
-/// A Tera function to generate a random signed 32-bit integer.
+/// A Tera function to generate a random signed 16-bit integer between `start` and `end`,
+/// which default to `i16::MIN` and `i16::MAX`. See [integer parameters](crate#integer-parameters)
+/// for these and its other parameters.
+///
+/// # Example usage
 ///
-/// The `start` parameter takes a signed 32-bit integer to indicate the beginning of the
-/// range (inclusive). If `start` is not passed in, it defaults to `i32::MIN`.
+/// ```edition2021
+/// use tera::{Context, Tera};
+/// use tera_rand::random_int16;
 ///
-/// The `end` parameter also takes an signed 32-bit integer indicating the end of the range,
-/// which is also inclusive. An inclusive range allows `i32::MAX` to be sampled where an
-/// exclusive range does not. If `end` is not passed in, it defaults to `i32::MAX`.
+/// let mut tera: Tera = Tera::default();
+/// tera.register_function("random_int16", random_int16);
+/// let context: Context = Context::new();
 ///
-/// It is possible to pass in both `start` and `end`, just one of them, or neither in order to
-/// sample across the entire `i32` space.
+/// // bound by both start and end
+/// let rendered: String = tera
+///     .render_str("{{ random_int16(start=-1000, end=1000) }}", &context)
+///     .unwrap();
+/// // bound by just start
+/// let rendered: String = tera
+///     .render_str("{{ random_int16(start=1) }}", &context)
+///     .unwrap();
+/// // bound by just end
+/// let rendered: String = tera
+///     .render_str("{{ random_int16(end=-1) }}", &context)
+///     .unwrap();
+/// // bound by neither start nor end
+/// let rendered: String = tera
+///     .render_str("{{ random_int16() }}", &context)
+///     .unwrap();
+/// ```
+pub fn random_int16(args: &HashMap<String, Value>) -> Result<Value> {
+    parse_range_and_gen_integer_in_range(args, i16::MIN, i16::MAX)
+}
+
+/// A Tera function to generate a random signed 32-bit integer between `start` and `end`,
+/// which default to `i32::MIN` and `i32::MAX`. See [integer parameters](crate#integer-parameters)
+/// for these and its other parameters.
 ///
 /// # Example usage
 ///
@@ -169,20 +423,12 @@ pub fn random_uint64(args: &HashMap<String, Value>) -> Result<Value> {
 ///     .unwrap();
 /// ```
 pub fn random_int32(args: &HashMap<String, Value>) -> Result<Value> {
-    parse_range_and_gen_value_in_range(args, i32::MIN, i32::MAX)
+    parse_range_and_gen_integer_in_range(args, i32::MIN, i32::MAX)
 }
 
-/// A Tera function to generate a random signed 64-bit integer.
-///
-/// The `start` parameter takes a signed 64-bit integer to indicate the beginning of the
-/// range (inclusive). If `start` is not passed in, it defaults to `i64::MIN`.
-///
-/// The `end` parameter also takes an signed 64-bit integer indicating the end of the range,
-/// which is also inclusive. An inclusive range allows `i64::MAX` to be sampled where an
-/// exclusive range does not. If `end` is not passed in, it defaults to `i64::MAX`.
-///
-/// It is possible to pass in both `start` and `end`, just one of them, or neither in order to
-/// sample across the entire `i64` space.
+/// A Tera function to generate a random signed 64-bit integer between `start` and `end`,
+/// which default to `i64::MIN` and `i64::MAX`. See [integer parameters](crate#integer-parameters)
+/// for these and its other parameters.
 ///
 /// # Example usage
 ///
@@ -212,7 +458,42 @@ pub fn random_int32(args: &HashMap<String, Value>) -> Result<Value> {
 ///     .unwrap();
 /// ```
 pub fn random_int64(args: &HashMap<String, Value>) -> Result<Value> {
-    parse_range_and_gen_value_in_range(args, i64::MIN, i64::MAX)
+    parse_range_and_gen_integer_in_range(args, i64::MIN, i64::MAX)
+}
+
+/// A Tera function to generate a random signed 128-bit integer between `start` and `end`,
+/// which default to `i128::MIN` and `i128::MAX`. See [integer parameters](crate#integer-parameters)
+/// for these and its other parameters.
+///
+/// # Example usage
+///
+/// ```edition2021
+/// use tera::{Context, Tera};
+/// use tera_rand::random_int128;
+///
+/// let mut tera: Tera = Tera::default();
+/// tera.register_function("random_int128", random_int128);
+/// let context: Context = Context::new();
+///
+/// // bound by both start and end
+/// let rendered: String = tera
+///     .render_str("{{ random_int128(start=-1000, end=1000) }}", &context)
+///     .unwrap();
+/// // bound by just start, passed as a string since it is too large for Tera to parse
+/// let rendered: String = tera
+///     .render_str(r#"{{ random_int128(start="9223372036854775808") }}"#, &context)
+///     .unwrap();
+/// // bound by just end
+/// let rendered: String = tera
+///     .render_str("{{ random_int128(end=-1) }}", &context)
+///     .unwrap();
+/// // bound by neither start nor end
+/// let rendered: String = tera
+///     .render_str("{{ random_int128() }}", &context)
+///     .unwrap();
+/// ```
+pub fn random_int128(args: &HashMap<String, Value>) -> Result<Value> {
+    parse_range_and_gen_wide_integer_in_range(args, i128::MIN, i128::MAX)
 }
 
 /// A Tera function to generate a random 32-bit float.
@@ -228,6 +509,19 @@ pub fn random_int64(args: &HashMap<String, Value>) -> Result<Value> {
 ///
 /// It is possible to pass in both `start` and `end`, just one of them, or neither.
 ///
+/// Both endpoints can be generated, whatever their signs: `start=-6.0, end=-5.0` can generate
+/// exactly `-6.0` and exactly `-5.0`. If `start` equals `end`, that value is always generated, and
+/// if they are adjacent floats, each is generated about half the time. Any range of finite floats
+/// is supported, even one as wide as `-f32::MAX` to `f32::MAX`. It is an error for `start` to be greater
+/// than `end`.
+///
+/// The optional `precision` parameter takes a number of decimal places to round the float to, so
+/// that `precision=2` generates floats such as `0.85`. The float is rounded as a number, so it is
+/// still rendered as a number; with `precision=0`, it is a whole float such as `5.0`, which Tera
+/// renders as `5`. The rounded value is rendered as a 64-bit float, so that `0.1` is not rendered
+/// as `0.10000000149011612`. Rounding may move the float outside of the range if `start` or `end`
+/// has more decimal places than `precision`.
+///
 /// # Example usage
 ///
 /// ```edition2021
@@ -254,9 +548,24 @@ pub fn random_int64(args: &HashMap<String, Value>) -> Result<Value> {
 /// let rendered: String = tera
 ///     .render_str("{{ random_float32() }}", &context)
 ///     .unwrap();
+/// // rounded to two decimal places
+/// let rendered: String = tera
+///     .render_str("{{ random_float32(start=0.0, end=100.0, precision=2) }}", &context)
+///     .unwrap();
 /// ```
 pub fn random_float32(args: &HashMap<String, Value>) -> Result<Value> {
-    parse_range_and_gen_value_in_range(args, 0.0, 1.0)
+    let start_opt: Option<f32> = parse_arg(args, "start")?;
+    let end_opt: Option<f32> = parse_arg(args, "end")?;
+    let precision_opt: Option<u32> = parse_arg(args, "precision")?;
+
+    // every f32 is exactly representable as an f64, and rounding a sample between two f32s to the
+    // nearest f32 cannot leave the range
+    let random_value: f64 = gen_float_in_range(start_opt.map(f64::from), end_opt.map(f64::from))?;
+    let json_value: Value = match precision_opt {
+        Some(precision) => to_value(round_to_precision(random_value, precision))?,
+        None => to_value(random_value as f32)?,
+    };
+    Ok(json_value)
 }
 
 /// A Tera function to generate a random 64-bit float.
@@ -272,6 +581,18 @@ pub fn random_float32(args: &HashMap<String, Value>) -> Result<Value> {
 ///
 /// It is possible to pass in both `start` and `end`, just one of them, or neither.
 ///
+/// Both endpoints can be generated, whatever their signs: `start=-6.0, end=-5.0` can generate
+/// exactly `-6.0` and exactly `-5.0`. If `start` equals `end`, that value is always generated, and
+/// if they are adjacent floats, each is generated about half the time. Any range of finite floats
+/// is supported, even one as wide as `-f64::MAX` to `f64::MAX`. It is an error for `start` to be greater
+/// than `end`.
+///
+/// The optional `precision` parameter takes a number of decimal places to round the float to, so
+/// that `precision=2` generates floats such as `0.85`. The float is rounded as a number, so it is
+/// still rendered as a number; with `precision=0`, it is a whole float such as `5.0`, which Tera
+/// renders as `5`. Rounding may move the float outside of the range if `start` or `end` has more
+/// decimal places than `precision`.
+///
 /// # Example usage
 ///
 /// ```edition2021
@@ -298,265 +619,1365 @@ pub fn random_float32(args: &HashMap<String, Value>) -> Result<Value> {
 /// let rendered: String = tera
 ///     .render_str("{{ random_float64() }}", &context)
 ///     .unwrap();
+/// // rounded to two decimal places
+/// let rendered: String = tera
+///     .render_str("{{ random_float64(start=0.0, end=100.0, precision=2) }}", &context)
+///     .unwrap();
 /// ```
 pub fn random_float64(args: &HashMap<String, Value>) -> Result<Value> {
-    parse_range_and_gen_value_in_range(args, 0.0, 1.0)
-}
-
-#[cfg(test)]
-mod tests {
-    use crate::common::tests::test_tera_rand_function;
-    use crate::primitives::*;
-    use tracing_test::traced_test;
+    let start_opt: Option<f64> = parse_arg(args, "start")?;
+    let end_opt: Option<f64> = parse_arg(args, "end")?;
+    let precision_opt: Option<u32> = parse_arg(args, "precision")?;
 
-    #[test]
-    #[traced_test]
-    fn test_random_bool() {
-        test_tera_rand_function(
-            random_bool,
-            "random_bool",
-            r#"{ "some_field": {{ random_bool() }} }"#,
-            r#"\{ "some_field": (true|false) }"#,
-        );
+    let mut random_value: f64 = gen_float_in_range(start_opt, end_opt)?;
+    if let Some(precision) = precision_opt {
+        random_value = round_to_precision(random_value, precision);
     }
+    let json_value: Value = to_value(random_value)?;
+    Ok(json_value)
+}
 
-    #[test]
-    #[traced_test]
-    fn test_random_char() {
-        test_tera_rand_function(
-            random_char,
-            "random_char",
-            r#"{ "some_field": {{ random_char() }} }"#,
-            r#"\{ "some_field": . }"#,
-        );
+/// A Tera function to generate a random 64-bit float from a weighted mixture of distributions,
+/// such as a latency metric which is usually fast but occasionally slow. Each call samples one
+/// component in proportion to its weight, and then draws a float from that component's
+/// distribution.
+///
+/// The `components` parameter takes an array of objects, each with a `distribution` and an
+/// optional `weight`, which defaults to `1.0`. The supported distributions and their parameters
+/// are:
+/// - `normal`, with `mean` (default `0.0`) and `std_dev` (default `1.0`, must not be negative)
+/// - `exponential`, with `lambda`, the rate (default `1.0`, must be positive)
+/// - `uniform`, with `start` (default `0.0`) and `end` (default `1.0`), both inclusive
+///
+/// Since Tera templates cannot contain object literals, the components are passed in through the
+/// context.
+///
+/// This function requires the `distributions` feature, which is enabled by default.
+///
+/// # Example usage
+///
+/// ```edition2021
+/// use tera::{Context, Tera};
+/// use tera_rand::random_mixture;
+///
+/// let mut tera: Tera = Tera::default();
+/// tera.register_function("random_mixture", random_mixture);
+/// let mut context: Context = Context::new();
+///
+/// // cache hits are fast, and the occasional cache miss is slow
+/// context.insert(
+///     "latency_ms",
+///     &serde_json::json!([
+///         {"distribution": "normal", "mean": 2.0, "std_dev": 0.5, "weight": 0.9},
+///         {"distribution": "normal", "mean": 80.0, "std_dev": 15.0, "weight": 0.1},
+///     ]),
+/// );
+/// let rendered: String = tera
+///     .render_str("{{ random_mixture(components=latency_ms) }}", &context)
+///     .unwrap();
+/// ```
+#[cfg(feature = "distributions")]
+pub fn random_mixture(args: &HashMap<String, Value>) -> Result<Value> {
+    let components_opt: Option<Vec<MixtureComponent>> = parse_arg(args, "components")?;
+    let components: Vec<MixtureComponent> =
+        components_opt.ok_or_else(|| missing_arg("components"))?;
+    if components.is_empty() {
+        return Err(no_values_to_sample("components"));
     }
 
-    // uint32
-    #[test]
-    #[traced_test]
-    fn test_random_uint32() {
-        test_tera_rand_function(
-            random_uint32,
-            "random_uint32",
-            r#"{ "some_field": {{ random_uint32() }} }"#,
-            r#"\{ "some_field": \d+ }"#,
-        );
-    }
+    let samplers: Vec<ComponentSampler> = components
+        .iter()
+        .map(|component| ComponentSampler::new(&component.distribution))
+        .collect::<Result<Vec<ComponentSampler>>>()?;
+    let weighted_index: WeightedIndex<f64> =
+        WeightedIndex::new(components.iter().map(|component| component.weight))
+            .map_err(|source| invalid_weights(source.to_string()))?;
 
-    #[test]
-    #[traced_test]
-    fn test_random_uint32_near_min() {
-        test_tera_rand_function(
-            random_uint32,
-            "random_uint32",
-            r#"{ "some_field": {{ random_uint32(start=0, end=2) }} }"#,
-            r#"\{ "some_field": 0|1|2 }"#,
-        );
-    }
+    let component_index: usize = with_rng(|rng| weighted_index.sample(rng));
+    let random_value: f64 = samplers[component_index].sample()?;
+    let json_value: Value = to_value(random_value)?;
+    Ok(json_value)
+}
 
-    #[test]
-    #[traced_test]
-    fn test_random_uint32_near_max() {
-        test_tera_rand_function(
-            random_uint32,
-            "random_uint32",
-            r#"{ "some_field": {{ random_uint32(start=4294967293, end=4294967295) }} }"#,
-            r#"\{ "some_field": 4294967293|4294967294|4294967295 }"#,
-        );
-    }
+/// A stub for [`random_mixture`] when the `distributions` feature is disabled, which always
+/// returns an error explaining that the feature is not enabled.
+#[cfg(not(feature = "distributions"))]
+pub fn random_mixture(_args: &HashMap<String, Value>) -> Result<Value> {
+    Err(feature_not_enabled("random_mixture", "distributions"))
+}
+
+/// A Tera function to generate a random 64-bit float from a normal (Gaussian) distribution, which
+/// suits metrics that cluster around a typical value, such as latency or CPU load.
+///
+/// The `mean` parameter takes the center of the distribution, and defaults to `0.0`.
+///
+/// The `std_dev` parameter takes the standard deviation, which controls how far values spread from
+/// the mean: about 68% of values fall within one standard deviation of it, and about 95% within
+/// two. It must not be negative, and it defaults to `1.0`.
+///
+/// The distribution is unbounded, so a value far from the mean is unlikely but possible. Use
+/// [`random_float64`] instead when a value must stay within bounds.
+///
+/// This function requires the `distributions` feature, which is enabled by default.
+///
+/// # Example usage
+///
+/// ```edition2021
+/// use tera::{Context, Tera};
+/// use tera_rand::random_normal;
+///
+/// let mut tera: Tera = Tera::default();
+/// tera.register_function("random_normal", random_normal);
+/// let context: Context = Context::new();
+///
+/// let rendered: String = tera
+///     .render_str("{{ random_normal() }}", &context)
+///     .unwrap();
+/// // a latency of about 120ms, give or take 15ms
+/// let rendered: String = tera
+///     .render_str("{{ random_normal(mean=120.0, std_dev=15.0) }}", &context)
+///     .unwrap();
+/// ```
+#[cfg(feature = "distributions")]
+pub fn random_normal(args: &HashMap<String, Value>) -> Result<Value> {
+    let mean: f64 = parse_arg(args, "mean")?.unwrap_or(0.0);
+    let std_dev: f64 = parse_arg(args, "std_dev")?.unwrap_or(1.0);
+
+    let normal: Normal<f64> = normal_distribution(mean, std_dev)?;
+    let random_value: f64 = with_rng(|rng| normal.sample(rng));
+    let json_value: Value = to_value(random_value)?;
+    Ok(json_value)
+}
+
+/// A stub for [`random_normal`] when the `distributions` feature is disabled, which always
+/// returns an error explaining that the feature is not enabled.
+#[cfg(not(feature = "distributions"))]
+pub fn random_normal(_args: &HashMap<String, Value>) -> Result<Value> {
+    Err(feature_not_enabled("random_normal", "distributions"))
+}
+
+/// A Tera function to generate a random, non-negative 64-bit float from an exponential
+/// distribution, which models the time between independent events, such as the inter-arrival
+/// times of requests to a queue.
+///
+/// The `lambda` parameter takes the rate of events per unit of time, so that the mean of the
+/// generated values is `1 / lambda`. It must be positive, and it defaults to `1.0`.
+///
+/// This function requires the `distributions` feature, which is enabled by default.
+///
+/// # Example usage
+///
+/// ```edition2021
+/// use tera::{Context, Tera};
+/// use tera_rand::random_exponential;
+///
+/// let mut tera: Tera = Tera::default();
+/// tera.register_function("random_exponential", random_exponential);
+/// let context: Context = Context::new();
+///
+/// let rendered: String = tera
+///     .render_str("{{ random_exponential() }}", &context)
+///     .unwrap();
+/// // seconds until the next request, at an average of 20 requests per second
+/// let rendered: String = tera
+///     .render_str("{{ random_exponential(lambda=20.0) }}", &context)
+///     .unwrap();
+/// ```
+#[cfg(feature = "distributions")]
+pub fn random_exponential(args: &HashMap<String, Value>) -> Result<Value> {
+    let lambda: f64 = parse_arg(args, "lambda")?.unwrap_or(1.0);
+
+    let exponential: Exp<f64> = exponential_distribution(lambda)?;
+    let random_value: f64 = with_rng(|rng| exponential.sample(rng));
+    let json_value: Value = to_value(random_value)?;
+    Ok(json_value)
+}
+
+/// A stub for [`random_exponential`] when the `distributions` feature is disabled, which always
+/// returns an error explaining that the feature is not enabled.
+#[cfg(not(feature = "distributions"))]
+pub fn random_exponential(_args: &HashMap<String, Value>) -> Result<Value> {
+    Err(feature_not_enabled("random_exponential", "distributions"))
+}
+
+/// A Tera function to generate a random, positive 64-bit float from a log-normal distribution,
+/// whose logarithm is normally distributed. It suits values which are usually small but
+/// occasionally very large, such as response sizes or request latencies.
+///
+/// The `mu` parameter takes the mean of the logarithm of the generated values, so that their
+/// median is `e^mu`. It defaults to `0.0`.
+///
+/// The `sigma` parameter takes the standard deviation of the logarithm of the generated values,
+/// which controls how heavy the tail of large values is. It must not be negative, and it defaults
+/// to `1.0`.
+///
+/// This function requires the `distributions` feature, which is enabled by default.
+///
+/// # Example usage
+///
+/// ```edition2021
+/// use tera::{Context, Tera};
+/// use tera_rand::random_lognormal;
+///
+/// let mut tera: Tera = Tera::default();
+/// tera.register_function("random_lognormal", random_lognormal);
+/// let context: Context = Context::new();
+///
+/// let rendered: String = tera
+///     .render_str("{{ random_lognormal() }}", &context)
+///     .unwrap();
+/// // a response size with a median of about e^8, or 3KB, and a long tail
+/// let rendered: String = tera
+///     .render_str("{{ random_lognormal(mu=8.0, sigma=1.5) }}", &context)
+///     .unwrap();
+/// ```
+#[cfg(feature = "distributions")]
+pub fn random_lognormal(args: &HashMap<String, Value>) -> Result<Value> {
+    let mu: f64 = parse_arg(args, "mu")?.unwrap_or(0.0);
+    if !mu.is_finite() {
+        return Err(unsupported_arg("mu", mu.to_string()));
+    }
+    let sigma: f64 = parse_arg(args, "sigma")?.unwrap_or(1.0);
+    if !(sigma >= 0.0 && sigma.is_finite()) {
+        return Err(unsupported_arg("sigma", sigma.to_string()));
+    }
+
+    let log_normal: LogNormal<f64> =
+        LogNormal::new(mu, sigma).map_err(|_| unsupported_arg("sigma", sigma.to_string()))?;
+    let random_value: f64 = with_rng(|rng| log_normal.sample(rng));
+    let json_value: Value = to_value(random_value)?;
+    Ok(json_value)
+}
+
+/// A stub for [`random_lognormal`] when the `distributions` feature is disabled, which always
+/// returns an error explaining that the feature is not enabled.
+#[cfg(not(feature = "distributions"))]
+pub fn random_lognormal(_args: &HashMap<String, Value>) -> Result<Value> {
+    Err(feature_not_enabled("random_lognormal", "distributions"))
+}
+
+/// A Tera function to generate a random, non-negative integer from a Poisson distribution, which
+/// models the number of independent events in an interval, such as requests per second or errors
+/// per batch.
+///
+/// The `lambda` parameter takes the expected number of events per interval, which is also the mean
+/// of the generated integers. It must be positive and finite, and it defaults to `1.0`.
+///
+/// This function requires the `distributions` feature, which is enabled by default.
+///
+/// # Example usage
+///
+/// ```edition2021
+/// use tera::{Context, Tera};
+/// use tera_rand::random_poisson;
+///
+/// let mut tera: Tera = Tera::default();
+/// tera.register_function("random_poisson", random_poisson);
+/// let context: Context = Context::new();
+///
+/// let rendered: String = tera
+///     .render_str("{{ random_poisson() }}", &context)
+///     .unwrap();
+/// // requests in the last second, at an average of 250 requests per second
+/// let rendered: String = tera
+///     .render_str("{{ random_poisson(lambda=250.0) }}", &context)
+///     .unwrap();
+/// ```
+#[cfg(feature = "distributions")]
+pub fn random_poisson(args: &HashMap<String, Value>) -> Result<Value> {
+    let lambda: f64 = parse_arg(args, "lambda")?.unwrap_or(1.0);
+    if !(lambda > 0.0 && lambda.is_finite()) {
+        return Err(unsupported_arg("lambda", lambda.to_string()));
+    }
+
+    let poisson: Poisson<f64> =
+        Poisson::new(lambda).map_err(|_| unsupported_arg("lambda", lambda.to_string()))?;
+    // a Poisson sample is a whole number, and the cast saturates if it is extremely large
+    let random_value: u64 = with_rng(|rng| poisson.sample(rng)) as u64;
+    let json_value: Value = to_value(random_value)?;
+    Ok(json_value)
+}
+
+/// A stub for [`random_poisson`] when the `distributions` feature is disabled, which always
+/// returns an error explaining that the feature is not enabled.
+#[cfg(not(feature = "distributions"))]
+pub fn random_poisson(_args: &HashMap<String, Value>) -> Result<Value> {
+    Err(feature_not_enabled("random_poisson", "distributions"))
+}
+
+// One component of a mixture passed to `random_mixture`.
+#[cfg(feature = "distributions")]
+#[derive(Debug, Deserialize)]
+struct MixtureComponent {
+    #[serde(flatten)]
+    distribution: MixtureDistribution,
+    #[serde(default = "default_to_one")]
+    weight: f64,
+}
+
+#[cfg(feature = "distributions")]
+#[derive(Debug, Deserialize)]
+#[serde(tag = "distribution", rename_all = "lowercase")]
+enum MixtureDistribution {
+    Normal {
+        #[serde(default)]
+        mean: f64,
+        #[serde(default = "default_to_one")]
+        std_dev: f64,
+    },
+    Exponential {
+        #[serde(default = "default_to_one")]
+        lambda: f64,
+    },
+    Uniform {
+        #[serde(default)]
+        start: f64,
+        #[serde(default = "default_to_one")]
+        end: f64,
+    },
+}
+
+#[cfg(feature = "distributions")]
+fn default_to_one() -> f64 {
+    1.0
+}
+
+// A validated distribution which a mixture component draws from.
+#[cfg(feature = "distributions")]
+#[derive(Debug)]
+enum ComponentSampler {
+    Normal(Normal<f64>),
+    Exponential(Exp<f64>),
+    // the inclusive bounds of a uniform distribution, which is sampled by `gen_float_in_range` so
+    // that a range as wide as `-f64::MAX..=f64::MAX` cannot overflow
+    Uniform { start: f64, end: f64 },
+}
+
+#[cfg(feature = "distributions")]
+impl ComponentSampler {
+    fn new(distribution: &MixtureDistribution) -> Result<Self> {
+        match *distribution {
+            MixtureDistribution::Normal { mean, std_dev } => {
+                normal_distribution(mean, std_dev).map(ComponentSampler::Normal)
+            }
+            MixtureDistribution::Exponential { lambda } => {
+                exponential_distribution(lambda).map(ComponentSampler::Exponential)
+            }
+            MixtureDistribution::Uniform { start, end } => {
+                if !(start <= end && start.is_finite() && end.is_finite()) {
+                    return Err(empty_range(start.to_string(), end.to_string()));
+                }
+                Ok(ComponentSampler::Uniform { start, end })
+            }
+        }
+    }
+
+    fn sample(&self) -> Result<f64> {
+        match self {
+            ComponentSampler::Normal(normal) => Ok(with_rng(|rng| normal.sample(rng))),
+            ComponentSampler::Exponential(exponential) => {
+                Ok(with_rng(|rng| exponential.sample(rng)))
+            }
+            ComponentSampler::Uniform { start, end } => {
+                gen_float_in_range(Some(*start), Some(*end))
+            }
+        }
+    }
+}
+
+// Build a normal distribution, rejecting a negative or non-finite standard deviation.
+#[cfg(feature = "distributions")]
+fn normal_distribution(mean: f64, std_dev: f64) -> Result<Normal<f64>> {
+    if !(std_dev >= 0.0 && std_dev.is_finite()) {
+        return Err(unsupported_arg("std_dev", std_dev.to_string()));
+    }
+    Normal::new(mean, std_dev).map_err(|_| unsupported_arg("mean", mean.to_string()))
+}
+
+// Build an exponential distribution, rejecting a rate which is not positive.
+#[cfg(feature = "distributions")]
+fn exponential_distribution(lambda: f64) -> Result<Exp<f64>> {
+    if lambda.is_nan() || lambda <= 0.0 {
+        return Err(unsupported_arg("lambda", lambda.to_string()));
+    }
+    Exp::new(lambda).map_err(|_| unsupported_arg("lambda", lambda.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::tests::{test_tera_rand_function, test_tera_rand_function_returns_error};
+    use crate::primitives::*;
+    use regex::Regex;
+    use std::collections::HashSet;
+    use tera::{Context, Tera};
+    use tracing_test::traced_test;
+
+    #[test]
+    #[traced_test]
+    fn test_random_bool() {
+        test_tera_rand_function(
+            random_bool,
+            "random_bool",
+            r#"{ "some_field": {{ random_bool() }} }"#,
+            r#"\{ "some_field": (true|false) }"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_char() {
+        test_tera_rand_function(
+            random_char,
+            "random_char",
+            r#"{ "some_field": {{ random_char() }} }"#,
+            r#"\{ "some_field": . }"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_char_in_json_string_is_valid_json() {
+        let mut tera: Tera = Tera::default();
+        tera.register_function("random_char", random_char);
+        let context: Context = Context::new();
+
+        for _ in 0..2000 {
+            let rendered: String = tera
+                .render_str(r#"{ "some_field": "{{ random_char() }}" }"#, &context)
+                .unwrap();
+            let parsed: Value = serde_json::from_str(&rendered)
+                .unwrap_or_else(|e| panic!("{rendered:?} is not valid JSON: {e}"));
+            assert_eq!(parsed["some_field"].as_str().unwrap().chars().count(), 1);
+        }
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_char_with_safe_false_samples_every_scalar_value() {
+        let args: HashMap<String, Value> =
+            HashMap::from([(String::from("safe"), Value::from(false))]);
+        for _ in 0..500 {
+            let random_char: Value = random_char(&args).unwrap();
+            assert_eq!(random_char.as_str().unwrap().chars().count(), 1);
+        }
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_char_with_safe_and_class_returns_error() {
+        test_tera_rand_function_returns_error(
+            random_char,
+            "random_char",
+            r#"{{ random_char(safe=true, class="digit") }}"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_char_with_class() {
+        for (class, is_in_class) in [
+            ("ascii", char::is_ascii as fn(&char) -> bool),
+            ("printable", |c: &char| c.is_ascii_graphic() || *c == ' '),
+            ("alphanumeric", char::is_ascii_alphanumeric),
+            ("alphabetic", char::is_ascii_alphabetic),
+            ("digit", char::is_ascii_digit),
+        ] {
+            let args: HashMap<String, Value> =
+                HashMap::from([(String::from("class"), Value::from(class))]);
+            for _ in 0..500 {
+                let random_char: Value = random_char(&args).unwrap();
+                let random_char: char = random_char.as_str().unwrap().chars().next().unwrap();
+                assert!(is_in_class(&random_char), "{random_char:?} is not {class}");
+            }
+        }
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_char_alphabetic_covers_both_cases() {
+        let args: HashMap<String, Value> =
+            HashMap::from([(String::from("class"), Value::from("alphabetic"))]);
+        let letters: HashSet<char> = (0..2000)
+            .map(|_| {
+                random_char(&args)
+                    .unwrap()
+                    .as_str()
+                    .unwrap()
+                    .chars()
+                    .next()
+                    .unwrap()
+            })
+            .collect();
+        assert_eq!(letters.len(), 52);
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_char_with_unknown_class_returns_error() {
+        test_tera_rand_function_returns_error(
+            random_char,
+            "random_char",
+            r#"{{ random_char(class="emoji") }}"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_tristate() {
+        test_tera_rand_function(
+            random_tristate,
+            "random_tristate",
+            r#"{ "some_field": {{ random_tristate() | json_encode() }} }"#,
+            r#"\{ "some_field": (true|false|null) }"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_tristate_preserves_configured_rates() {
+        let args: HashMap<String, Value> = HashMap::from([
+            (String::from("true_weight"), Value::from(6)),
+            (String::from("false_weight"), Value::from(3)),
+            (String::from("null_weight"), Value::from(1)),
+        ]);
+
+        let sample_count: usize = 20_000;
+        let mut value_counts: HashMap<String, usize> = HashMap::new();
+        for _ in 0..sample_count {
+            let value: Value = random_tristate(&args).unwrap();
+            *value_counts.entry(value.to_string()).or_default() += 1;
+        }
+
+        assert_eq!(value_counts.len(), 3usize);
+        for (value, expected_frequency) in [("true", 0.6), ("false", 0.3), ("null", 0.1)] {
+            let frequency: f64 = value_counts[value] as f64 / sample_count as f64;
+            assert!((frequency - expected_frequency).abs() < 0.02);
+        }
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_tristate_with_invalid_weights_returns_error() {
+        for template in [
+            "{{ random_tristate(true_weight=0, false_weight=0, null_weight=0) }}",
+            "{{ random_tristate(null_weight=-1) }}",
+        ] {
+            test_tera_rand_function_returns_error(random_tristate, "random_tristate", template);
+        }
+    }
+
+    // uint8
+    #[test]
+    #[traced_test]
+    fn test_random_uint8() {
+        test_tera_rand_function(
+            random_uint8,
+            "random_uint8",
+            r#"{ "some_field": {{ random_uint8() }} }"#,
+            r#"\{ "some_field": \d+ }"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_uint8_near_min() {
+        test_tera_rand_function(
+            random_uint8,
+            "random_uint8",
+            r#"{ "some_field": {{ random_uint8(start=0, end=2) }} }"#,
+            r#"\{ "some_field": 0|1|2 }"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_uint8_near_max() {
+        test_tera_rand_function(
+            random_uint8,
+            "random_uint8",
+            r#"{ "some_field": {{ random_uint8(start=253, end=255) }} }"#,
+            r#"\{ "some_field": 253|254|255 }"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_uint8_with_end_only() {
+        test_tera_rand_function(
+            random_uint8,
+            "random_uint8",
+            r#"{ "some_field": {{ random_uint8(end=2) }} }"#,
+            r#"\{ "some_field": 0|1|2 }"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_uint8_out_of_range_returns_error() {
+        test_tera_rand_function_returns_error(
+            random_uint8,
+            "random_uint8",
+            r#"{{ random_uint8(end=256) }}"#,
+        );
+    }
+
+    // uint16
+    #[test]
+    #[traced_test]
+    fn test_random_uint16() {
+        test_tera_rand_function(
+            random_uint16,
+            "random_uint16",
+            r#"{ "some_field": {{ random_uint16() }} }"#,
+            r#"\{ "some_field": \d+ }"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_uint16_near_min() {
+        test_tera_rand_function(
+            random_uint16,
+            "random_uint16",
+            r#"{ "some_field": {{ random_uint16(start=0, end=2) }} }"#,
+            r#"\{ "some_field": 0|1|2 }"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_uint16_near_max() {
+        test_tera_rand_function(
+            random_uint16,
+            "random_uint16",
+            r#"{ "some_field": {{ random_uint16(start=65533, end=65535) }} }"#,
+            r#"\{ "some_field": 65533|65534|65535 }"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_uint16_with_end_only() {
+        test_tera_rand_function(
+            random_uint16,
+            "random_uint16",
+            r#"{ "some_field": {{ random_uint16(end=2) }} }"#,
+            r#"\{ "some_field": 0|1|2 }"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_uint16_out_of_range_returns_error() {
+        test_tera_rand_function_returns_error(
+            random_uint16,
+            "random_uint16",
+            r#"{{ random_uint16(end=65536) }}"#,
+        );
+    }
+
+    // uint32
+    #[test]
+    #[traced_test]
+    fn test_random_uint32() {
+        test_tera_rand_function(
+            random_uint32,
+            "random_uint32",
+            r#"{ "some_field": {{ random_uint32() }} }"#,
+            r#"\{ "some_field": \d+ }"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_uint32_near_min() {
+        test_tera_rand_function(
+            random_uint32,
+            "random_uint32",
+            r#"{ "some_field": {{ random_uint32(start=0, end=2) }} }"#,
+            r#"\{ "some_field": 0|1|2 }"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_uint32_near_max() {
+        test_tera_rand_function(
+            random_uint32,
+            "random_uint32",
+            r#"{ "some_field": {{ random_uint32(start=4294967293, end=4294967295) }} }"#,
+            r#"\{ "some_field": 4294967293|4294967294|4294967295 }"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_uint32_with_start_only() {
+        test_tera_rand_function(
+            random_uint32,
+            "random_uint32",
+            r#"{ "some_field": {{ random_uint32(start=4294967293) }} }"#,
+            r#"\{ "some_field": 4294967293|4294967294|4294967295 }"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_uint32_with_end_only() {
+        test_tera_rand_function(
+            random_uint32,
+            "random_uint32",
+            r#"{ "some_field": {{ random_uint32(end=2) }} }"#,
+            r#"\{ "some_field": 0|1|2 }"#,
+        );
+    }
+
+    // uint64
+    #[test]
+    #[traced_test]
+    fn test_random_uint64() {
+        test_tera_rand_function(
+            random_uint64,
+            "random_uint64",
+            r#"{ "some_field": {{ random_uint64() | json_encode() }} }"#,
+            r#"\{ "some_field": (\d+|"\d+") }"#,
+        );
+    }
 
     #[test]
     #[traced_test]
-    fn test_random_uint32_with_start_only() {
+    fn test_random_uint64_near_min() {
         test_tera_rand_function(
-            random_uint32,
-            "random_uint32",
-            r#"{ "some_field": {{ random_uint32(start=4294967293) }} }"#,
-            r#"\{ "some_field": 4294967293|4294967294|4294967295 }"#,
+            random_uint64,
+            "random_uint64",
+            r#"{ "some_field": {{ random_uint64(start=0, end=2) }} }"#,
+            r#"\{ "some_field": 0|1|2 }"#,
+        );
+    }
+
+    // TODO: unignore once Tera supports u64s in functions: https://github.com/Keats/tera/issues/851
+    #[test]
+    #[ignore]
+    #[traced_test]
+    fn test_random_uint64_near_max() {
+        test_tera_rand_function(
+            random_uint64,
+            "random_uint64",
+            r#"{ "some_field": {{ random_uint64(start=18446744073709551601, end=18446744073709551603) }} }"#,
+            r#"\{ "some_field": 18446744073709551601|18446744073709551602|18446744073709551603 }"#,
+        );
+    }
+
+    // TODO: unignore once Tera supports u64s in functions: https://github.com/Keats/tera/issues/851
+    #[test]
+    #[ignore]
+    #[traced_test]
+    fn test_random_uint64_with_start_only() {
+        test_tera_rand_function(
+            random_uint64,
+            "random_uint64",
+            r#"{ "some_field": {{ random_uint64(start=18446744073709551601) }} }"#,
+            r#"\{ "some_field": 18446744073709551601|18446744073709551602|18446744073709551603 }"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_uint64_with_end_only() {
+        test_tera_rand_function(
+            random_uint64,
+            "random_uint64",
+            r#"{ "some_field": {{ random_uint64(end=2) }} }"#,
+            r#"\{ "some_field": 0|1|2 }"#,
+        );
+    }
+
+    // uint128
+    #[test]
+    #[traced_test]
+    fn test_random_uint128() {
+        test_tera_rand_function(
+            random_uint128,
+            "random_uint128",
+            r#"{ "some_field": {{ random_uint128() | json_encode() }} }"#,
+            r#"\{ "some_field": (\d+|"\d+") }"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_uint128_near_min() {
+        test_tera_rand_function(
+            random_uint128,
+            "random_uint128",
+            r#"{ "some_field": {{ random_uint128(start=0, end=2) }} }"#,
+            r#"\{ "some_field": [0-2] }"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_uint128_near_max_renders_exactly() {
+        test_tera_rand_function(
+            random_uint128,
+            "random_uint128",
+            r#"{ "some_field": {{ random_uint128(start="340282366920938463463374607431768211453", end="340282366920938463463374607431768211455") | json_encode() }} }"#,
+            r#"\{ "some_field": "(340282366920938463463374607431768211453|340282366920938463463374607431768211454|340282366920938463463374607431768211455)" }"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_uint128_with_start_only() {
+        test_tera_rand_function(
+            random_uint128,
+            "random_uint128",
+            r#"{ "some_field": {{ random_uint128(start="340282366920938463463374607431768211454") }} }"#,
+            r#"\{ "some_field": 34028236692093846346337460743176821145[45] }"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_uint128_with_exclusive_end() {
+        test_tera_rand_function(
+            random_uint128,
+            "random_uint128",
+            r#"{ "some_field": {{ random_uint128(start="340282366920938463463374607431768211454", exclusive=true) }} }"#,
+            r#"\{ "some_field": 340282366920938463463374607431768211454 }"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_uint128_with_invalid_args_returns_error() {
+        for template in [
+            "{{ random_uint128(end=-1) }}",
+            r#"{{ random_uint128(end="340282366920938463463374607431768211456") }}"#,
+            r#"{{ random_uint128(start="one") }}"#,
+            "{{ random_uint128(start=5, end=4) }}",
+            "{{ random_uint128(start=5, end=5, exclusive=true) }}",
+        ] {
+            test_tera_rand_function_returns_error(random_uint128, "random_uint128", template);
+        }
+    }
+
+    // int8
+    #[test]
+    #[traced_test]
+    fn test_random_int8() {
+        test_tera_rand_function(
+            random_int8,
+            "random_int8",
+            r#"{ "some_field": {{ random_int8() }} }"#,
+            r#"\{ "some_field": -?\d+ }"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_int8_near_min() {
+        test_tera_rand_function(
+            random_int8,
+            "random_int8",
+            r#"{ "some_field": {{ random_int8(start=-128, end=-126) }} }"#,
+            r#"\{ "some_field": (-128|-127|-126) }"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_int8_near_max() {
+        test_tera_rand_function(
+            random_int8,
+            "random_int8",
+            r#"{ "some_field": {{ random_int8(start=125, end=127) }} }"#,
+            r#"\{ "some_field": (125|126|127) }"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_int8_with_end_only() {
+        test_tera_rand_function(
+            random_int8,
+            "random_int8",
+            r#"{ "some_field": {{ random_int8(end=-126) }} }"#,
+            r#"\{ "some_field": (-128|-127|-126) }"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_int8_out_of_range_returns_error() {
+        test_tera_rand_function_returns_error(
+            random_int8,
+            "random_int8",
+            r#"{{ random_int8(end=128) }}"#,
+        );
+    }
+
+    // int16
+    #[test]
+    #[traced_test]
+    fn test_random_int16() {
+        test_tera_rand_function(
+            random_int16,
+            "random_int16",
+            r#"{ "some_field": {{ random_int16() }} }"#,
+            r#"\{ "some_field": -?\d+ }"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_int16_near_min() {
+        test_tera_rand_function(
+            random_int16,
+            "random_int16",
+            r#"{ "some_field": {{ random_int16(start=-32768, end=-32766) }} }"#,
+            r#"\{ "some_field": (-32768|-32767|-32766) }"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_int16_near_max() {
+        test_tera_rand_function(
+            random_int16,
+            "random_int16",
+            r#"{ "some_field": {{ random_int16(start=32765, end=32767) }} }"#,
+            r#"\{ "some_field": (32765|32766|32767) }"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_int16_with_end_only() {
+        test_tera_rand_function(
+            random_int16,
+            "random_int16",
+            r#"{ "some_field": {{ random_int16(end=-32766) }} }"#,
+            r#"\{ "some_field": (-32768|-32767|-32766) }"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_int16_out_of_range_returns_error() {
+        test_tera_rand_function_returns_error(
+            random_int16,
+            "random_int16",
+            r#"{{ random_int16(end=32768) }}"#,
+        );
+    }
+
+    // int32
+    #[test]
+    #[traced_test]
+    fn test_random_int32() {
+        test_tera_rand_function(
+            random_int32,
+            "random_int32",
+            r#"{ "some_field": {{ random_int32() }} }"#,
+            r#"\{ "some_field": -?\d+ }"#,
+        );
+    }
+
+    // int32 in range
+    #[test]
+    #[traced_test]
+    fn test_random_int32_near_min() {
+        test_tera_rand_function(
+            random_int32,
+            "random_int32",
+            r#"{ "some_field": {{ random_int32(start=-2147483648, end=-2147483646) }} }"#,
+            r#"\{ "some_field": (-2147483648|-2147483647|-2147483646) }"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_int32_near_max() {
+        test_tera_rand_function(
+            random_int32,
+            "random_int32",
+            r#"{ "some_field": {{ random_int32(start=2147483645, end=2147483647) }} }"#,
+            r#"\{ "some_field": 2147483645|2147483646|2147483647 }"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_int32_with_start_only() {
+        test_tera_rand_function(
+            random_int32,
+            "random_int32",
+            r#"{ "some_field": {{ random_int32(start=2147483645) }} }"#,
+            r#"\{ "some_field": 2147483645|2147483646|2147483647 }"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_int32_with_end_only() {
+        test_tera_rand_function(
+            random_int32,
+            "random_int32",
+            r#"{ "some_field": {{ random_int32(end=-2147483646) }} }"#,
+            r#"\{ "some_field": (-2147483648|-2147483647|-2147483646) }"#,
+        );
+    }
+
+    // int64
+    #[test]
+    #[traced_test]
+    fn test_random_int64() {
+        test_tera_rand_function(
+            random_int64,
+            "random_int64",
+            r#"{ "some_field": {{ random_int64() | json_encode() }} }"#,
+            r#"\{ "some_field": (-?\d+|"-?\d+") }"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_int64_near_min() {
+        test_tera_rand_function(
+            random_int64,
+            "random_int64",
+            r#"{ "some_field": {{ random_int64(start=-9223372036854775808, end=-9223372036854775806) | json_encode() }} }"#,
+            r#"\{ "some_field": "(-9223372036854775808|-9223372036854775807|-9223372036854775806)" }"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_int64_near_max() {
+        test_tera_rand_function(
+            random_int64,
+            "random_int64",
+            r#"{ "some_field": {{ random_int64(start=9223372036854775805, end=9223372036854775807) | json_encode() }} }"#,
+            r#"\{ "some_field": "(9223372036854775805|9223372036854775806|9223372036854775807)" }"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_int64_with_start_only() {
+        test_tera_rand_function(
+            random_int64,
+            "random_int64",
+            r#"{ "some_field": {{ random_int64(start=9223372036854775805) | json_encode() }} }"#,
+            r#"\{ "some_field": "(9223372036854775805|9223372036854775806|9223372036854775807)" }"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_int64_with_end_only() {
+        test_tera_rand_function(
+            random_int64,
+            "random_int64",
+            r#"{ "some_field": {{ random_int64(end=-9223372036854775806) | json_encode() }} }"#,
+            r#"\{ "some_field": "(-9223372036854775808|-9223372036854775807|-9223372036854775806)" }"#,
+        );
+    }
+
+    // int128
+    #[test]
+    #[traced_test]
+    fn test_random_int128() {
+        test_tera_rand_function(
+            random_int128,
+            "random_int128",
+            r#"{ "some_field": {{ random_int128() | json_encode() }} }"#,
+            r#"\{ "some_field": (-?\d+|"-?\d+") }"#,
         );
     }
 
     #[test]
     #[traced_test]
-    fn test_random_uint32_with_end_only() {
+    fn test_random_int128_near_min_renders_exactly() {
         test_tera_rand_function(
-            random_uint32,
-            "random_uint32",
-            r#"{ "some_field": {{ random_uint32(end=2) }} }"#,
-            r#"\{ "some_field": 0|1|2 }"#,
+            random_int128,
+            "random_int128",
+            r#"{ "some_field": {{ random_int128(start="-170141183460469231731687303715884105728", end="-170141183460469231731687303715884105727") | json_encode() }} }"#,
+            r#"\{ "some_field": "-17014118346046923173168730371588410572[78]" }"#,
         );
     }
 
-    // uint64
     #[test]
     #[traced_test]
-    fn test_random_uint64() {
+    fn test_random_int128_near_max_renders_exactly() {
         test_tera_rand_function(
-            random_uint64,
-            "random_uint64",
-            r#"{ "some_field": {{ random_uint64() }} }"#,
-            r#"\{ "some_field": \d+ }"#,
+            random_int128,
+            "random_int128",
+            r#"{ "some_field": {{ random_int128(start="170141183460469231731687303715884105726") | json_encode() }} }"#,
+            r#"\{ "some_field": "17014118346046923173168730371588410572[67]" }"#,
         );
     }
 
     #[test]
     #[traced_test]
-    fn test_random_uint64_near_min() {
+    fn test_random_int128_with_small_range_renders_number() {
         test_tera_rand_function(
-            random_uint64,
-            "random_uint64",
-            r#"{ "some_field": {{ random_uint64(start=0, end=2) }} }"#,
-            r#"\{ "some_field": 0|1|2 }"#,
+            random_int128,
+            "random_int128",
+            r#"{ "some_field": {{ random_int128(start=-2, end="0") }} }"#,
+            r#"\{ "some_field": (-2|-1|0) }"#,
         );
     }
 
-    // TODO: unignore once Tera supports u64s in functions: https://github.com/Keats/tera/issues/851
     #[test]
-    #[ignore]
     #[traced_test]
-    fn test_random_uint64_near_max() {
-        test_tera_rand_function(
-            random_uint64,
-            "random_uint64",
-            r#"{ "some_field": {{ random_uint64(start=18446744073709551601, end=18446744073709551603) }} }"#,
-            r#"\{ "some_field": 18446744073709551601|18446744073709551602|18446744073709551603 }"#,
-        );
+    fn test_random_int128_with_invalid_args_returns_error() {
+        for template in [
+            r#"{{ random_int128(start="170141183460469231731687303715884105728") }}"#,
+            r#"{{ random_int128(end="1.5") }}"#,
+            "{{ random_int128(start=1, end=-1) }}",
+        ] {
+            test_tera_rand_function_returns_error(random_int128, "random_int128", template);
+        }
     }
 
-    // TODO: unignore once Tera supports u64s in functions: https://github.com/Keats/tera/issues/851
+    // number_mode
     #[test]
-    #[ignore]
     #[traced_test]
-    fn test_random_uint64_with_start_only() {
+    fn test_random_int64_around_max_safe_integer() {
         test_tera_rand_function(
-            random_uint64,
-            "random_uint64",
-            r#"{ "some_field": {{ random_uint64(start=18446744073709551601) }} }"#,
-            r#"\{ "some_field": 18446744073709551601|18446744073709551602|18446744073709551603 }"#,
+            random_int64,
+            "random_int64",
+            r#"{ "some_field": {{ random_int64(start=9007199254740991, end=9007199254740991) | json_encode() }} }"#,
+            r#"\{ "some_field": 9007199254740991 }"#,
+        );
+        test_tera_rand_function(
+            random_int64,
+            "random_int64",
+            r#"{ "some_field": {{ random_int64(start=9007199254740992, end=9007199254740992) | json_encode() }} }"#,
+            r#"\{ "some_field": "9007199254740992" }"#,
         );
     }
 
     #[test]
     #[traced_test]
-    fn test_random_uint64_with_end_only() {
+    fn test_random_uint32_with_string_number_mode() {
         test_tera_rand_function(
-            random_uint64,
-            "random_uint64",
-            r#"{ "some_field": {{ random_uint64(end=2) }} }"#,
-            r#"\{ "some_field": 0|1|2 }"#,
+            random_uint32,
+            "random_uint32",
+            r#"{ "some_field": {{ random_uint32(start=0, end=2, number_mode="string") | json_encode() }} }"#,
+            r#"\{ "some_field": "(0|1|2)" }"#,
         );
     }
 
-    // int32
     #[test]
     #[traced_test]
-    fn test_random_int32() {
+    fn test_random_int64_with_number_number_mode() {
         test_tera_rand_function(
-            random_int32,
-            "random_int32",
-            r#"{ "some_field": {{ random_int32() }} }"#,
-            r#"\{ "some_field": -?\d+ }"#,
+            random_int64,
+            "random_int64",
+            r#"{ "some_field": {{ random_int64(start=9223372036854775805, number_mode="number") | json_encode() }} }"#,
+            r#"\{ "some_field": (9223372036854775805|9223372036854775806|9223372036854775807) }"#,
         );
     }
 
-    // int32 in range
     #[test]
     #[traced_test]
-    fn test_random_int32_near_min() {
-        test_tera_rand_function(
-            random_int32,
-            "random_int32",
-            r#"{ "some_field": {{ random_int32(start=-2147483648, end=-2147483646) }} }"#,
-            r#"\{ "some_field": (-2147483648|-2147483647|-2147483646) }"#,
+    fn test_random_uint32_with_unknown_number_mode_returns_error() {
+        test_tera_rand_function_returns_error(
+            random_uint32,
+            "random_uint32",
+            r#"{{ random_uint32(number_mode="hex") }}"#,
         );
     }
 
+    // step and exclusive
     #[test]
     #[traced_test]
-    fn test_random_int32_near_max() {
+    fn test_random_uint32_with_step() {
         test_tera_rand_function(
-            random_int32,
-            "random_int32",
-            r#"{ "some_field": {{ random_int32(start=2147483645, end=2147483647) }} }"#,
-            r#"\{ "some_field": 2147483645|2147483646|2147483647 }"#,
+            random_uint32,
+            "random_uint32",
+            r#"{ "some_field": {{ random_uint32(start=10, end=30, step=10) }} }"#,
+            r#"^\{ "some_field": (10|20|30) }$"#,
         );
     }
 
     #[test]
     #[traced_test]
-    fn test_random_int32_with_start_only() {
+    fn test_random_int32_with_exclusive() {
         test_tera_rand_function(
             random_int32,
             "random_int32",
-            r#"{ "some_field": {{ random_int32(start=2147483645) }} }"#,
-            r#"\{ "some_field": 2147483645|2147483646|2147483647 }"#,
+            r#"{ "some_field": {{ random_int32(start=-1, end=1, exclusive=true) }} }"#,
+            r#"^\{ "some_field": (-1|0) }$"#,
         );
     }
 
     #[test]
     #[traced_test]
-    fn test_random_int32_with_end_only() {
-        test_tera_rand_function(
-            random_int32,
-            "random_int32",
-            r#"{ "some_field": {{ random_int32(end=-2147483646) }} }"#,
-            r#"\{ "some_field": (-2147483648|-2147483647|-2147483646) }"#,
-        );
+    fn test_integers_with_multiple_of_are_divisible() {
+        for (function, start, end, multiple_of) in [
+            (random_uint32 as crate::TeraRandFunction, 1i64, 100i64, 8u64),
+            (random_uint64, 1_700_000_001, 1_700_100_000, 300),
+            (random_int32, -100, -1, 7),
+            (random_int64, -50, 50, 25),
+        ] {
+            let args: HashMap<String, Value> = HashMap::from([
+                (String::from("start"), Value::from(start)),
+                (String::from("end"), Value::from(end)),
+                (String::from("multiple_of"), Value::from(multiple_of)),
+            ]);
+            for _ in 0..200 {
+                let random_value: i64 = function(&args).unwrap().as_i64().unwrap();
+                assert!((start..=end).contains(&random_value));
+                assert_eq!(random_value % multiple_of as i64, 0);
+            }
+        }
     }
 
-    // int64
     #[test]
     #[traced_test]
-    fn test_random_int64() {
+    fn test_random_uint64_at_max_with_multiple_of() {
+        let args: HashMap<String, Value> = HashMap::from([
+            (String::from("start"), Value::from(u64::MAX - 20)),
+            (String::from("multiple_of"), Value::from(10)),
+            (String::from("exclusive"), Value::from(true)),
+            (String::from("number_mode"), Value::from("number")),
+        ]);
+        for _ in 0..100 {
+            let random_value: u64 = random_uint64(&args).unwrap().as_u64().unwrap();
+            assert!([u64::MAX - 15, u64::MAX - 5].contains(&random_value));
+        }
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_int32_with_invalid_multiple_of_returns_error() {
+        for template in [
+            "{{ random_int32(start=0, end=10, multiple_of=0) }}",
+            "{{ random_int32(start=0, end=10, multiple_of=11) }}",
+            "{{ random_int32(start=0, end=10, multiple_of=2, step=2) }}",
+            "{{ random_int32(start=10, end=0, multiple_of=2) }}",
+        ] {
+            test_tera_rand_function_returns_error(random_int32, "random_int32", template);
+        }
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_uint32_with_exclusive_end() {
         test_tera_rand_function(
-            random_int64,
-            "random_int64",
-            r#"{ "some_field": {{ random_int64() }} }"#,
-            r#"\{ "some_field": -?\d+ }"#,
+            random_uint32,
+            "random_uint32",
+            r#"{ "some_field": {{ random_uint32(start=0, end=3, exclusive_end=true) }} }"#,
+            r#"^\{ "some_field": (0|1|2) }$"#,
         );
     }
 
     #[test]
     #[traced_test]
-    fn test_random_int64_near_min() {
-        test_tera_rand_function(
+    fn test_exclusive_end_with_equal_bounds_returns_error() {
+        let args: HashMap<String, Value> = HashMap::from([
+            (String::from("start"), Value::from(7)),
+            (String::from("end"), Value::from(7)),
+            (String::from("exclusive_end"), Value::from(true)),
+        ]);
+        for function in [random_uint32, random_uint64, random_int32, random_int64] {
+            let error_message: String = function(&args).unwrap_err().to_string();
+            assert_eq!(error_message, "The range from 7 to 7 is empty");
+        }
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_int64_with_exclusive_and_exclusive_end_returns_error() {
+        test_tera_rand_function_returns_error(
             random_int64,
             "random_int64",
-            r#"{ "some_field": {{ random_int64(start=-9223372036854775808, end=-9223372036854775806) }} }"#,
-            r#"\{ "some_field": (-9223372036854775808|-9223372036854775807|-9223372036854775806) }"#,
+            r#"{{ random_int64(start=0, end=3, exclusive=true, exclusive_end=true) }}"#,
         );
     }
 
     #[test]
     #[traced_test]
-    fn test_random_int64_near_max() {
-        test_tera_rand_function(
-            random_int64,
-            "random_int64",
-            r#"{ "some_field": {{ random_int64(start=9223372036854775805, end=9223372036854775807) }} }"#,
-            r#"\{ "some_field": 9223372036854775805|9223372036854775806|9223372036854775807 }"#,
+    fn test_random_uint64_at_max_with_step_and_exclusive() {
+        let near_max: u64 = u64::MAX - 4;
+        for exclusive in [false, true] {
+            let args: HashMap<String, Value> = HashMap::from([
+                (String::from("start"), Value::from(near_max)),
+                (String::from("end"), Value::from(u64::MAX)),
+                (String::from("step"), Value::from(2)),
+                (String::from("exclusive"), Value::from(exclusive)),
+                (String::from("number_mode"), Value::from("number")),
+            ]);
+            for _ in 0..100 {
+                let random_value: u64 = random_uint64(&args).unwrap().as_u64().unwrap();
+                assert!([near_max, near_max + 2, u64::MAX].contains(&random_value));
+                assert!(!exclusive || random_value != u64::MAX);
+            }
+        }
+
+        let args: HashMap<String, Value> = HashMap::from([
+            (String::from("start"), Value::from(u64::MAX)),
+            (String::from("exclusive"), Value::from(false)),
+            (String::from("step"), Value::from(u64::MAX)),
+            (String::from("number_mode"), Value::from("number")),
+        ]);
+        assert_eq!(random_uint64(&args).unwrap(), Value::from(u64::MAX));
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_int64_at_min_with_step_and_exclusive() {
+        for exclusive in [false, true] {
+            let args: HashMap<String, Value> = HashMap::from([
+                (String::from("end"), Value::from(i64::MIN + 4)),
+                (String::from("step"), Value::from(4)),
+                (String::from("exclusive"), Value::from(exclusive)),
+                (String::from("number_mode"), Value::from("number")),
+            ]);
+            for _ in 0..100 {
+                let random_value: i64 = random_int64(&args).unwrap().as_i64().unwrap();
+                assert!(random_value == i64::MIN || (!exclusive && random_value == i64::MIN + 4));
+            }
+        }
+
+        let args: HashMap<String, Value> = HashMap::from([
+            (String::from("end"), Value::from(i64::MIN)),
+            (String::from("exclusive"), Value::from(true)),
+        ]);
+        assert!(random_int64(&args).is_err());
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_uint32_with_start_greater_than_end_returns_error() {
+        test_tera_rand_function_returns_error(
+            random_uint32,
+            "random_uint32",
+            r#"{{ random_uint32(start=2, end=1) }}"#,
         );
     }
 
     #[test]
     #[traced_test]
-    fn test_random_int64_with_start_only() {
-        test_tera_rand_function(
-            random_int64,
-            "random_int64",
-            r#"{ "some_field": {{ random_int64(start=9223372036854775805) }} }"#,
-            r#"\{ "some_field": 9223372036854775805|9223372036854775806|9223372036854775807 }"#,
+    fn test_random_float64_with_start_greater_than_end_returns_error() {
+        test_tera_rand_function_returns_error(
+            random_float64,
+            "random_float64",
+            r#"{{ random_float64(start=2.0, end=1.0) }}"#,
         );
     }
 
     #[test]
     #[traced_test]
-    fn test_random_int64_with_end_only() {
-        test_tera_rand_function(
-            random_int64,
-            "random_int64",
-            r#"{ "some_field": {{ random_int64(end=-9223372036854775806) }} }"#,
-            r#"\{ "some_field": (-9223372036854775808|-9223372036854775807|-9223372036854775806) }"#,
+    fn test_random_uint32_with_zero_step_returns_error() {
+        test_tera_rand_function_returns_error(
+            random_uint32,
+            "random_uint32",
+            r#"{{ random_uint32(step=0) }}"#,
         );
     }
 
@@ -583,4 +2004,439 @@ mod tests {
             r#"\{ "some_field": -5\.\d+ }"#,
         );
     }
+
+    #[test]
+    #[traced_test]
+    fn test_random_floats_with_precision_have_at_most_that_many_fractional_digits() {
+        let mut tera: Tera = Tera::default();
+        tera.register_function("random_float32", random_float32);
+        tera.register_function("random_float64", random_float64);
+        let context: Context = Context::new();
+
+        for (template, max_fractional_digits) in [
+            (
+                "{{ random_float32(start=-100.0, end=100.0, precision=2) }}",
+                2,
+            ),
+            (
+                "{{ random_float64(start=-100.0, end=100.0, precision=2) }}",
+                2,
+            ),
+            ("{{ random_float64(precision=1) }}", 1),
+        ] {
+            let expected_regex: Regex =
+                Regex::new(&format!(r"^-?\d+(\.\d{{1,{max_fractional_digits}}})?$")).unwrap();
+            for _ in 0..500 {
+                let rendered: String = tera.render_str(template, &context).unwrap();
+                assert!(
+                    expected_regex.is_match(&rendered),
+                    "{rendered} has more than {max_fractional_digits} fractional digits"
+                );
+            }
+        }
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_floats_with_zero_precision_are_whole_floats() {
+        for function in [random_float32, random_float64] {
+            let args: HashMap<String, Value> = HashMap::from([
+                (String::from("start"), Value::from(-10.0)),
+                (String::from("end"), Value::from(10.0)),
+                (String::from("precision"), Value::from(0)),
+            ]);
+            for _ in 0..500 {
+                let random_value: Value = function(&args).unwrap();
+                assert!(random_value.is_f64());
+                assert_eq!(random_value.as_f64().unwrap().fract(), 0.0);
+            }
+        }
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_float64_with_precision_keeps_huge_floats() {
+        let args: HashMap<String, Value> = HashMap::from([
+            (String::from("start"), Value::from(f64::MAX / 2.0)),
+            (String::from("end"), Value::from(f64::MAX)),
+            (String::from("precision"), Value::from(400)),
+        ]);
+        for _ in 0..100 {
+            let random_value: f64 = random_float64(&args).unwrap().as_f64().unwrap();
+            assert!(random_value.is_finite() && random_value >= f64::MAX / 2.0);
+        }
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_float64_with_negative_precision_returns_error() {
+        test_tera_rand_function_returns_error(
+            random_float64,
+            "random_float64",
+            "{{ random_float64(precision=-1) }}",
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_float32_reaches_both_ends_of_tiny_negative_range() {
+        let end: f32 = -5.0;
+        let start: f32 = f32::from_bits(end.to_bits() + 1);
+        let args: HashMap<String, Value> = HashMap::from([
+            (String::from("start"), Value::from(start)),
+            (String::from("end"), Value::from(end)),
+        ]);
+
+        let samples: Vec<f32> = (0..1000)
+            .map(|_| random_float32(&args).unwrap().as_f64().unwrap() as f32)
+            .collect();
+        assert!(samples
+            .iter()
+            .all(|sample| *sample == start || *sample == end));
+        assert!(samples.contains(&start));
+        assert!(samples.contains(&end));
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_float64_reaches_both_ends_of_tiny_negative_range() {
+        let end: f64 = -5.0;
+        let start: f64 = f64::from_bits(end.to_bits() + 1);
+        let args: HashMap<String, Value> = HashMap::from([
+            (String::from("start"), Value::from(start)),
+            (String::from("end"), Value::from(end)),
+        ]);
+
+        let samples: Vec<f64> = (0..1000)
+            .map(|_| random_float64(&args).unwrap().as_f64().unwrap())
+            .collect();
+        assert!(samples
+            .iter()
+            .all(|sample| *sample == start || *sample == end));
+        assert!(samples.contains(&start));
+        assert!(samples.contains(&end));
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_float64_with_equal_negative_start_and_end() {
+        test_tera_rand_function(
+            random_float64,
+            "random_float64",
+            r#"{ "some_field": {{ random_float64(start=-6.0, end=-6.0) }} }"#,
+            r#"\{ "some_field": -6(\.0)? }"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_float_with_widest_ranges_does_not_panic() {
+        for (start, end) in [(-f64::MAX, -1.0), (-f64::MAX, f64::MAX), (1.0, f64::MAX)] {
+            let args: HashMap<String, Value> = HashMap::from([
+                (String::from("start"), Value::from(start)),
+                (String::from("end"), Value::from(end)),
+            ]);
+            for _ in 0..100 {
+                let sample: f64 = random_float64(&args).unwrap().as_f64().unwrap();
+                assert!((start..=end).contains(&sample));
+            }
+        }
+
+        let args: HashMap<String, Value> = HashMap::from([
+            (String::from("start"), Value::from(-f32::MAX)),
+            (String::from("end"), Value::from(f32::MAX)),
+        ]);
+        for _ in 0..100 {
+            let sample: f64 = random_float32(&args).unwrap().as_f64().unwrap();
+            assert!((f64::from(-f32::MAX)..=f64::from(f32::MAX)).contains(&sample));
+        }
+    }
+
+    // mixture
+    #[test]
+    #[traced_test]
+    #[cfg(feature = "distributions")]
+    fn test_random_mixture_is_bimodal() {
+        let args: HashMap<String, Value> = HashMap::from([(
+            String::from("components"),
+            serde_json::json!([
+                {"distribution": "normal", "mean": 10.0, "std_dev": 1.0, "weight": 0.7},
+                {"distribution": "normal", "mean": 100.0, "std_dev": 5.0, "weight": 0.3},
+            ]),
+        )]);
+
+        let sample_count: usize = 10_000;
+        let samples: Vec<f64> = (0..sample_count)
+            .map(|_| random_mixture(&args).unwrap().as_f64().unwrap())
+            .collect();
+        let frequency_of = |predicate: fn(f64) -> bool| {
+            samples.iter().filter(|sample| predicate(**sample)).count() as f64 / sample_count as f64
+        };
+
+        // both modes appear in proportion to their weights, with almost nothing in between
+        assert!((frequency_of(|sample| (5.0..15.0).contains(&sample)) - 0.7).abs() < 0.03);
+        assert!((frequency_of(|sample| (80.0..120.0).contains(&sample)) - 0.3).abs() < 0.03);
+        assert!(frequency_of(|sample| (20.0..70.0).contains(&sample)) < 0.005);
+    }
+
+    #[test]
+    #[traced_test]
+    #[cfg(feature = "distributions")]
+    fn test_random_mixture_with_exponential_and_uniform() {
+        let args: HashMap<String, Value> = HashMap::from([(
+            String::from("components"),
+            serde_json::json!([
+                {"distribution": "exponential", "lambda": 2.0},
+                {"distribution": "uniform", "start": -2.0, "end": -1.0},
+            ]),
+        )]);
+        for _ in 0..100 {
+            let sample: f64 = random_mixture(&args).unwrap().as_f64().unwrap();
+            assert!(sample >= 0.0 || (-2.0..=-1.0).contains(&sample));
+        }
+    }
+
+    #[test]
+    #[traced_test]
+    #[cfg(feature = "distributions")]
+    fn test_random_mixture_with_widest_uniform_range() {
+        for start in [-f64::MAX, 0.0] {
+            let args: HashMap<String, Value> = HashMap::from([(
+                String::from("components"),
+                serde_json::json!([{"distribution": "uniform", "start": start, "end": f64::MAX}]),
+            )]);
+            for _ in 0..100 {
+                let sample: f64 = random_mixture(&args).unwrap().as_f64().unwrap();
+                assert!((start..=f64::MAX).contains(&sample));
+            }
+        }
+    }
+
+    #[test]
+    #[traced_test]
+    #[cfg(not(feature = "distributions"))]
+    fn test_random_mixture_without_distributions_feature_returns_error() {
+        test_tera_rand_function_returns_error(
+            random_mixture,
+            "random_mixture",
+            "{{ random_mixture(components=[]) }}",
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    #[cfg(feature = "distributions")]
+    fn test_random_mixture_with_invalid_component_returns_error() {
+        for components in [
+            serde_json::json!([{"distribution": "normal", "std_dev": -1.0}]),
+            serde_json::json!([{"distribution": "exponential", "lambda": 0.0}]),
+            serde_json::json!([{"distribution": "cauchy"}]),
+            serde_json::json!([{"distribution": "uniform", "start": 1.0, "end": 0.0}]),
+            serde_json::json!([{"distribution": "normal", "weight": -1.0}]),
+            serde_json::json!([]),
+        ] {
+            let args: HashMap<String, Value> =
+                HashMap::from([(String::from("components"), components)]);
+            assert!(random_mixture(&args).is_err());
+        }
+    }
+
+    // normal
+    #[test]
+    #[traced_test]
+    #[cfg(feature = "distributions")]
+    fn test_random_normal() {
+        test_tera_rand_function(
+            random_normal,
+            "random_normal",
+            r#"{ "some_field": {{ random_normal(mean=5.0, std_dev=0.0) }} }"#,
+            r#"\{ "some_field": 5(\.0)? }"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    #[cfg(feature = "distributions")]
+    fn test_random_normal_matches_mean_and_std_dev() {
+        let args: HashMap<String, Value> = HashMap::from([
+            (String::from("mean"), Value::from(120.0)),
+            (String::from("std_dev"), Value::from(15.0)),
+        ]);
+
+        let sample_count: usize = 10_000;
+        let samples: Vec<f64> = (0..sample_count)
+            .map(|_| random_normal(&args).unwrap().as_f64().unwrap())
+            .collect();
+        let mean: f64 = samples.iter().sum::<f64>() / sample_count as f64;
+        let variance: f64 = samples
+            .iter()
+            .map(|sample| (sample - mean).powi(2))
+            .sum::<f64>()
+            / sample_count as f64;
+        let within_one_std_dev: usize = samples
+            .iter()
+            .filter(|sample| (105.0..=135.0).contains(*sample))
+            .count();
+
+        assert!((mean - 120.0).abs() < 1.0);
+        assert!((variance.sqrt() - 15.0).abs() < 1.0);
+        assert!((within_one_std_dev as f64 / sample_count as f64 - 0.68).abs() < 0.03);
+    }
+
+    #[test]
+    #[traced_test]
+    #[cfg(feature = "distributions")]
+    fn test_random_normal_defaults_to_standard_normal() {
+        let sample_count: usize = 10_000;
+        let mean: f64 = (0..sample_count)
+            .map(|_| random_normal(&HashMap::new()).unwrap().as_f64().unwrap())
+            .sum::<f64>()
+            / sample_count as f64;
+        assert!(mean.abs() < 0.1);
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_normal_with_negative_std_dev_returns_error() {
+        test_tera_rand_function_returns_error(
+            random_normal,
+            "random_normal",
+            "{{ random_normal(std_dev=-1.0) }}",
+        );
+    }
+
+    // exponential
+    #[test]
+    #[traced_test]
+    #[cfg(feature = "distributions")]
+    fn test_random_exponential_is_non_negative() {
+        for _ in 0..200 {
+            test_tera_rand_function(
+                random_exponential,
+                "random_exponential",
+                r#"{ "some_field": {{ random_exponential(lambda=0.5) }} }"#,
+                r#"^\{ "some_field": \d+(\.\d+)?(e-?\d+)? }$"#,
+            );
+        }
+    }
+
+    #[test]
+    #[traced_test]
+    #[cfg(feature = "distributions")]
+    fn test_random_exponential_mean_is_inverse_of_lambda() {
+        let args: HashMap<String, Value> =
+            HashMap::from([(String::from("lambda"), Value::from(4.0))]);
+
+        let sample_count: usize = 10_000;
+        let mean: f64 = (0..sample_count)
+            .map(|_| random_exponential(&args).unwrap().as_f64().unwrap())
+            .sum::<f64>()
+            / sample_count as f64;
+        assert!((mean - 0.25).abs() < 0.02);
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_exponential_with_invalid_lambda_returns_error() {
+        for template in [
+            "{{ random_exponential(lambda=0.0) }}",
+            "{{ random_exponential(lambda=-1.0) }}",
+        ] {
+            test_tera_rand_function_returns_error(
+                random_exponential,
+                "random_exponential",
+                template,
+            );
+        }
+    }
+
+    // lognormal
+    #[test]
+    #[traced_test]
+    #[cfg(feature = "distributions")]
+    fn test_random_lognormal_is_positive() {
+        for _ in 0..200 {
+            test_tera_rand_function(
+                random_lognormal,
+                "random_lognormal",
+                r#"{ "some_field": {{ random_lognormal(mu=1.0, sigma=2.0) }} }"#,
+                r#"^\{ "some_field": \d+(\.\d+)?(e-?\d+)? }$"#,
+            );
+        }
+
+        let args: HashMap<String, Value> = HashMap::new();
+        for _ in 0..1000 {
+            assert!(random_lognormal(&args).unwrap().as_f64().unwrap() > 0.0);
+        }
+    }
+
+    #[test]
+    #[traced_test]
+    #[cfg(feature = "distributions")]
+    fn test_random_lognormal_median_is_exp_of_mu() {
+        let args: HashMap<String, Value> = HashMap::from([
+            (String::from("mu"), Value::from(2.0)),
+            (String::from("sigma"), Value::from(0.5)),
+        ]);
+
+        let sample_count: usize = 10_000;
+        let mut samples: Vec<f64> = (0..sample_count)
+            .map(|_| random_lognormal(&args).unwrap().as_f64().unwrap())
+            .collect();
+        samples.sort_by(f64::total_cmp);
+        let median: f64 = samples[sample_count / 2];
+        assert!((median - 2f64.exp()).abs() < 0.3);
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_lognormal_with_invalid_sigma_returns_error() {
+        test_tera_rand_function_returns_error(
+            random_lognormal,
+            "random_lognormal",
+            "{{ random_lognormal(sigma=-1.0) }}",
+        );
+    }
+
+    // poisson
+    #[test]
+    #[traced_test]
+    #[cfg(feature = "distributions")]
+    fn test_random_poisson_is_non_negative_integer() {
+        for _ in 0..200 {
+            test_tera_rand_function(
+                random_poisson,
+                "random_poisson",
+                r#"{ "some_field": {{ random_poisson(lambda=3.5) }} }"#,
+                r#"^\{ "some_field": \d+ }$"#,
+            );
+        }
+    }
+
+    #[test]
+    #[traced_test]
+    #[cfg(feature = "distributions")]
+    fn test_random_poisson_mean_is_lambda() {
+        let args: HashMap<String, Value> =
+            HashMap::from([(String::from("lambda"), Value::from(250.0))]);
+
+        let sample_count: usize = 10_000;
+        let mean: f64 = (0..sample_count)
+            .map(|_| random_poisson(&args).unwrap().as_u64().unwrap() as f64)
+            .sum::<f64>()
+            / sample_count as f64;
+        assert!((mean - 250.0).abs() < 1.0);
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_poisson_with_invalid_lambda_returns_error() {
+        for template in [
+            "{{ random_poisson(lambda=0.0) }}",
+            "{{ random_poisson(lambda=-2.5) }}",
+            "{{ random_poisson(lambda=1e400) }}",
+        ] {
+            test_tera_rand_function_returns_error(random_poisson, "random_poisson", template);
+        }
+    }
 }
@@ -0,0 +1,483 @@
+use crate::*;
+use tera::Tera;
+
+/// Every tera-rand function under its conventional name, which is the name of the function itself.
+///
+/// Functions which require a disabled feature, such as [`random_uuid`] without the `uuid`
+/// feature, are left out.
+pub const ALL_FUNCTIONS: &[(&str, TeraRandFunction)] = &[
+    ("line_from_file", line_from_file),
+    ("random_base64", random_base64),
+    ("random_bool", random_bool),
+    ("random_char", random_char),
+    ("random_choice", random_choice),
+    ("random_cron", random_cron),
+    ("random_currency_code", random_currency_code),
+    #[cfg(feature = "time")]
+    ("random_datetime", random_datetime),
+    #[cfg(feature = "time")]
+    ("random_duration", random_duration),
+    ("random_e164", random_e164),
+    ("random_email", random_email),
+    ("random_emoji", random_emoji),
+    ("random_endpoint", random_endpoint),
+    ("random_event_sequence", random_event_sequence),
+    #[cfg(feature = "distributions")]
+    ("random_exponential", random_exponential),
+    #[cfg(feature = "distributions")]
+    ("random_file_size", random_file_size),
+    ("random_fk", random_fk),
+    ("random_float32", random_float32),
+    ("random_float64", random_float64),
+    ("random_frequent_word", random_frequent_word),
+    ("random_from_csv", random_from_csv),
+    ("random_from_file", random_from_file),
+    ("random_from_json_file", random_from_json_file),
+    #[cfg(feature = "schema")]
+    ("random_from_schema", random_from_schema),
+    #[cfg(feature = "sqlite")]
+    ("random_from_sqlite", random_from_sqlite),
+    ("random_from_tree", random_from_tree),
+    ("random_geo_ip", random_geo_ip),
+    ("random_hex", random_hex),
+    ("random_http_status", random_http_status),
+    ("random_identifier", random_identifier),
+    ("random_int128", random_int128),
+    ("random_int16", random_int16),
+    ("random_int32", random_int32),
+    ("random_int64", random_int64),
+    ("random_int8", random_int8),
+    ("random_ipv4", random_ipv4),
+    ("random_ipv4_cidr", random_ipv4_cidr),
+    ("random_ipv4_host", random_ipv4_host),
+    ("random_ipv4_range", random_ipv4_range),
+    ("random_ipv6", random_ipv6),
+    ("random_ipv6_cidr", random_ipv6_cidr),
+    ("random_ipv6_host", random_ipv6_host),
+    ("random_log_level", random_log_level),
+    #[cfg(feature = "distributions")]
+    ("random_lognormal", random_lognormal),
+    ("random_mac", random_mac),
+    #[cfg(feature = "distributions")]
+    ("random_mixture", random_mixture),
+    ("random_netmask", random_netmask),
+    #[cfg(feature = "distributions")]
+    ("random_normal", random_normal),
+    ("random_once", random_once),
+    #[cfg(feature = "distributions")]
+    ("random_poisson", random_poisson),
+    ("random_quantile", random_quantile),
+    ("random_sample_from_file", random_sample_from_file),
+    ("random_string", random_string),
+    ("random_subset", random_subset),
+    ("random_timestamp", random_timestamp),
+    ("random_tristate", random_tristate),
+    ("random_uint128", random_uint128),
+    ("random_uint16", random_uint16),
+    ("random_uint32", random_uint32),
+    ("random_uint64", random_uint64),
+    ("random_uint8", random_uint8),
+    #[cfg(feature = "ulid")]
+    ("random_ulid", random_ulid),
+    #[cfg(feature = "uuid")]
+    ("random_uuid", random_uuid),
+    #[cfg(feature = "uuid")]
+    ("random_uuid_v5", random_uuid_v5),
+    #[cfg(feature = "uuid")]
+    ("random_uuid_v7", random_uuid_v7),
+    ("random_weighted", random_weighted),
+    ("random_words", random_words),
+    ("register_id", register_id),
+    ("weighted_from_file", weighted_from_file),
+];
+
+/// The parameters accepted by each tera-rand function, by the function's conventional name.
+///
+/// Unlike [`ALL_FUNCTIONS`], this includes functions which require a disabled feature, since their
+/// stubs are registered under the same names. Tools such as linters can use it to find unknown
+/// parameters in templates.
+pub const FUNCTION_PARAMETERS: &[(&str, &[&str])] = &[
+    ("line_from_file", &["path", "line_num", "cache", "quote"]),
+    ("random_base64", &["bytes", "quote"]),
+    ("random_bool", &[]),
+    ("random_char", &["class", "safe"]),
+    (
+        "random_choice",
+        &[
+            "values",
+            "name",
+            "except",
+            "weights",
+            "probabilities",
+            "coerce",
+            "ensure_all",
+        ],
+    ),
+    ("random_cron", &["complexity", "quote"]),
+    ("random_currency_code", &["format", "only", "quote"]),
+    ("random_datetime", &["start", "end", "format", "quote"]),
+    ("random_duration", &["min", "max", "format", "quote"]),
+    ("random_e164", &["country", "quote"]),
+    ("random_email", &["local_length", "domains", "quote"]),
+    ("random_emoji", &["category", "quote"]),
+    (
+        "random_endpoint",
+        &["services", "ips", "addr_start", "addr_end", "quote"],
+    ),
+    (
+        "random_event_sequence",
+        &["count", "start", "max_gap", "types"],
+    ),
+    ("random_exponential", &["lambda"]),
+    ("random_file_size", &["median", "sigma", "format"]),
+    ("random_fk", &["pool", "quote"]),
+    ("random_float32", &["start", "end", "precision"]),
+    ("random_float64", &["start", "end", "precision"]),
+    ("random_frequent_word", &["path", "quote"]),
+    (
+        "random_from_csv",
+        &["path", "delimiter", "columns", "join", "quote"],
+    ),
+    (
+        "random_from_file",
+        &[
+            "path",
+            "except",
+            "blocklist_path",
+            "cache",
+            "tail",
+            "decay",
+            "expand_ranges",
+            "column",
+            "delimiter",
+            "skip_empty",
+            "comment_prefix",
+            "cache_ttl_secs",
+            "quote",
+        ],
+    ),
+    ("random_from_json_file", &["path", "pointer"]),
+    ("random_from_schema", &["schema", "path", "quote"]),
+    ("random_from_sqlite", &["db", "query", "quote"]),
+    ("random_from_tree", &["root", "extension", "quote"]),
+    ("random_geo_ip", &["path"]),
+    ("random_hex", &["length", "quote"]),
+    ("random_http_status", &["with_message"]),
+    ("random_identifier", &["max_length", "prefix", "quote"]),
+    (
+        "random_int128",
+        &["start", "end", "exclusive", "exclusive_end", "number_mode"],
+    ),
+    (
+        "random_int16",
+        &[
+            "start",
+            "end",
+            "step",
+            "multiple_of",
+            "exclusive",
+            "exclusive_end",
+            "number_mode",
+        ],
+    ),
+    (
+        "random_int32",
+        &[
+            "start",
+            "end",
+            "step",
+            "multiple_of",
+            "exclusive",
+            "exclusive_end",
+            "number_mode",
+        ],
+    ),
+    (
+        "random_int64",
+        &[
+            "start",
+            "end",
+            "step",
+            "multiple_of",
+            "exclusive",
+            "exclusive_end",
+            "number_mode",
+        ],
+    ),
+    (
+        "random_int8",
+        &[
+            "start",
+            "end",
+            "step",
+            "multiple_of",
+            "exclusive",
+            "exclusive_end",
+            "number_mode",
+        ],
+    ),
+    (
+        "random_ipv4",
+        &["start", "end", "exclude", "format", "quote"],
+    ),
+    (
+        "random_ipv4_cidr",
+        &[
+            "addr_start",
+            "addr_end",
+            "length_start",
+            "length_end",
+            "quote",
+        ],
+    ),
+    (
+        "random_ipv4_host",
+        &["cidr", "include_network", "include_broadcast", "quote"],
+    ),
+    (
+        "random_ipv4_range",
+        &["addr_start", "addr_end", "max_size", "quote"],
+    ),
+    ("random_ipv6", &["start", "end", "format", "quote"]),
+    (
+        "random_ipv6_cidr",
+        &[
+            "addr_start",
+            "addr_end",
+            "length_start",
+            "length_end",
+            "quote",
+        ],
+    ),
+    ("random_ipv6_host", &["cidr", "include_network", "quote"]),
+    (
+        "random_log_level",
+        &[
+            "trace_weight",
+            "debug_weight",
+            "info_weight",
+            "warn_weight",
+            "error_weight",
+            "quote",
+        ],
+    ),
+    ("random_lognormal", &["mu", "sigma"]),
+    ("random_mac", &["oui", "format", "quote"]),
+    ("random_mixture", &["components"]),
+    ("random_netmask", &["length_start", "length_end", "quote"]),
+    ("random_normal", &["mean", "std_dev"]),
+    ("random_once", &["key", "values", "value"]),
+    ("random_poisson", &["lambda"]),
+    ("random_quantile", &["quantiles", "values", "weights"]),
+    ("random_sample_from_file", &["path", "count"]),
+    (
+        "random_string",
+        &[
+            "length",
+            "length_min",
+            "length_max",
+            "space",
+            "json_safe",
+            "pronounceable",
+            "charset",
+            "blocklist_path",
+            "quote",
+        ],
+    ),
+    ("random_subset", &["values", "count", "ordered"]),
+    ("random_timestamp", &["start", "end", "unit"]),
+    (
+        "random_tristate",
+        &["true_weight", "false_weight", "null_weight"],
+    ),
+    (
+        "random_uint128",
+        &["start", "end", "exclusive", "exclusive_end", "number_mode"],
+    ),
+    (
+        "random_uint16",
+        &[
+            "start",
+            "end",
+            "step",
+            "multiple_of",
+            "exclusive",
+            "exclusive_end",
+            "number_mode",
+        ],
+    ),
+    (
+        "random_uint32",
+        &[
+            "start",
+            "end",
+            "step",
+            "multiple_of",
+            "exclusive",
+            "exclusive_end",
+            "number_mode",
+        ],
+    ),
+    (
+        "random_uint64",
+        &[
+            "start",
+            "end",
+            "step",
+            "multiple_of",
+            "exclusive",
+            "exclusive_end",
+            "number_mode",
+        ],
+    ),
+    (
+        "random_uint8",
+        &[
+            "start",
+            "end",
+            "step",
+            "multiple_of",
+            "exclusive",
+            "exclusive_end",
+            "number_mode",
+        ],
+    ),
+    ("random_ulid", &["quote"]),
+    ("random_uuid", &["version", "format", "quote"]),
+    ("random_uuid_v5", &["namespace", "name", "format", "quote"]),
+    ("random_uuid_v7", &["format", "quote"]),
+    ("random_weighted", &["choices", "weights"]),
+    ("random_words", &["count", "capitalize", "quote"]),
+    ("register_id", &["pool", "value", "quote"]),
+    ("weighted_from_file", &["path", "delimiter", "quote"]),
+];
+
+/// Look up the parameters accepted by the tera-rand function named `name` in
+/// [`FUNCTION_PARAMETERS`], or return `None` if there is no such function.
+///
+/// # Example usage
+///
+/// ```edition2021
+/// use tera_rand::function_parameters;
+///
+/// assert_eq!(function_parameters("random_bool"), Some(&[][..]));
+/// assert_eq!(function_parameters("not_a_function"), None);
+/// ```
+pub fn function_parameters(name: &str) -> Option<&'static [&'static str]> {
+    FUNCTION_PARAMETERS
+        .iter()
+        .find(|(function_name, _)| *function_name == name)
+        .map(|(_, parameters)| *parameters)
+}
+
+/// Register every function in [`ALL_FUNCTIONS`] with `tera` under its conventional name, both as a
+/// function and, through [`GeneratorFilter`], as a filter. This saves registering each function
+/// one by one.
+///
+/// Functions which require a disabled feature are not registered, so a template which calls one
+/// fails to render with an unknown function error.
+///
+/// # Example usage
+///
+/// ```edition2021
+/// use tera::{Context, Tera};
+///
+/// let mut tera: Tera = Tera::default();
+/// tera_rand::register_all(&mut tera);
+/// let context: Context = Context::new();
+///
+/// let rendered: String = tera
+///     .render_str(
+///         r#"{"hostname": "{{ random_string() }}", "port": {{ random_uint16() }}}"#,
+///         &context
+///     )
+///     .unwrap();
+/// ```
+pub fn register_all(tera: &mut Tera) {
+    for (name, function) in ALL_FUNCTIONS.iter().copied() {
+        tera.register_function(name, function);
+        tera.register_filter(name, GeneratorFilter::new(function));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::register::*;
+    use regex::Regex;
+    use std::collections::HashSet;
+    use tera::Context;
+    use tracing_test::traced_test;
+
+    #[test]
+    #[traced_test]
+    fn test_register_all_renders_template_with_several_functions() {
+        let mut tera: Tera = Tera::default();
+        register_all(&mut tera);
+
+        let rendered: String = tera
+            .render_str(
+                r#"{"host": "{{ random_string(length=6) }}", "port": {{ random_uint16(start=1024) }}, "day": "{{ random_from_file(path="resources/test/days.txt") }}", "upper": "{{ "" | random_hex(length=4) | upper }}"}"#,
+                &Context::new(),
+            )
+            .unwrap();
+
+        let expected_regex: Regex = Regex::new(
+            r#"^\{"host": "\w{6}", "port": \d+, "day": "\w+day", "upper": "[\dA-F]{4}"}$"#,
+        )
+        .unwrap();
+        assert!(expected_regex.is_match(&rendered), "{rendered}");
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_all_functions_have_unique_names() {
+        let names: HashSet<&str> = ALL_FUNCTIONS.iter().map(|(name, _)| *name).collect();
+        assert_eq!(names.len(), ALL_FUNCTIONS.len());
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_all_functions_have_parameters() {
+        for (name, _) in ALL_FUNCTIONS {
+            assert!(function_parameters(name).is_some(), "{name}");
+        }
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_function_parameters_are_sorted_and_unique() {
+        for pair in FUNCTION_PARAMETERS.windows(2) {
+            assert!(pair[0].0 < pair[1].0, "{} and {}", pair[0].0, pair[1].0);
+        }
+    }
+
+    #[test]
+    #[traced_test]
+    #[cfg(all(
+        feature = "distributions",
+        feature = "schema",
+        feature = "sqlite",
+        feature = "time",
+        feature = "ulid",
+        feature = "uuid"
+    ))]
+    fn test_function_parameters_match_all_functions_with_every_feature() {
+        let function_names: Vec<&str> = ALL_FUNCTIONS.iter().map(|(name, _)| *name).collect();
+        let parameter_names: Vec<&str> =
+            FUNCTION_PARAMETERS.iter().map(|(name, _)| *name).collect();
+        assert_eq!(function_names, parameter_names);
+    }
+
+    #[test]
+    #[traced_test]
+    #[cfg(not(feature = "uuid"))]
+    fn test_register_all_skips_functions_of_disabled_features() {
+        let mut tera: Tera = Tera::default();
+        register_all(&mut tera);
+
+        let render_result: tera::Result<String> =
+            tera.render_str("{{ random_uuid() }}", &Context::new());
+        assert!(render_result.is_err());
+        assert!(ALL_FUNCTIONS.iter().all(|(name, _)| *name != "random_uuid"));
+    }
+}
@@ -0,0 +1,230 @@
+use rand::rngs::{OsRng, StdRng};
+use rand::{thread_rng, RngCore, SeedableRng};
+use std::cell::{Cell, RefCell};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use tracing::warn;
+
+// The fallback seed is only meaningful while the fallback is enabled. Both are atomics rather than
+// a `Mutex<Option<u64>>` so that no lock is taken on every draw.
+static ENTROPY_FALLBACK_ENABLED: AtomicBool = AtomicBool::new(false);
+static ENTROPY_FALLBACK_SEED: AtomicU64 = AtomicU64::new(0);
+
+thread_local! {
+    static SEEDED_RNG: RefCell<Option<StdRng>> = const { RefCell::new(None) };
+    // the generator seeded with the fallback seed, once this thread has found that the operating
+    // system cannot provide entropy. It is kept apart from `SEEDED_RNG` so that `clear_seed` does
+    // not restart its stream.
+    static FALLBACK_RNG: RefCell<Option<StdRng>> = const { RefCell::new(None) };
+    // whether the operating system could provide entropy to this thread, or `None` if not checked
+    static OS_ENTROPY_AVAILABLE: Cell<Option<bool>> = const { Cell::new(None) };
+}
+
+/// Seed the random number generator used by every tera-rand function on the current thread.
+///
+/// By default, tera-rand functions draw from [`rand::thread_rng`], so rendering the same template
+/// twice produces different output. After calling `set_seed`, functions called on the same thread
+/// instead draw from a [`StdRng`] seeded with `seed`, so rendering the same template the same
+/// number of times produces the same output.
+///
+/// The seed is thread-local: functions called from other threads are unaffected.
+///
+/// # Example usage
+///
+/// ```edition2021
+/// use tera::{Context, Tera};
+/// use tera_rand::{random_string, set_seed};
+///
+/// let mut tera: Tera = Tera::default();
+/// tera.register_function("random_string", random_string);
+/// let context: Context = Context::new();
+///
+/// set_seed(42);
+/// let first: String = tera.render_str("{{ random_string() }}", &context).unwrap();
+/// set_seed(42);
+/// let second: String = tera.render_str("{{ random_string() }}", &context).unwrap();
+/// assert_eq!(first, second);
+/// ```
+pub fn set_seed(seed: u64) {
+    SEEDED_RNG.with(|seeded_rng| {
+        seeded_rng.replace(Some(StdRng::seed_from_u64(seed)));
+    });
+}
+
+/// Remove the seed installed by [`set_seed`] on the current thread, so that tera-rand functions
+/// go back to drawing from [`rand::thread_rng`].
+pub fn clear_seed() {
+    SEEDED_RNG.with(|seeded_rng| {
+        seeded_rng.replace(None);
+    });
+}
+
+/// Allow tera-rand functions to keep working when the operating system cannot provide entropy,
+/// as in some locked-down sandboxes, by falling back to a [`StdRng`] seeded with `fallback_seed`.
+///
+/// By default, tera-rand functions draw from [`rand::thread_rng`], which panics if it cannot be
+/// seeded from the operating system. With a fallback seed, each thread checks once whether
+/// entropy is available before its first draw. If it is not, the thread logs a warning and
+/// draws from its own generator seeded with `fallback_seed`, so its output is deterministic.
+/// That generator is separate from the one installed by [`set_seed`]: [`clear_seed`] does not
+/// restart it. Threads which can get entropy are unaffected, and a thread on which [`set_seed`]
+/// has been called uses its seeded generator until [`clear_seed`].
+///
+/// Pass `None` to disable the fallback, which is the default.
+///
+/// # Example usage
+///
+/// ```edition2021
+/// use tera_rand::set_entropy_fallback_seed;
+///
+/// // read the fallback seed from the environment, or use a fixed one
+/// let fallback_seed: u64 = std::env::var("MY_FALLBACK_SEED")
+///     .ok()
+///     .and_then(|seed| seed.parse().ok())
+///     .unwrap_or(0);
+/// set_entropy_fallback_seed(Some(fallback_seed));
+/// ```
+pub fn set_entropy_fallback_seed(fallback_seed: Option<u64>) {
+    ENTROPY_FALLBACK_SEED.store(fallback_seed.unwrap_or_default(), Ordering::SeqCst);
+    ENTROPY_FALLBACK_ENABLED.store(fallback_seed.is_some(), Ordering::SeqCst);
+}
+
+// Run `f` with the random number generator for the current thread: the seeded generator if
+// `set_seed` has been called, the fallback generator if this thread has no entropy and a fallback
+// seed is set, or `thread_rng()` otherwise.
+//
+// `f` must not call `with_rng` itself, since the seeded or fallback generator is mutably borrowed
+// while `f` runs.
+pub(crate) fn with_rng<T>(f: impl FnOnce(&mut dyn RngCore) -> T) -> T {
+    SEEDED_RNG.with(|seeded_rng| {
+        if let Some(seeded_rng) = seeded_rng.borrow_mut().as_mut() {
+            return f(seeded_rng);
+        }
+        if !ENTROPY_FALLBACK_ENABLED.load(Ordering::Relaxed) || os_entropy_available() {
+            return f(&mut thread_rng());
+        }
+        FALLBACK_RNG.with(|fallback_rng| {
+            f(fallback_rng
+                .borrow_mut()
+                .get_or_insert_with(new_fallback_rng))
+        })
+    })
+}
+
+// Whether the operating system can provide entropy to this thread, which is checked only once.
+fn os_entropy_available() -> bool {
+    OS_ENTROPY_AVAILABLE.with(|os_entropy_available| match os_entropy_available.get() {
+        Some(available) => available,
+        None => {
+            let available: bool = OsRng.try_fill_bytes(&mut [0u8; 32]).is_ok();
+            os_entropy_available.set(Some(available));
+            available
+        }
+    })
+}
+
+// Create the fallback generator for a thread which the operating system cannot provide entropy to.
+fn new_fallback_rng() -> StdRng {
+    let fallback_seed: u64 = ENTROPY_FALLBACK_SEED.load(Ordering::SeqCst);
+    warn!(
+        "The operating system could not provide entropy, so tera-rand is falling back to a \
+         generator seeded with {fallback_seed}. Output on this thread is deterministic."
+    );
+    StdRng::seed_from_u64(fallback_seed)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::rng::*;
+    use crate::{
+        random_choice, random_email, random_float64, random_hex, random_ipv4, random_mac,
+        random_string, random_uint32, random_uuid, random_words,
+    };
+    use rand::Rng;
+    use std::collections::HashMap;
+    use tera::{Context, Tera, Value};
+    use tracing_test::traced_test;
+
+    #[test]
+    #[traced_test]
+    fn test_same_seed_produces_same_values() {
+        set_seed(7);
+        let first: Vec<u64> = (0..8).map(|_| with_rng(|rng| rng.gen())).collect();
+        set_seed(7);
+        let second: Vec<u64> = (0..8).map(|_| with_rng(|rng| rng.gen())).collect();
+        clear_seed();
+
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_entropy_fallback_when_os_entropy_is_unavailable() {
+        set_entropy_fallback_seed(Some(11));
+        // simulate a sandbox without entropy on this thread
+        OS_ENTROPY_AVAILABLE.with(|os_entropy_available| os_entropy_available.set(Some(false)));
+
+        let first: Vec<u64> = (0..8).map(|_| with_rng(|rng| rng.gen())).collect();
+        // clearing a seed must not restart the fallback generator's stream
+        clear_seed();
+        let second: Vec<u64> = (0..8).map(|_| with_rng(|rng| rng.gen())).collect();
+        let strings: Vec<Value> = (0..8)
+            .map(|_| random_string(&HashMap::new()).unwrap())
+            .collect();
+        set_entropy_fallback_seed(None);
+
+        let mut expected_rng: StdRng = StdRng::seed_from_u64(11);
+        let expected: Vec<u64> = (0..16).map(|_| expected_rng.gen()).collect();
+        assert_eq!(first, expected[..8]);
+        assert_eq!(second, expected[8..]);
+        assert!(strings
+            .iter()
+            .all(|string| string.as_str().unwrap().len() == 8));
+        assert!(logs_contain("falling back to a generator seeded with 11"));
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_same_seed_renders_template_identically() {
+        let mut tera: Tera = Tera::default();
+        for (name, function) in [
+            (
+                "random_string",
+                random_string as fn(&HashMap<String, Value>) -> tera::Result<Value>,
+            ),
+            ("random_uint32", random_uint32),
+            ("random_float64", random_float64),
+            ("random_ipv4", random_ipv4),
+            ("random_mac", random_mac),
+            ("random_choice", random_choice),
+            ("random_email", random_email),
+            ("random_words", random_words),
+            ("random_hex", random_hex),
+            ("random_uuid", random_uuid),
+        ] {
+            tera.register_function(name, function);
+        }
+        #[cfg(feature = "uuid")]
+        let uuid_field: &str = r#""id": "{{ random_uuid() }}", "#;
+        #[cfg(not(feature = "uuid"))]
+        let uuid_field: &str = "";
+        let template: String = format!(
+            r#"{{{uuid_field}"name": "{{{{ random_string() }}}}", "age": {{{{ random_uint32(end=120) }}}}, "score": {{{{ random_float64() }}}}, "ip": "{{{{ random_ipv4() }}}}", "mac": "{{{{ random_mac() }}}}", "tier": "{{{{ random_choice(values=["free", "pro"], weights=[3, 1]) }}}}", "email": "{{{{ random_email() }}}}", "bio": "{{{{ random_words(count=6) }}}}", "token": "{{{{ random_hex() }}}}"}}"#
+        );
+
+        let mut render_many = |seed: u64| -> String {
+            set_seed(seed);
+            let rendered: String = (0..20)
+                .map(|_| tera.render_str(&template, &Context::new()).unwrap())
+                .collect::<Vec<String>>()
+                .join("\n");
+            clear_seed();
+            rendered
+        };
+
+        let first: String = render_many(42);
+        let second: String = render_many(42);
+        let other_seed: String = render_many(43);
+        assert_eq!(first, second);
+        assert_ne!(first, other_seed);
+    }
+}
@@ -0,0 +1,427 @@
+#[cfg(feature = "schema")]
+use crate::common::{gen_float_in_range, gen_value_in_range, parse_arg, quote_if_requested};
+#[cfg(not(feature = "schema"))]
+use crate::error::feature_not_enabled;
+#[cfg(feature = "schema")]
+use crate::error::{conflicting_args, missing_arg, unsupported_schema};
+#[cfg(feature = "schema")]
+use crate::file::read_json_document;
+#[cfg(feature = "schema")]
+use crate::rng::with_rng;
+#[cfg(feature = "schema")]
+use rand::distributions::{Alphanumeric, DistString};
+#[cfg(feature = "schema")]
+use rand::Rng;
+use std::collections::HashMap;
+#[cfg(feature = "schema")]
+use std::sync::Arc;
+#[cfg(feature = "schema")]
+use tera::Map;
+use tera::{Result, Value};
+
+// The span of generated values when a schema bounds only one side of a number, or neither side.
+#[cfg(feature = "schema")]
+const DEFAULT_NUMBER_SPAN: i64 = 1000;
+// The maximum length of generated strings when a schema sets no `maxLength`.
+#[cfg(feature = "schema")]
+const DEFAULT_MAX_LENGTH: u64 = 16;
+// The maximum number of items in generated arrays when a schema sets no `maxItems`.
+#[cfg(feature = "schema")]
+const DEFAULT_MAX_ITEMS: u64 = 5;
+
+/// A Tera function to generate a random JSON document which conforms to a JSON Schema.
+///
+/// The `schema` parameter takes the schema, either as an object, such as one from the template's
+/// context, or as a String containing JSON, since Tera has no object literals. Alternatively, the
+/// `path` parameter takes the path to a file containing the schema, which is read only once and
+/// cached.
+///
+/// Only a subset of JSON Schema is supported, and any other keyword is ignored:
+/// - `enum` picks one of its values, and takes precedence over every other keyword.
+/// - `type` is one of `"null"`, `"boolean"`, `"integer"`, `"number"`, `"string"`, `"array"`, or
+///   `"object"`, or an array of them, in which case one is picked at random. Every schema needs
+///   either a `type` or an `enum`.
+/// - `minimum` and `maximum` bound integers and numbers, inclusively. If only one is set, values
+///   fall within 1000 of it, and if neither is set, values fall between 0 and 1000.
+/// - `minLength` and `maxLength` bound the length of strings, which are alphanumeric.
+///   `maxLength` defaults to 16, or to `minLength` if that is larger.
+/// - `items` is the schema of every item of an array, and `minItems` and `maxItems` bound its
+///   length. `maxItems` defaults to 5, or to `minItems` if that is larger.
+/// - `properties` maps the name of each property of an object to its schema. Properties listed
+///   in `required` are always generated, and the others are generated half of the time.
+///
+/// Pass `quote=true` to render the document as JSON. Otherwise, Tera renders an object as
+/// `[object]`, though it can still be passed to a filter like `json_encode`.
+///
+/// This function requires the `schema` feature, which is not enabled by default.
+///
+/// # Example usage
+///
+/// ```edition2021
+/// use tera::{Context, Tera};
+/// use tera_rand::random_from_schema;
+///
+/// let mut tera: Tera = Tera::default();
+/// tera.register_function("random_from_schema", random_from_schema);
+/// let context: Context = Context::new();
+///
+/// let rendered: String = tera
+///     .render_str(
+///         r#"{{ random_from_schema(schema='{"type": "integer", "minimum": 1, "maximum": 6}') }}"#,
+///         &context,
+///     )
+///     .unwrap();
+/// assert!((1..=6).contains(&rendered.parse::<i64>().unwrap()));
+/// // read the schema from a file, and render the document as JSON
+/// let rendered: String = tera
+///     .render_str(
+///         r#"{{ random_from_schema(path="resources/test/user_schema.json", quote=true) }}"#,
+///         &context,
+///     )
+///     .unwrap();
+/// assert!(rendered.starts_with('{'));
+/// ```
+#[cfg(feature = "schema")]
+pub fn random_from_schema(args: &HashMap<String, Value>) -> Result<Value> {
+    let schema_opt: Option<Value> = parse_arg(args, "schema")?;
+    let path_opt: Option<String> = parse_arg(args, "path")?;
+
+    let document: Value = match (schema_opt, path_opt) {
+        (Some(_), Some(_)) => return Err(conflicting_args("schema", "path")),
+        (Some(Value::String(schema_json)), None) => {
+            let schema: Value = serde_json::from_str(&schema_json)
+                .map_err(|e| unsupported_schema(format!("unable to parse schema: {e}")))?;
+            gen_from_schema(&schema)?
+        }
+        (Some(schema), None) => gen_from_schema(&schema)?,
+        // the schema file is parsed once and cached, like every other file tera-rand reads
+        (None, Some(path)) => {
+            let schema: Arc<Value> = read_json_document(&path)?;
+            gen_from_schema(&schema)?
+        }
+        (None, None) => return Err(missing_arg("schema")),
+    };
+    quote_if_requested(args, document)
+}
+
+/// A stub for [`random_from_schema`] when the `schema` feature is disabled, which always returns
+/// an error explaining that the feature is not enabled.
+#[cfg(not(feature = "schema"))]
+pub fn random_from_schema(_args: &HashMap<String, Value>) -> Result<Value> {
+    Err(feature_not_enabled("random_from_schema", "schema"))
+}
+
+// Generate a random value which conforms to `schema`.
+#[cfg(feature = "schema")]
+fn gen_from_schema(schema: &Value) -> Result<Value> {
+    let schema: &Map<String, Value> = schema
+        .as_object()
+        .ok_or_else(|| unsupported_schema(format!("expected an object, but found {schema}")))?;
+
+    if let Some(enum_values) = schema.get("enum") {
+        return match enum_values.as_array() {
+            Some(enum_values) if !enum_values.is_empty() => {
+                let index: usize = with_rng(|rng| rng.gen_range(0usize..enum_values.len()));
+                Ok(enum_values[index].clone())
+            }
+            _ => Err(unsupported_schema(format!(
+                "`enum` should be a non-empty array, but found {enum_values}"
+            ))),
+        };
+    }
+
+    let schema_type: &str = match schema.get("type") {
+        Some(Value::String(schema_type)) => schema_type.as_str(),
+        Some(Value::Array(schema_types)) if !schema_types.is_empty() => {
+            let index: usize = with_rng(|rng| rng.gen_range(0usize..schema_types.len()));
+            schema_types[index].as_str().unwrap_or_default()
+        }
+        Some(schema_type) => {
+            return Err(unsupported_schema(format!(
+                "unsupported `type` {schema_type}"
+            )))
+        }
+        None => return Err(unsupported_schema(String::from("missing `type` or `enum`"))),
+    };
+    match schema_type {
+        "null" => Ok(Value::Null),
+        "boolean" => Ok(Value::Bool(with_rng(|rng| rng.gen()))),
+        "integer" => gen_integer(schema),
+        "number" => gen_number(schema),
+        "string" => gen_string(schema),
+        "array" => gen_array(schema),
+        "object" => gen_object(schema),
+        _ => Err(unsupported_schema(format!(
+            "unsupported `type` \"{schema_type}\""
+        ))),
+    }
+}
+
+#[cfg(feature = "schema")]
+fn gen_integer(schema: &Map<String, Value>) -> Result<Value> {
+    // round fractional bounds inwards, so that every generated integer is within them
+    let minimum_opt: Option<i64> = parse_keyword::<f64>(schema, "minimum")?
+        .map(|minimum| minimum.ceil().clamp(i64::MIN as f64, i64::MAX as f64) as i64);
+    let maximum_opt: Option<i64> = parse_keyword::<f64>(schema, "maximum")?
+        .map(|maximum| maximum.floor().clamp(i64::MIN as f64, i64::MAX as f64) as i64);
+    let (minimum, maximum): (i64, i64) = match (minimum_opt, maximum_opt) {
+        (Some(minimum), Some(maximum)) => (minimum, maximum),
+        (Some(minimum), None) => (minimum, minimum.saturating_add(DEFAULT_NUMBER_SPAN)),
+        (None, Some(maximum)) => (maximum.saturating_sub(DEFAULT_NUMBER_SPAN), maximum),
+        (None, None) => (0, DEFAULT_NUMBER_SPAN),
+    };
+    let integer: i64 = gen_value_in_range(Some(minimum), Some(maximum), minimum, maximum)
+        .map_err(|_| unsupported_schema(format!("no integers between {minimum} and {maximum}")))?;
+    Ok(Value::from(integer))
+}
+
+#[cfg(feature = "schema")]
+fn gen_number(schema: &Map<String, Value>) -> Result<Value> {
+    let default_span: f64 = DEFAULT_NUMBER_SPAN as f64;
+    let minimum_opt: Option<f64> = parse_keyword(schema, "minimum")?;
+    let maximum_opt: Option<f64> = parse_keyword(schema, "maximum")?;
+    let (minimum, maximum): (f64, f64) = match (minimum_opt, maximum_opt) {
+        (Some(minimum), Some(maximum)) => (minimum, maximum),
+        (Some(minimum), None) => (minimum, minimum + default_span),
+        (None, Some(maximum)) => (maximum - default_span, maximum),
+        (None, None) => (0.0, default_span),
+    };
+    let number: f64 = gen_float_in_range(Some(minimum), Some(maximum))
+        .map_err(|_| unsupported_schema(format!("no numbers between {minimum} and {maximum}")))?;
+    Ok(Value::from(number))
+}
+
+#[cfg(feature = "schema")]
+fn gen_string(schema: &Map<String, Value>) -> Result<Value> {
+    let (min_length, max_length): (u64, u64) =
+        parse_length_keywords(schema, "minLength", "maxLength", DEFAULT_MAX_LENGTH)?;
+    let length: u64 = with_rng(|rng| rng.gen_range(min_length..=max_length));
+    Ok(Value::from(with_rng(|rng| {
+        Alphanumeric.sample_string(rng, length as usize)
+    })))
+}
+
+#[cfg(feature = "schema")]
+fn gen_array(schema: &Map<String, Value>) -> Result<Value> {
+    let items_schema: &Value = schema
+        .get("items")
+        .ok_or_else(|| unsupported_schema(String::from("an array schema needs `items`")))?;
+    let (min_items, max_items): (u64, u64) =
+        parse_length_keywords(schema, "minItems", "maxItems", DEFAULT_MAX_ITEMS)?;
+    let item_count: u64 = with_rng(|rng| rng.gen_range(min_items..=max_items));
+    (0..item_count)
+        .map(|_| gen_from_schema(items_schema))
+        .collect::<Result<Vec<Value>>>()
+        .map(Value::Array)
+}
+
+#[cfg(feature = "schema")]
+fn gen_object(schema: &Map<String, Value>) -> Result<Value> {
+    let empty_properties: Map<String, Value> = Map::new();
+    let properties: &Map<String, Value> = match schema.get("properties") {
+        Some(Value::Object(properties)) => properties,
+        Some(properties) => {
+            return Err(unsupported_schema(format!(
+                "`properties` should be an object, but found {properties}"
+            )))
+        }
+        None => &empty_properties,
+    };
+    let required: Vec<String> = parse_keyword(schema, "required")?.unwrap_or_default();
+    if let Some(missing_property) = required
+        .iter()
+        .find(|property| !properties.contains_key(*property))
+    {
+        return Err(unsupported_schema(format!(
+            "required property `{missing_property}` has no schema in `properties`"
+        )));
+    }
+
+    let mut object: Map<String, Value> = Map::new();
+    for (property, property_schema) in properties {
+        let include: bool = required.contains(property) || with_rng(|rng| rng.gen_bool(0.5));
+        if include {
+            object.insert(property.clone(), gen_from_schema(property_schema)?);
+        }
+    }
+    Ok(Value::Object(object))
+}
+
+// Parse the minimum and maximum of a length, such as `minLength` and `maxLength`. The maximum
+// defaults to `default_max`, or to the minimum if that is larger.
+#[cfg(feature = "schema")]
+fn parse_length_keywords(
+    schema: &Map<String, Value>,
+    min_keyword: &str,
+    max_keyword: &str,
+    default_max: u64,
+) -> Result<(u64, u64)> {
+    let min: u64 = parse_keyword(schema, min_keyword)?.unwrap_or(0);
+    let max: u64 = parse_keyword(schema, max_keyword)?.unwrap_or(default_max.max(min));
+    if min > max {
+        return Err(unsupported_schema(format!(
+            "`{min_keyword}` {min} is greater than `{max_keyword}` {max}"
+        )));
+    }
+    Ok((min, max))
+}
+
+#[cfg(feature = "schema")]
+fn parse_keyword<T>(schema: &Map<String, Value>, keyword: &str) -> Result<Option<T>>
+where
+    T: serde::de::DeserializeOwned,
+{
+    schema
+        .get(keyword)
+        .map(|value| {
+            tera::from_value(value.clone())
+                .map_err(|_| unsupported_schema(format!("invalid `{keyword}` {value}")))
+        })
+        .transpose()
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "schema")]
+    use crate::common::tests::test_tera_rand_function;
+    use crate::common::tests::test_tera_rand_function_returns_error;
+    use crate::schema::*;
+    #[cfg(feature = "schema")]
+    use jsonschema::JSONSchema;
+    use tracing_test::traced_test;
+
+    #[cfg(feature = "schema")]
+    fn assert_documents_conform(schema: Value) {
+        let compiled_schema: JSONSchema = JSONSchema::compile(&schema).unwrap();
+        let args: HashMap<String, Value> = HashMap::from([(String::from("schema"), schema)]);
+        for _ in 0..200 {
+            let document: Value = random_from_schema(&args).unwrap();
+            assert!(
+                compiled_schema.is_valid(&document),
+                "{document} should conform to the schema"
+            );
+        }
+    }
+
+    #[test]
+    #[traced_test]
+    #[cfg(feature = "schema")]
+    fn test_random_from_schema() {
+        test_tera_rand_function(
+            random_from_schema,
+            "random_from_schema",
+            r#"{ "some_field": {{ random_from_schema(schema='{"type": "integer", "minimum": 1, "maximum": 6}') }} }"#,
+            r#"\{ "some_field": [1-6] }"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    #[cfg(feature = "schema")]
+    fn test_random_from_schema_from_path_with_quote() {
+        test_tera_rand_function(
+            random_from_schema,
+            "random_from_schema",
+            r#"{{ random_from_schema(path="resources/test/user_schema.json", quote=true) }}"#,
+            r#"^\{.*"id":\d+.*}$"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    #[cfg(feature = "schema")]
+    fn test_random_from_schema_from_path_reads_file_once() {
+        let filepath: std::path::PathBuf =
+            std::env::temp_dir().join(format!("tera_rand_test_schema_{}.json", std::process::id()));
+        std::fs::write(&filepath, r#"{"enum": ["before"]}"#).unwrap();
+        let args: HashMap<String, Value> = HashMap::from([(
+            String::from("path"),
+            Value::from(filepath.to_str().unwrap()),
+        )]);
+        assert_eq!(random_from_schema(&args).unwrap(), "before");
+
+        std::fs::write(&filepath, "not json").unwrap();
+        let cached_result: Result<Value> = random_from_schema(&args);
+        std::fs::remove_file(&filepath).unwrap();
+        assert_eq!(cached_result.unwrap(), "before");
+    }
+
+    #[test]
+    #[traced_test]
+    #[cfg(feature = "schema")]
+    fn test_random_from_schema_conforms_to_schema_file() {
+        let schema_json: String =
+            std::fs::read_to_string("resources/test/user_schema.json").unwrap();
+        assert_documents_conform(serde_json::from_str(&schema_json).unwrap());
+    }
+
+    #[test]
+    #[traced_test]
+    #[cfg(feature = "schema")]
+    fn test_random_from_schema_conforms_to_every_supported_keyword() {
+        assert_documents_conform(serde_json::json!({
+            "type": "object",
+            "required": ["count", "ratio", "code", "tags", "flag", "nothing", "either"],
+            "properties": {
+                "count": {"type": "integer", "minimum": -5.5, "maximum": 5.5},
+                "high": {"type": "integer", "minimum": 9007199254740000i64},
+                "low": {"type": "integer", "maximum": -9223372036854775808i64},
+                "ratio": {"type": "number", "minimum": 0.25, "maximum": 0.5},
+                "code": {"type": "string", "minLength": 3, "maxLength": 3},
+                "long": {"type": "string", "minLength": 20},
+                "tags": {
+                    "type": "array",
+                    "minItems": 1,
+                    "maxItems": 3,
+                    "items": {"enum": ["a", "b", 3, null]}
+                },
+                "flag": {"type": "boolean"},
+                "nothing": {"type": "null"},
+                "either": {"type": ["string", "integer"], "maxLength": 2, "maximum": 0},
+                "nested": {"type": "object", "properties": {}}
+            }
+        }));
+    }
+
+    #[test]
+    #[traced_test]
+    #[cfg(feature = "schema")]
+    fn test_random_from_schema_generates_optional_properties_sometimes() {
+        let args: HashMap<String, Value> = HashMap::from([(
+            String::from("schema"),
+            Value::from(r#"{"type": "object", "properties": {"maybe": {"type": "null"}}}"#),
+        )]);
+        let mut included_count: usize = 0;
+        for _ in 0..200 {
+            if random_from_schema(&args).unwrap().get("maybe").is_some() {
+                included_count += 1;
+            }
+        }
+        assert!(included_count > 0 && included_count < 200);
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_from_schema_with_invalid_args_returns_error() {
+        for template in [
+            "{{ random_from_schema() }}",
+            r#"{{ random_from_schema(schema='{"type": "integer"}', path="resources/test/user_schema.json") }}"#,
+            r#"{{ random_from_schema(path="resources/test/nonexistent.json") }}"#,
+            r#"{{ random_from_schema(schema='{"type": ') }}"#,
+            r#"{{ random_from_schema(schema='{"minimum": 1}') }}"#,
+            r#"{{ random_from_schema(schema='{"type": "date"}') }}"#,
+            r#"{{ random_from_schema(schema='{"enum": []}') }}"#,
+            r#"{{ random_from_schema(schema='{"type": "integer", "minimum": 2, "maximum": 1}') }}"#,
+            r#"{{ random_from_schema(schema='{"type": "integer", "minimum": 1.2, "maximum": 1.8}') }}"#,
+            r#"{{ random_from_schema(schema='{"type": "string", "minLength": 5, "maxLength": 4}') }}"#,
+            r#"{{ random_from_schema(schema='{"type": "array"}') }}"#,
+            r#"{{ random_from_schema(schema='{"type": "object", "required": ["id"]}') }}"#,
+        ] {
+            test_tera_rand_function_returns_error(
+                random_from_schema,
+                "random_from_schema",
+                template,
+            );
+        }
+    }
+}
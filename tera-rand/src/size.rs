@@ -0,0 +1,191 @@
+#[cfg(feature = "distributions")]
+use crate::common::parse_arg;
+#[cfg(not(feature = "distributions"))]
+use crate::error::feature_not_enabled;
+#[cfg(feature = "distributions")]
+use crate::error::unsupported_arg;
+#[cfg(feature = "distributions")]
+use crate::rng::with_rng;
+#[cfg(feature = "distributions")]
+use rand::distributions::Distribution;
+#[cfg(feature = "distributions")]
+use rand_distr::LogNormal;
+use std::collections::HashMap;
+#[cfg(feature = "distributions")]
+use tera::to_value;
+use tera::{Result, Value};
+
+#[cfg(feature = "distributions")]
+const BINARY_UNITS: &[&str] = &["KiB", "MiB", "GiB", "TiB", "PiB", "EiB"];
+
+/// A Tera function to generate a random, realistic file size in bytes. Sizes follow a log-normal
+/// distribution, as real file sizes tend to: most files are small, and a few are huge.
+///
+/// The `median` parameter takes the median size in bytes, which must be positive. Half of the
+/// generated sizes are smaller than it, and half are larger. It defaults to `65536` (64 KiB).
+///
+/// The `sigma` parameter takes the standard deviation of the natural logarithm of the size, which
+/// controls how far the sizes spread from the median. It must not be negative. It defaults to
+/// `2.0`, which puts roughly 1 in 20 sizes beyond 25 times the median.
+///
+/// The `format` parameter takes `"bytes"` to generate the size as an integer number of bytes, or
+/// `"human"` to generate a string in binary units with one decimal place, such as `12.3 MiB`. Sizes
+/// under 1 KiB are written as whole bytes, such as `512 B`. It defaults to `"bytes"`.
+///
+/// This function requires the `distributions` feature, which is enabled by default.
+///
+/// # Example usage
+///
+/// ```edition2021
+/// use tera::{Context, Tera};
+/// use tera_rand::random_file_size;
+///
+/// let mut tera: Tera = Tera::default();
+/// tera.register_function("random_file_size", random_file_size);
+/// let context: Context = Context::new();
+///
+/// let rendered: String = tera
+///     .render_str("{{ random_file_size() }}", &context)
+///     .unwrap();
+/// // mostly around 1 MiB, written like "1.2 MiB"
+/// let rendered: String = tera
+///     .render_str(
+///         r#"{{ random_file_size(median=1048576, sigma=0.5, format="human") }}"#,
+///         &context,
+///     )
+///     .unwrap();
+/// ```
+#[cfg(feature = "distributions")]
+pub fn random_file_size(args: &HashMap<String, Value>) -> Result<Value> {
+    let median: f64 = parse_arg(args, "median")?.unwrap_or(65536.0);
+    if !(median > 0.0 && median.is_finite()) {
+        return Err(unsupported_arg("median", median.to_string()));
+    }
+    let sigma: f64 = parse_arg(args, "sigma")?.unwrap_or(2.0);
+    if !(sigma >= 0.0 && sigma.is_finite()) {
+        return Err(unsupported_arg("sigma", sigma.to_string()));
+    }
+    let format: String = parse_arg(args, "format")?.unwrap_or_else(|| String::from("bytes"));
+
+    // the median of a log-normal distribution is e^mu
+    let log_normal: LogNormal<f64> = LogNormal::new(median.ln(), sigma)
+        .map_err(|_| unsupported_arg("sigma", sigma.to_string()))?;
+    // the cast saturates, so that an extremely large sample becomes u64::MAX
+    let random_size: u64 = with_rng(|rng| log_normal.sample(rng)).round() as u64;
+
+    match format.as_str() {
+        "bytes" => Ok(to_value(random_size)?),
+        "human" => Ok(to_value(format_human_size(random_size))?),
+        _ => Err(unsupported_arg("format", format)),
+    }
+}
+
+/// A stub for [`random_file_size`] when the `distributions` feature is disabled, which always
+/// returns an error explaining that the feature is not enabled.
+#[cfg(not(feature = "distributions"))]
+pub fn random_file_size(_args: &HashMap<String, Value>) -> Result<Value> {
+    Err(feature_not_enabled("random_file_size", "distributions"))
+}
+
+// Write a size in bytes using the largest binary unit which keeps the value at least 1.0 once
+// rounded to one decimal place.
+#[cfg(feature = "distributions")]
+fn format_human_size(size: u64) -> String {
+    if size < 1024 {
+        return format!("{size} B");
+    }
+    let mut scaled_size: f64 = size as f64 / 1024.0;
+    let mut unit_index: usize = 0;
+    while (scaled_size * 10.0).round() >= 1024.0 * 10.0 && unit_index + 1 < BINARY_UNITS.len() {
+        scaled_size /= 1024.0;
+        unit_index += 1;
+    }
+    format!("{scaled_size:.1} {}", BINARY_UNITS[unit_index])
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "distributions")]
+    use crate::common::tests::test_tera_rand_function;
+    use crate::common::tests::test_tera_rand_function_returns_error;
+    use crate::size::*;
+    use tracing_test::traced_test;
+
+    #[test]
+    #[traced_test]
+    #[cfg(feature = "distributions")]
+    fn test_random_file_size() {
+        test_tera_rand_function(
+            random_file_size,
+            "random_file_size",
+            r#"{ "some_field": {{ random_file_size() }} }"#,
+            r#"\{ "some_field": \d+ }"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    #[cfg(feature = "distributions")]
+    fn test_random_file_size_skews_small() {
+        let args: HashMap<String, Value> = HashMap::from([
+            (String::from("median"), Value::from(1000)),
+            (String::from("sigma"), Value::from(1.5)),
+        ]);
+
+        let sample_count: usize = 10_000;
+        let mut sizes: Vec<u64> = (0..sample_count)
+            .map(|_| random_file_size(&args).unwrap().as_u64().unwrap())
+            .collect();
+        sizes.sort_unstable();
+
+        // about half of the sizes are below the median, but the long tail of huge sizes drags the
+        // mean far above it
+        let below_median: usize = sizes.iter().filter(|size| **size < 1000).count();
+        let mean: f64 = sizes.iter().map(|size| *size as f64).sum::<f64>() / sample_count as f64;
+        assert!((below_median as f64 / sample_count as f64 - 0.5).abs() < 0.03);
+        assert!(mean > 2000.0);
+        assert!(sizes[sample_count * 99 / 100] > 10 * sizes[sample_count / 2]);
+    }
+
+    #[test]
+    #[traced_test]
+    #[cfg(feature = "distributions")]
+    fn test_random_file_size_with_human_format() {
+        test_tera_rand_function(
+            random_file_size,
+            "random_file_size",
+            r#"{ "some_field": "{{ random_file_size(format="human") }}" }"#,
+            r#"\{ "some_field": "(\d+ B|\d+\.\d [KMGTPE]iB)" }"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    #[cfg(feature = "distributions")]
+    fn test_format_human_size() {
+        for (size, expected) in [
+            (0, "0 B"),
+            (1023, "1023 B"),
+            (1024, "1.0 KiB"),
+            (1536, "1.5 KiB"),
+            (12_897_484, "12.3 MiB"),
+            (1_048_575, "1.0 MiB"),
+            (5 * 1024 * 1024 * 1024, "5.0 GiB"),
+            (u64::MAX, "16.0 EiB"),
+        ] {
+            assert_eq!(format_human_size(size), expected);
+        }
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_file_size_with_invalid_args_returns_error() {
+        for template in [
+            "{{ random_file_size(median=0) }}",
+            "{{ random_file_size(sigma=-1.0) }}",
+            r#"{{ random_file_size(format="kilobytes") }}"#,
+        ] {
+            test_tera_rand_function_returns_error(random_file_size, "random_file_size", template);
+        }
+    }
+}
@@ -0,0 +1,259 @@
+#[cfg(feature = "sqlite")]
+use crate::common::{parse_arg, quote_if_requested};
+#[cfg(not(feature = "sqlite"))]
+use crate::error::feature_not_enabled;
+#[cfg(feature = "sqlite")]
+use crate::error::{missing_arg, no_values_to_sample, query_database_error};
+#[cfg(feature = "sqlite")]
+use crate::rng::with_rng;
+#[cfg(feature = "sqlite")]
+use dashmap::DashMap;
+#[cfg(feature = "sqlite")]
+use lazy_static::lazy_static;
+#[cfg(feature = "sqlite")]
+use rand::Rng;
+#[cfg(feature = "sqlite")]
+use rusqlite::types::ValueRef;
+#[cfg(feature = "sqlite")]
+use rusqlite::{Connection, OpenFlags, Row};
+use std::collections::HashMap;
+#[cfg(feature = "sqlite")]
+use std::sync::Arc;
+#[cfg(feature = "sqlite")]
+use tera::Map;
+use tera::{Result, Value};
+
+#[cfg(feature = "sqlite")]
+lazy_static! {
+    // query results keyed by database path, then by query
+    static ref QUERY_RESULTS: DashMap<(String, String), Arc<Vec<Value>>> = DashMap::new();
+}
+
+/// A Tera function to sample a random row from the results of a query against a SQLite database.
+///
+/// The `db` parameter takes the path to the database file, which is opened read-only. The `query`
+/// parameter takes the SQL query to run. The query runs once for each combination of `db` and
+/// `query`, and its results are cached, so later calls only sample from the cached rows.
+///
+/// If the query selects a single column, the sampled value is the value of that column. If it
+/// selects several columns, the sampled value is an object keyed by column name, whose fields a
+/// template can access individually. SQL `NULL` becomes null, integers and reals become numbers,
+/// text becomes a String, and blobs become arrays of bytes.
+///
+/// Pass `quote=true` to render the value as JSON.
+///
+/// This function requires the `sqlite` feature, which is not enabled by default.
+///
+/// # Example usage
+///
+/// ```edition2021
+/// use tera::{Context, Tera};
+/// use tera_rand::random_from_sqlite;
+///
+/// let mut tera: Tera = Tera::default();
+/// tera.register_function("random_from_sqlite", random_from_sqlite);
+/// let context: Context = Context::new();
+///
+/// let rendered: String = tera
+///     .render_str(
+///         r#"{{ random_from_sqlite(db="resources/test/colors.db", query="SELECT name FROM colors") }}"#,
+///         &context,
+///     )
+///     .unwrap_or_default();
+/// // access the columns of a row by name
+/// let rendered: String = tera
+///     .render_str(
+///         r#"{% set color = random_from_sqlite(db="resources/test/colors.db", query="SELECT name, hex FROM colors") %}{{ color.name }}: {{ color.hex }}"#,
+///         &context,
+///     )
+///     .unwrap_or_default();
+/// ```
+#[cfg(feature = "sqlite")]
+pub fn random_from_sqlite(args: &HashMap<String, Value>) -> Result<Value> {
+    let db_opt: Option<String> = parse_arg(args, "db")?;
+    let db: String = db_opt.ok_or_else(|| missing_arg("db"))?;
+    let query_opt: Option<String> = parse_arg(args, "query")?;
+    let query: String = query_opt.ok_or_else(|| missing_arg("query"))?;
+
+    let rows: Arc<Vec<Value>> = get_or_run_query(db, query)?;
+    if rows.is_empty() {
+        return Err(no_values_to_sample("query"));
+    }
+    let random_row: Value = rows[with_rng(|rng| rng.gen_range(0usize..rows.len()))].clone();
+    quote_if_requested(args, random_row)
+}
+
+/// A stub for [`random_from_sqlite`] when the `sqlite` feature is disabled, which always returns
+/// an error explaining that the feature is not enabled.
+#[cfg(not(feature = "sqlite"))]
+pub fn random_from_sqlite(_args: &HashMap<String, Value>) -> Result<Value> {
+    Err(feature_not_enabled("random_from_sqlite", "sqlite"))
+}
+
+// Return the cached rows for the query, running it first if this is the first time it is used.
+// Failed queries are not cached, so that a fixed database is picked up on the next call.
+#[cfg(feature = "sqlite")]
+fn get_or_run_query(db: String, query: String) -> Result<Arc<Vec<Value>>> {
+    let cache_key: (String, String) = (db, query);
+    if let Some(rows) = QUERY_RESULTS.get(&cache_key) {
+        return Ok(rows.clone());
+    }
+    let (db, query) = &cache_key;
+    let rows: Arc<Vec<Value>> = Arc::new(
+        run_query(db, query)
+            .map_err(|e| query_database_error(db.clone(), query.clone(), e.to_string()))?,
+    );
+    QUERY_RESULTS.insert(cache_key.clone(), rows.clone());
+    Ok(rows)
+}
+
+#[cfg(feature = "sqlite")]
+fn run_query(db: &str, query: &str) -> rusqlite::Result<Vec<Value>> {
+    let connection: Connection = Connection::open_with_flags(db, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+    let mut statement = connection.prepare(query)?;
+    let column_names: Vec<String> = statement
+        .column_names()
+        .into_iter()
+        .map(String::from)
+        .collect();
+
+    let mut rows: Vec<Value> = Vec::new();
+    let mut query_rows = statement.query([])?;
+    while let Some(row) = query_rows.next()? {
+        rows.push(row_to_value(row, &column_names)?);
+    }
+    Ok(rows)
+}
+
+// Convert a single-column row into the value of that column, and any other row into an object
+// keyed by column name.
+#[cfg(feature = "sqlite")]
+fn row_to_value(row: &Row, column_names: &[String]) -> rusqlite::Result<Value> {
+    if column_names.len() == 1 {
+        return Ok(column_to_value(row.get_ref(0)?));
+    }
+    let mut object: Map<String, Value> = Map::new();
+    for (column_index, column_name) in column_names.iter().enumerate() {
+        object.insert(
+            column_name.clone(),
+            column_to_value(row.get_ref(column_index)?),
+        );
+    }
+    Ok(Value::Object(object))
+}
+
+#[cfg(feature = "sqlite")]
+fn column_to_value(column: ValueRef) -> Value {
+    match column {
+        ValueRef::Null => Value::Null,
+        ValueRef::Integer(integer) => Value::from(integer),
+        ValueRef::Real(real) => Value::from(real),
+        ValueRef::Text(text) => Value::from(String::from_utf8_lossy(text).into_owned()),
+        ValueRef::Blob(blob) => Value::from(blob.to_vec()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "sqlite")]
+    use crate::common::tests::test_tera_rand_function;
+    use crate::common::tests::test_tera_rand_function_returns_error;
+    use crate::sqlite::*;
+    use tracing_test::traced_test;
+
+    #[test]
+    #[traced_test]
+    #[cfg(feature = "sqlite")]
+    fn test_random_from_sqlite_with_single_column() {
+        test_tera_rand_function(
+            random_from_sqlite,
+            "random_from_sqlite",
+            r#"{ "some_field": "{{ random_from_sqlite(db="resources/test/colors.db", query="SELECT name FROM colors") }}" }"#,
+            r#"\{ "some_field": "(red|green|blue)" }"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    #[cfg(feature = "sqlite")]
+    fn test_random_from_sqlite_with_several_columns() {
+        let args: HashMap<String, Value> = HashMap::from([
+            (String::from("db"), Value::from("resources/test/colors.db")),
+            (
+                String::from("query"),
+                Value::from("SELECT name, hex, brightness, rank FROM colors ORDER BY rank"),
+            ),
+        ]);
+
+        let mut sampled_names: Vec<String> = Vec::new();
+        for _ in 0..100 {
+            let row: Value = random_from_sqlite(&args).unwrap();
+            let name: &str = row["name"].as_str().unwrap();
+            match name {
+                "red" => {
+                    assert_eq!(row["hex"], "#ff0000");
+                    assert_eq!(row["brightness"], 0.3);
+                    assert_eq!(row["rank"], 1);
+                }
+                "green" => assert_eq!(row["rank"], 2),
+                "blue" => {
+                    assert_eq!(row["brightness"], Value::Null);
+                    assert_eq!(row["rank"], 3);
+                }
+                _ => panic!("unexpected row {row}"),
+            }
+            if !sampled_names.iter().any(|sampled| sampled == name) {
+                sampled_names.push(String::from(name));
+            }
+        }
+        assert_eq!(sampled_names.len(), 3);
+    }
+
+    #[test]
+    #[traced_test]
+    #[cfg(feature = "sqlite")]
+    fn test_random_from_sqlite_with_quote() {
+        test_tera_rand_function(
+            random_from_sqlite,
+            "random_from_sqlite",
+            r#"{ "some_field": {{ random_from_sqlite(db="resources/test/colors.db", query="SELECT hex FROM colors WHERE rank = 1", quote=true) }} }"#,
+            r##"\{ "some_field": "#ff0000" }"##,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    #[cfg(feature = "sqlite")]
+    fn test_random_from_sqlite_with_failing_query_explains_error() {
+        let args: HashMap<String, Value> = HashMap::from([
+            (String::from("db"), Value::from("resources/test/colors.db")),
+            (
+                String::from("query"),
+                Value::from("SELECT name FROM shapes"),
+            ),
+        ]);
+
+        let error_message: String = random_from_sqlite(&args).unwrap_err().to_string();
+        assert!(error_message.contains("SELECT name FROM shapes"));
+        assert!(error_message.contains("resources/test/colors.db"));
+        assert!(error_message.contains("no such table: shapes"));
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_from_sqlite_with_invalid_args_returns_error() {
+        for template in [
+            r#"{{ random_from_sqlite(query="SELECT name FROM colors") }}"#,
+            r#"{{ random_from_sqlite(db="resources/test/colors.db") }}"#,
+            r#"{{ random_from_sqlite(db="resources/test/nonexistent.db", query="SELECT 1") }}"#,
+            r#"{{ random_from_sqlite(db="resources/test/colors.db", query="SELECT name FROM colors WHERE rank > 3") }}"#,
+            r#"{{ random_from_sqlite(db="resources/test/colors.db", query="DELETE FROM colors") }}"#,
+        ] {
+            test_tera_rand_function_returns_error(
+                random_from_sqlite,
+                "random_from_sqlite",
+                template,
+            );
+        }
+    }
+}
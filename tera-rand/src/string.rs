@@ -1,14 +1,67 @@
-use crate::common::parse_arg;
-use crate::error::unsupported_arg;
+use crate::common::{gen_json_safe_char, parse_arg, quote_if_requested};
+use crate::error::{
+    blocklist_attempts_exhausted, conflicting_args, empty_range, missing_arg, unsupported_arg,
+};
+use crate::file::read_blocklist;
+use crate::rng::with_rng;
+use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
+use base64::Engine;
 use rand::distributions::{Alphanumeric, DistString, Standard};
-use rand::thread_rng;
-use std::collections::HashMap;
+use rand::{Rng, RngCore};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
 use tera::{to_value, Result, Value};
 
+// The number of strings to generate before giving up on finding one which is not in the blocklist.
+const MAX_BLOCKLIST_ATTEMPTS: usize = 100;
+// The longest supported hex string, so that a mistyped length cannot exhaust memory.
+const MAX_HEX_LENGTH: usize = 1_000_000;
+
+// Characters which may start an unquoted SQL identifier. Only lowercase letters are used, since
+// many databases fold unquoted identifiers to lowercase or uppercase.
+const IDENTIFIER_START_CHARS: &[u8] = b"abcdefghijklmnopqrstuvwxyz_";
+// Characters which may appear after the first character of an unquoted SQL identifier.
+const IDENTIFIER_CHARS: &[u8] = b"abcdefghijklmnopqrstuvwxyz_0123456789";
+
+// Letters which alternate in pronounceable strings. Consonants which are easily confused when read
+// aloud, or which rarely start a syllable, are left out.
+const PRONOUNCEABLE_CONSONANTS: &[u8] = b"bdfghjklmnprstvz";
+const PRONOUNCEABLE_VOWELS: &[u8] = b"aeiou";
+
 /// A Tera function to generate a random String.
 ///
 /// By default, this function will generate an alphanumeric string of length 8. For a string with
-/// a different length, pass an integer length to the `length` parameter in the template.
+/// a different length, pass an integer length to the `length` parameter in the template. A
+/// `length` of 0 produces an empty string, and a negative `length` is an error.
+///
+/// For strings of varying length, pass `length_min` and `length_max` instead of `length`. The
+/// length is then sampled uniformly between them, inclusive. Both must be passed, and it is an
+/// error to combine them with `length`.
+///
+/// The `space` parameter chooses which characters are sampled: `alphanumeric` (the default) or
+/// `standard`, which samples from all valid Unicode scalar values. Be careful when using the
+/// `standard` space inside a JSON string in a template: it can produce quotation marks, reverse
+/// solidi, and control characters, which make the rendered JSON invalid. To prevent that, pass
+/// `json_safe=true`, which avoids sampling any character that would need escaping in a JSON
+/// string. The length of the generated string is unaffected.
+///
+/// For strings which are easy to read aloud and remember, such as human-friendly passwords, pass
+/// `pronounceable=true`. The string then alternates lowercase consonants and vowels, like
+/// `tobivaku`, starting with a consonant. `pronounceable` cannot be used together with `space`.
+///
+/// To sample from a specific alphabet instead, such as hex digits or DNA bases, pass a string of
+/// the allowed characters to the `charset` parameter, like `charset="ACGT"`. Each character of the
+/// generated string is sampled uniformly from `charset`, which takes precedence over `space` and
+/// `json_safe`. `charset` must not be empty, and it cannot be used together with `pronounceable`.
+///
+/// The optional `blocklist_path` parameter takes the path to a line-delimited file of strings which
+/// should never be generated, such as profanity or reserved words. A generated string which is in
+/// the blocklist is discarded and generated again, up to 100 attempts, after which an error is
+/// returned. The blocklist is read only once and cached.
+///
+/// Pass `quote=true` to render the string as a quoted JSON string literal, escaping any special
+/// characters, so that the template does not need to add quotation marks around it. See the
+/// [crate-level documentation](crate#quoting).
 ///
 /// # Example usage
 ///
@@ -28,6 +81,10 @@ use tera::{to_value, Result, Value};
 /// let rendered: String = tera
 ///     .render_str("{{ random_string(length=16) }}", &context)
 ///     .unwrap();
+/// // use a length between 4 and 12
+/// let rendered: String = tera
+///     .render_str("{{ random_string(length_min=4, length_max=12) }}", &context)
+///     .unwrap();
 /// // use alphanumeric space (which is also the default)
 /// let rendered: String = tera
 ///     .render_str(r#"{{ random_string(space="alphanumeric") }}"#, &context)
@@ -36,26 +93,292 @@ use tera::{to_value, Result, Value};
 /// let rendered: String = tera
 ///     .render_str(r#"{{ random_string(space="standard") }}"#, &context)
 ///     .unwrap();
+/// // use standard space, but avoid characters which would break a JSON string
+/// let rendered: String = tera
+///     .render_str(
+///         r#"{"name": "{{ random_string(space="standard", json_safe=true) }}"}"#,
+///         &context
+///     )
+///     .unwrap();
+/// // alternate consonants and vowels
+/// let rendered: String = tera
+///     .render_str("{{ random_string(length=10, pronounceable=true) }}", &context)
+///     .unwrap();
+/// // sample DNA bases
+/// let rendered: String = tera
+///     .render_str(r#"{{ random_string(length=12, charset="ACGT") }}"#, &context)
+///     .unwrap();
 /// ```
 pub fn random_string(args: &HashMap<String, Value>) -> Result<Value> {
-    let str_length: usize = parse_arg(args, "length")?.unwrap_or(8usize);
+    let str_length: usize = parse_string_length_args(args)?;
+
+    let space_opt: Option<String> = parse_arg(args, "space")?;
+    let pronounceable: bool = parse_arg(args, "pronounceable")?.unwrap_or(false);
+    if pronounceable && space_opt.is_some() {
+        return Err(conflicting_args("pronounceable", "space"));
+    }
+    let space_as_string: String = space_opt.unwrap_or_else(|| String::from("alphanumeric"));
+
+    let charset_opt: Option<Vec<char>> =
+        parse_arg::<String>(args, "charset")?.map(|charset| charset.chars().collect());
+    if pronounceable && charset_opt.is_some() {
+        return Err(conflicting_args("pronounceable", "charset"));
+    }
+    if let Some(charset) = &charset_opt {
+        if charset.is_empty() {
+            return Err(unsupported_arg("charset", String::new()));
+        }
+    }
+
+    let json_safe: bool = parse_arg(args, "json_safe")?.unwrap_or(false);
+
+    let blocklist_path_opt: Option<String> = parse_arg(args, "blocklist_path")?;
+    let blocklist_opt: Option<Arc<HashSet<String>>> = blocklist_path_opt
+        .map(|blocklist_path| read_blocklist(&blocklist_path))
+        .transpose()?;
 
-    let space_as_string: String =
-        parse_arg(args, "space")?.unwrap_or_else(|| String::from("alphanumeric"));
+    let gen_string = || match (&charset_opt, space_as_string.as_str()) {
+        (Some(charset), _) => Ok(with_rng(|rng| {
+            gen_string_from_charset(rng, charset, str_length)
+        })),
+        _ if pronounceable => Ok(with_rng(|rng| gen_pronounceable_string(rng, str_length))),
+        (None, "alphanumeric") => Ok(with_rng(|rng| Alphanumeric.sample_string(rng, str_length))),
+        (None, "standard") if json_safe => Ok(with_rng(|rng| {
+            (0..str_length)
+                .map(|_| gen_json_safe_char(rng))
+                .collect::<String>()
+        })),
+        (None, "standard") => Ok(with_rng(|rng| Standard.sample_string(rng, str_length))),
+        _ => Err(unsupported_arg("space", space_as_string.clone())),
+    };
 
-    let random_string: String = match space_as_string.as_str() {
-        "alphanumeric" => Ok(Alphanumeric.sample_string(&mut thread_rng(), str_length)),
-        "standard" => Ok(Standard.sample_string(&mut thread_rng(), str_length)),
-        _ => Err(unsupported_arg("space", space_as_string)),
-    }?;
+    let random_string: String = match blocklist_opt {
+        Some(blocklist) => gen_string_not_in_blocklist(gen_string, &blocklist)?,
+        None => gen_string()?,
+    };
     let json_value: Value = to_value(random_string)?;
-    Ok(json_value)
+    quote_if_requested(args, json_value)
+}
+
+// Generate a String of `length` lowercase letters which alternate between consonants and vowels,
+// starting with a consonant.
+fn gen_pronounceable_string(rng: &mut dyn RngCore, length: usize) -> String {
+    (0..length)
+        .map(|index| {
+            let letters: &[u8] = if index % 2 == 0 {
+                PRONOUNCEABLE_CONSONANTS
+            } else {
+                PRONOUNCEABLE_VOWELS
+            };
+            sample_byte(rng, letters)
+        })
+        .collect()
+}
+
+// Parse either a fixed `length`, or a `length_min` and `length_max` to sample the length from.
+fn parse_string_length_args(args: &HashMap<String, Value>) -> Result<usize> {
+    let length_opt: Option<usize> = parse_arg(args, "length")?;
+    let length_min_opt: Option<usize> = parse_arg(args, "length_min")?;
+    let length_max_opt: Option<usize> = parse_arg(args, "length_max")?;
+
+    match (length_opt, length_min_opt, length_max_opt) {
+        (Some(length), None, None) => Ok(length),
+        (None, None, None) => Ok(8usize),
+        (Some(_), Some(_), _) => Err(conflicting_args("length", "length_min")),
+        (Some(_), None, Some(_)) => Err(conflicting_args("length", "length_max")),
+        (None, Some(length_min), Some(length_max)) if length_min <= length_max => {
+            Ok(with_rng(|rng| rng.gen_range(length_min..=length_max)))
+        }
+        (None, Some(length_min), Some(length_max)) => {
+            Err(empty_range(length_min.to_string(), length_max.to_string()))
+        }
+        (None, Some(_), None) => Err(missing_arg("length_max")),
+        (None, None, Some(_)) => Err(missing_arg("length_min")),
+    }
+}
+
+// Generate a String of `length` characters, each sampled uniformly from the non-empty `charset`.
+fn gen_string_from_charset(rng: &mut dyn RngCore, charset: &[char], length: usize) -> String {
+    (0..length)
+        .map(|_| charset[rng.gen_range(0usize..charset.len())])
+        .collect()
+}
+
+// Call `gen_string` until it generates a String which is not in `blocklist`, giving up after
+// `MAX_BLOCKLIST_ATTEMPTS` attempts.
+fn gen_string_not_in_blocklist<F>(gen_string: F, blocklist: &HashSet<String>) -> Result<String>
+where
+    F: Fn() -> Result<String>,
+{
+    for _ in 0..MAX_BLOCKLIST_ATTEMPTS {
+        let random_string: String = gen_string()?;
+        if !blocklist.contains(&random_string) {
+            return Ok(random_string);
+        }
+    }
+    Err(blocklist_attempts_exhausted(MAX_BLOCKLIST_ATTEMPTS))
+}
+
+/// A Tera function to generate a random string of lowercase hexadecimal digits, such as for a fake
+/// hash or API token.
+///
+/// The `length` parameter takes the number of hex digits to generate, which defaults to 32, the
+/// length of an MD5 hash. An odd `length` is allowed, and it must be at most 1,000,000.
+///
+/// Pass `quote=true` to render the string wrapped in JSON quotation marks.
+///
+/// # Example usage
+///
+/// ```edition2021
+/// use tera::{Context, Tera};
+/// use tera_rand::random_hex;
+///
+/// let mut tera: Tera = Tera::default();
+/// tera.register_function("random_hex", random_hex);
+/// let context: Context = Context::new();
+///
+/// let rendered: String = tera
+///     .render_str("{{ random_hex() }}", &context)
+///     .unwrap();
+/// // the length of a SHA-256 hash
+/// let rendered: String = tera
+///     .render_str("{{ random_hex(length=64) }}", &context)
+///     .unwrap();
+/// ```
+pub fn random_hex(args: &HashMap<String, Value>) -> Result<Value> {
+    let hex_length: usize = parse_arg(args, "length")?.unwrap_or(32usize);
+    if hex_length > MAX_HEX_LENGTH {
+        return Err(unsupported_arg("length", hex_length.to_string()));
+    }
+
+    // each random byte provides two hex digits. `usize::div_ceil` needs a newer Rust than the
+    // minimum supported version
+    let mut bytes: Vec<u8> = vec![0u8; hex_length / 2 + hex_length % 2];
+    with_rng(|rng| rng.fill(bytes.as_mut_slice()));
+    let mut random_hex: String = bytes.iter().map(|byte| format!("{byte:02x}")).collect();
+    random_hex.truncate(hex_length);
+
+    let json_value: Value = to_value(random_hex)?;
+    quote_if_requested(args, json_value)
+}
+
+/// A Tera function to generate random bytes encoded as a standard, padded base64 string, such as
+/// for a fake session token or binary payload.
+///
+/// The `bytes` parameter takes the number of random bytes to encode, which defaults to 16. The
+/// generated string is 4 characters long for every 3 bytes, rounded up.
+///
+/// Pass `quote=true` to render the string wrapped in JSON quotation marks.
+///
+/// # Example usage
+///
+/// ```edition2021
+/// use tera::{Context, Tera};
+/// use tera_rand::random_base64;
+///
+/// let mut tera: Tera = Tera::default();
+/// tera.register_function("random_base64", random_base64);
+/// let context: Context = Context::new();
+///
+/// let rendered: String = tera
+///     .render_str("{{ random_base64() }}", &context)
+///     .unwrap();
+/// // encode 32 random bytes, such as for a secret key
+/// let rendered: String = tera
+///     .render_str("{{ random_base64(bytes=32) }}", &context)
+///     .unwrap();
+/// ```
+pub fn random_base64(args: &HashMap<String, Value>) -> Result<Value> {
+    let byte_count: usize = parse_arg(args, "bytes")?.unwrap_or(16usize);
+
+    let mut bytes: Vec<u8> = vec![0u8; byte_count];
+    with_rng(|rng| rng.fill(bytes.as_mut_slice()));
+    let random_base64: String = BASE64_STANDARD.encode(bytes);
+
+    let json_value: Value = to_value(random_base64)?;
+    quote_if_requested(args, json_value)
+}
+
+/// A Tera function to generate a random identifier which is valid as an unquoted SQL identifier,
+/// such as `tbl_a1b2`. The identifier starts with a lowercase letter or an underscore, followed by
+/// lowercase letters, digits, and underscores.
+///
+/// The `max_length` parameter bounds the length of the identifier, including any prefix. The
+/// length is sampled uniformly between 1 (or one more than the length of the prefix) and
+/// `max_length`. If `max_length` is not passed in, it defaults to 16.
+///
+/// The optional `prefix` parameter takes a String to start the identifier with, like `tbl_`. The
+/// prefix must itself be a valid identifier, and it must be shorter than `max_length` so that at
+/// least one random character follows it.
+///
+/// Pass `quote=true` to render the identifier wrapped in JSON quotation marks.
+///
+/// # Example usage
+///
+/// ```edition2021
+/// use tera::{Context, Tera};
+/// use tera_rand::random_identifier;
+///
+/// let mut tera: Tera = Tera::default();
+/// tera.register_function("random_identifier", random_identifier);
+/// let context: Context = Context::new();
+///
+/// let rendered: String = tera
+///     .render_str("CREATE TABLE {{ random_identifier() }} (id INT);", &context)
+///     .unwrap();
+/// // start with a prefix and keep within PostgreSQL's limit of 63 characters
+/// let rendered: String = tera
+///     .render_str(
+///         r#"CREATE TABLE {{ random_identifier(prefix="tbl_", max_length=63) }} (id INT);"#,
+///         &context
+///     )
+///     .unwrap();
+/// ```
+pub fn random_identifier(args: &HashMap<String, Value>) -> Result<Value> {
+    let max_length: usize = parse_arg(args, "max_length")?.unwrap_or(16usize);
+    let prefix: String = parse_arg(args, "prefix")?.unwrap_or_default();
+
+    if !prefix.is_empty() && !is_identifier(&prefix) {
+        return Err(unsupported_arg("prefix", prefix));
+    }
+    if prefix.len() >= max_length {
+        return Err(unsupported_arg("max_length", max_length.to_string()));
+    }
+
+    let identifier: String = with_rng(|rng| {
+        let length: usize = rng.gen_range(prefix.len() + 1..=max_length);
+        let mut identifier: String = prefix;
+        if identifier.is_empty() {
+            identifier.push(sample_byte(rng, IDENTIFIER_START_CHARS));
+        }
+        while identifier.len() < length {
+            identifier.push(sample_byte(rng, IDENTIFIER_CHARS));
+        }
+        identifier
+    });
+    let json_value: Value = to_value(identifier)?;
+    quote_if_requested(args, json_value)
+}
+
+// Whether `s` is made up of only identifier characters and starts with a letter or underscore.
+fn is_identifier(s: &str) -> bool {
+    let mut chars = s.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn sample_byte(rng: &mut dyn RngCore, bytes: &[u8]) -> char {
+    char::from(bytes[rng.gen_range(0usize..bytes.len())])
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::common::tests::test_tera_rand_function;
+    use crate::common::tests::{test_tera_rand_function, test_tera_rand_function_returns_error};
     use crate::string::*;
+    use regex::Regex;
+    use tera::{Context, Tera};
     use tracing_test::traced_test;
 
     #[test]
@@ -112,4 +435,354 @@ mod tests {
             r#"\{ "some_field": ".{12}" }"#,
         );
     }
+
+    #[test]
+    #[traced_test]
+    fn test_random_string_with_zero_length() {
+        test_tera_rand_function(
+            random_string,
+            "random_string",
+            r#"{ "some_field": "{{ random_string(length=0) }}" }"#,
+            r#"\{ "some_field": "" }"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_string_with_negative_length_returns_error() {
+        test_tera_rand_function_returns_error(
+            random_string,
+            "random_string",
+            r#"{{ random_string(length=-1) }}"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_string_with_standard_space_and_json_safe_is_valid_json() {
+        let mut tera: Tera = Tera::default();
+        tera.register_function("random_string", random_string);
+        let context: Context = Context::new();
+
+        for _ in 0..100 {
+            let rendered: String = tera
+                .render_str(
+                    r#"{ "some_field": "{{ random_string(space="standard", length=64, json_safe=true) }}" }"#,
+                    &context,
+                )
+                .unwrap();
+            let parsed: Value = serde_json::from_str(&rendered)
+                .unwrap_or_else(|e| panic!("Rendered invalid JSON {rendered} due to error: {e:?}"));
+            assert_eq!(parsed["some_field"].as_str().unwrap().chars().count(), 64);
+        }
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_string_with_quote() {
+        let args: HashMap<String, Value> = HashMap::from([
+            (String::from("space"), Value::from("standard")),
+            (String::from("length"), Value::from(64)),
+            (String::from("quote"), Value::from(true)),
+        ]);
+        for _ in 0..100 {
+            let quoted_string: Value = random_string(&args).unwrap();
+            let quoted_string: &str = quoted_string.as_str().unwrap();
+
+            let parsed_string: String = serde_json::from_str(quoted_string).unwrap();
+            assert_eq!(parsed_string.chars().count(), 64usize);
+        }
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_string_with_blocklist() {
+        let args: HashMap<String, Value> = HashMap::from([
+            (String::from("length"), Value::from(1)),
+            (
+                String::from("blocklist_path"),
+                Value::from("resources/test/blocklist_half_alphanumeric.txt"),
+            ),
+        ]);
+        let blocklist: Arc<HashSet<String>> =
+            read_blocklist("resources/test/blocklist_half_alphanumeric.txt").unwrap();
+        for _ in 0..200 {
+            let random_string: Value = random_string(&args).unwrap();
+            assert!(!blocklist.contains(random_string.as_str().unwrap()));
+        }
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_string_with_everything_blocklisted_returns_error() {
+        test_tera_rand_function_returns_error(
+            random_string,
+            "random_string",
+            r#"{{ random_string(length=1, blocklist_path="resources/test/blocklist_alphanumeric.txt") }}"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_string_pronounceable_alternates_consonants_and_vowels() {
+        let args: HashMap<String, Value> = HashMap::from([
+            (String::from("length"), Value::from(11)),
+            (String::from("pronounceable"), Value::from(true)),
+        ]);
+        for _ in 0..100 {
+            let random_string: Value = random_string(&args).unwrap();
+            let random_string: &str = random_string.as_str().unwrap();
+
+            assert_eq!(random_string.len(), 11);
+            for (index, c) in random_string.bytes().enumerate() {
+                let is_vowel: bool = PRONOUNCEABLE_VOWELS.contains(&c);
+                let is_consonant: bool = PRONOUNCEABLE_CONSONANTS.contains(&c);
+                assert_eq!(is_consonant, index % 2 == 0);
+                assert_eq!(is_vowel, index % 2 == 1);
+            }
+        }
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_string_pronounceable_with_space_returns_error() {
+        test_tera_rand_function_returns_error(
+            random_string,
+            "random_string",
+            r#"{{ random_string(pronounceable=true, space="standard") }}"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_string_with_length_range() {
+        let args: HashMap<String, Value> = HashMap::from([
+            (String::from("length_min"), Value::from(3)),
+            (String::from("length_max"), Value::from(6)),
+        ]);
+        let lengths: HashSet<usize> = (0..500)
+            .map(|_| random_string(&args).unwrap().as_str().unwrap().len())
+            .collect();
+        assert_eq!(lengths, HashSet::from([3, 4, 5, 6]));
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_string_with_equal_length_bounds() {
+        test_tera_rand_function(
+            random_string,
+            "random_string",
+            r#"{ "some_field": "{{ random_string(length_min=5, length_max=5) }}" }"#,
+            r#"^\{ "some_field": "[A-Za-z0-9]{5}" }$"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_string_with_invalid_length_range_returns_error() {
+        for template in [
+            "{{ random_string(length_min=2) }}",
+            "{{ random_string(length_max=6) }}",
+            "{{ random_string(length_min=6, length_max=2) }}",
+        ] {
+            test_tera_rand_function_returns_error(random_string, "random_string", template);
+        }
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_string_with_length_and_length_range_returns_error() {
+        for (range_args, conflicting_parameter) in [
+            (vec![("length_min", 2), ("length_max", 6)], "length_min"),
+            (vec![("length_max", 6)], "length_max"),
+        ] {
+            let mut args: HashMap<String, Value> =
+                HashMap::from([(String::from("length"), Value::from(4))]);
+            for (parameter, argument) in range_args {
+                args.insert(String::from(parameter), Value::from(argument));
+            }
+
+            let error_message: String = random_string(&args).unwrap_err().to_string();
+            assert!(
+                error_message.contains(&format!(
+                    "`length` cannot be used together with `{conflicting_parameter}`"
+                )),
+                "{error_message}"
+            );
+        }
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_string_with_charset() {
+        for _ in 0..100 {
+            test_tera_rand_function(
+                random_string,
+                "random_string",
+                r#"{ "some_field": "{{ random_string(charset="ACGT") }}" }"#,
+                r#"^\{ "some_field": "[ACGT]{8}" }$"#,
+            );
+        }
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_string_with_charset_takes_precedence_over_space() {
+        test_tera_rand_function(
+            random_string,
+            "random_string",
+            r#"{ "some_field": "{{ random_string(length=16, charset="0123456789abcdef", space="standard") }}" }"#,
+            r#"^\{ "some_field": "[0-9a-f]{16}" }$"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_string_with_multi_byte_charset() {
+        let args: HashMap<String, Value> = HashMap::from([
+            (String::from("length"), Value::from(10)),
+            (String::from("charset"), Value::from("αβγ")),
+        ]);
+        let random_string: Value = random_string(&args).unwrap();
+        let random_string: &str = random_string.as_str().unwrap();
+        assert_eq!(random_string.chars().count(), 10);
+        assert!(random_string.chars().all(|c| "αβγ".contains(c)));
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_string_with_invalid_charset_returns_error() {
+        for template in [
+            r#"{{ random_string(charset="") }}"#,
+            r#"{{ random_string(charset="abc", pronounceable=true) }}"#,
+        ] {
+            test_tera_rand_function_returns_error(random_string, "random_string", template);
+        }
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_identifier() {
+        let identifier_regex: Regex = Regex::new(r"^[a-z_][a-z0-9_]*$").unwrap();
+        for _ in 0..100 {
+            let identifier: Value = random_identifier(&HashMap::new()).unwrap();
+            let identifier: &str = identifier.as_str().unwrap();
+            assert!(identifier_regex.is_match(identifier));
+            assert!((1..=16).contains(&identifier.len()));
+        }
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_identifier_with_prefix_and_max_length() {
+        let args: HashMap<String, Value> = HashMap::from([
+            (String::from("prefix"), Value::from("tbl_")),
+            (String::from("max_length"), Value::from(8)),
+        ]);
+        let identifier_regex: Regex = Regex::new(r"^tbl_[a-z0-9_]{1,4}$").unwrap();
+        for _ in 0..100 {
+            let identifier: Value = random_identifier(&args).unwrap();
+            assert!(identifier_regex.is_match(identifier.as_str().unwrap()));
+        }
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_identifier_with_invalid_prefix_returns_error() {
+        test_tera_rand_function_returns_error(
+            random_identifier,
+            "random_identifier",
+            r#"{{ random_identifier(prefix="1tbl") }}"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_identifier_with_prefix_as_long_as_max_length_returns_error() {
+        test_tera_rand_function_returns_error(
+            random_identifier,
+            "random_identifier",
+            r#"{{ random_identifier(prefix="tbl_", max_length=4) }}"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_hex() {
+        test_tera_rand_function(
+            random_hex,
+            "random_hex",
+            r#"{ "some_field": "{{ random_hex() }}" }"#,
+            r#"^\{ "some_field": "[0-9a-f]{32}" }$"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_hex_with_custom_length() {
+        for (length, expected_regex) in [
+            (0, r#"^\{ "some_field": "" }$"#),
+            (7, r#"^\{ "some_field": "[0-9a-f]{7}" }$"#),
+            (64, r#"^\{ "some_field": "[0-9a-f]{64}" }$"#),
+        ] {
+            test_tera_rand_function(
+                random_hex,
+                "random_hex",
+                &format!(r#"{{ "some_field": "{{{{ random_hex(length={length}) }}}}" }}"#),
+                expected_regex,
+            );
+        }
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_base64() {
+        test_tera_rand_function(
+            random_base64,
+            "random_base64",
+            r#"{ "some_field": "{{ random_base64() }}" }"#,
+            r#"^\{ "some_field": "[A-Za-z0-9+/]{22}==" }$"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_base64_decodes_to_requested_bytes() {
+        for byte_count in [0usize, 1, 2, 3, 32] {
+            let args: HashMap<String, Value> =
+                HashMap::from([(String::from("bytes"), Value::from(byte_count))]);
+            let encoded: Value = random_base64(&args).unwrap();
+            let decoded: Vec<u8> = BASE64_STANDARD.decode(encoded.as_str().unwrap()).unwrap();
+            assert_eq!(decoded.len(), byte_count);
+        }
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_hex_with_too_long_length_returns_error() {
+        test_tera_rand_function_returns_error(
+            random_hex,
+            "random_hex",
+            "{{ random_hex(length=1000001) }}",
+        );
+        // Tera cannot parse an integer literal this large, so pass it in directly
+        let args: HashMap<String, Value> =
+            HashMap::from([(String::from("length"), Value::from(u64::MAX))]);
+        assert!(random_hex(&args).is_err());
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_hex_and_base64_with_negative_length_returns_error() {
+        test_tera_rand_function_returns_error(
+            random_hex,
+            "random_hex",
+            "{{ random_hex(length=-1) }}",
+        );
+        test_tera_rand_function_returns_error(
+            random_base64,
+            "random_base64",
+            "{{ random_base64(bytes=-1) }}",
+        );
+    }
 }
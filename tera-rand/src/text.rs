@@ -0,0 +1,182 @@
+use crate::common::{parse_arg, quote_if_requested};
+use crate::error::{missing_arg, unsupported_arg};
+use crate::rng::with_rng;
+use rand::Rng;
+use std::collections::HashMap;
+use tera::{to_value, Result, Value};
+
+// The vocabulary of the classic lorem ipsum placeholder text.
+const LOREM_IPSUM_WORDS: &[&str] = &[
+    "lorem",
+    "ipsum",
+    "dolor",
+    "sit",
+    "amet",
+    "consectetur",
+    "adipiscing",
+    "elit",
+    "sed",
+    "do",
+    "eiusmod",
+    "tempor",
+    "incididunt",
+    "ut",
+    "labore",
+    "et",
+    "dolore",
+    "magna",
+    "aliqua",
+    "enim",
+    "ad",
+    "minim",
+    "veniam",
+    "quis",
+    "nostrud",
+    "exercitation",
+    "ullamco",
+    "laboris",
+    "nisi",
+    "aliquip",
+    "ex",
+    "ea",
+    "commodo",
+    "consequat",
+    "duis",
+    "aute",
+    "irure",
+    "in",
+    "reprehenderit",
+    "voluptate",
+    "velit",
+    "esse",
+    "cillum",
+    "eu",
+    "fugiat",
+    "nulla",
+    "pariatur",
+    "excepteur",
+    "sint",
+    "occaecat",
+    "cupidatat",
+    "non",
+    "proident",
+    "sunt",
+    "culpa",
+    "qui",
+    "officia",
+    "deserunt",
+    "mollit",
+    "anim",
+    "id",
+    "est",
+    "laborum",
+];
+
+/// A Tera function to generate space-separated lorem ipsum words, such as for placeholder log
+/// messages or product descriptions.
+///
+/// The `count` parameter takes the number of words to generate, which must be positive. Each word
+/// is sampled independently from a built-in lorem ipsum vocabulary, so words may repeat.
+///
+/// Pass `capitalize=true` to capitalize the first word, like the start of a sentence.
+///
+/// Pass `quote=true` to render the words wrapped in JSON quotation marks.
+///
+/// # Example usage
+///
+/// ```edition2021
+/// use tera::{Context, Tera};
+/// use tera_rand::random_words;
+///
+/// let mut tera: Tera = Tera::default();
+/// tera.register_function("random_words", random_words);
+/// let context: Context = Context::new();
+///
+/// let rendered: String = tera
+///     .render_str("{{ random_words(count=5) }}", &context)
+///     .unwrap();
+/// // a sentence-like description
+/// let rendered: String = tera
+///     .render_str("{{ random_words(count=12, capitalize=true) }}.", &context)
+///     .unwrap();
+/// ```
+pub fn random_words(args: &HashMap<String, Value>) -> Result<Value> {
+    let count_opt: Option<usize> = parse_arg(args, "count")?;
+    let count: usize = count_opt.ok_or_else(|| missing_arg("count"))?;
+    if count == 0 {
+        return Err(unsupported_arg("count", count.to_string()));
+    }
+    let capitalize: bool = parse_arg(args, "capitalize")?.unwrap_or(false);
+
+    let mut words: Vec<String> = with_rng(|rng| {
+        (0..count)
+            .map(|_| LOREM_IPSUM_WORDS[rng.gen_range(0usize..LOREM_IPSUM_WORDS.len())].to_string())
+            .collect()
+    });
+    if capitalize {
+        words[0] = capitalize_word(&words[0]);
+    }
+
+    let json_value: Value = to_value(words.join(" "))?;
+    quote_if_requested(args, json_value)
+}
+
+fn capitalize_word(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::tests::{test_tera_rand_function, test_tera_rand_function_returns_error};
+    use crate::text::*;
+    use tracing_test::traced_test;
+
+    #[test]
+    #[traced_test]
+    fn test_random_words() {
+        test_tera_rand_function(
+            random_words,
+            "random_words",
+            r#"{ "some_field": "{{ random_words(count=5) }}" }"#,
+            r#"^\{ "some_field": "[a-z]+( [a-z]+){4}" }$"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_words_are_from_vocabulary() {
+        let args: HashMap<String, Value> =
+            HashMap::from([(String::from("count"), Value::from(50))]);
+        let words: Value = random_words(&args).unwrap();
+        let words: Vec<&str> = words.as_str().unwrap().split(' ').collect();
+        assert_eq!(words.len(), 50);
+        assert!(words.iter().all(|word| LOREM_IPSUM_WORDS.contains(word)));
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_words_with_capitalize() {
+        test_tera_rand_function(
+            random_words,
+            "random_words",
+            r#"{ "some_field": "{{ random_words(count=3, capitalize=true) }}" }"#,
+            r#"^\{ "some_field": "[A-Z][a-z]*( [a-z]+){2}" }$"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_words_with_invalid_count_returns_error() {
+        for template in [
+            "{{ random_words() }}",
+            "{{ random_words(count=0) }}",
+            "{{ random_words(count=-1) }}",
+        ] {
+            test_tera_rand_function_returns_error(random_words, "random_words", template);
+        }
+    }
+}
@@ -0,0 +1,532 @@
+#[cfg(feature = "time")]
+use crate::common::quote_if_requested;
+use crate::common::{current_unix_timestamp, parse_arg};
+#[cfg(not(feature = "time"))]
+use crate::error::feature_not_enabled;
+#[cfg(feature = "time")]
+use crate::error::{arg_parse_error, internal_error, missing_arg};
+use crate::error::{empty_range, unsupported_arg};
+use crate::rng::with_rng;
+#[cfg(feature = "time")]
+use anyhow::anyhow;
+#[cfg(feature = "time")]
+use chrono::format::{Item, StrftimeItems};
+#[cfg(feature = "time")]
+use chrono::{DateTime, Duration, SecondsFormat, TimeZone, Utc};
+use rand::Rng;
+use std::collections::HashMap;
+#[cfg(feature = "time")]
+use std::time::SystemTime;
+#[cfg(feature = "time")]
+use tera::to_value;
+use tera::{Result, Value};
+
+// How far back `random_datetime` reaches when it is not given a `start`.
+#[cfg(feature = "time")]
+const DEFAULT_DATETIME_RANGE_DAYS: i64 = 30;
+
+// How far back `random_timestamp` reaches when it is not given a `start`, in seconds.
+const DEFAULT_TIMESTAMP_RANGE_SECS: i64 = 365 * 24 * 60 * 60;
+
+// The units which `random_timestamp` can render in, along with the number of each unit in a
+// second.
+const TIMESTAMP_UNITS: &[(&str, i64)] =
+    &[("seconds", 1), ("millis", 1_000), ("nanos", 1_000_000_000)];
+
+/// A Tera function to generate a random date and time, such as `2023-11-14T22:13:20Z`.
+///
+/// The `start` and `end` parameters take the inclusive bounds of the range to sample from, as
+/// RFC 3339 strings like `"2023-01-01T00:00:00Z"` or `"2023-01-01T09:00:00+09:00"`. The date and
+/// time is sampled uniformly from this range, to the second. If `end` is not provided, it defaults
+/// to the current time. If `start` is not provided, it defaults to 30 days before `end`.
+///
+/// By default, the date and time is rendered as an RFC 3339 string in UTC. The `format` parameter
+/// takes a [`chrono` strftime pattern] to render it with instead, such as `"%Y-%m-%d"`.
+///
+/// This function requires the `time` feature, which is not enabled by default.
+///
+/// Pass `quote=true` to render the date and time wrapped in JSON quotation marks.
+///
+/// # Example usage
+///
+/// ```edition2021
+/// use tera::{Context, Tera};
+/// use tera_rand::random_datetime;
+///
+/// let mut tera: Tera = Tera::default();
+/// tera.register_function("random_datetime", random_datetime);
+/// let context: Context = Context::new();
+///
+/// // sometime in the last 30 days
+/// let rendered: tera::Result<String> = tera.render_str("{{ random_datetime() }}", &context);
+/// // a date in 2023
+/// let rendered: tera::Result<String> = tera.render_str(
+///     r#"{{ random_datetime(start="2023-01-01T00:00:00Z", end="2023-12-31T23:59:59Z", format="%Y-%m-%d") }}"#,
+///     &context,
+/// );
+/// ```
+///
+/// [`chrono` strftime pattern]: https://docs.rs/chrono/latest/chrono/format/strftime/index.html
+#[cfg(feature = "time")]
+pub fn random_datetime(args: &HashMap<String, Value>) -> Result<Value> {
+    let end: DateTime<Utc> = match parse_datetime_arg(args, "end")? {
+        Some(end) => end,
+        None => DateTime::<Utc>::from(SystemTime::now()),
+    };
+    let start: DateTime<Utc> = match parse_datetime_arg(args, "start")? {
+        Some(start) => start,
+        None => end - Duration::days(DEFAULT_DATETIME_RANGE_DAYS),
+    };
+    if start > end {
+        return Err(empty_range(start.to_rfc3339(), end.to_rfc3339()));
+    }
+    let format_opt: Option<String> = parse_arg(args, "format")?;
+
+    let timestamp: i64 = with_rng(|rng| rng.gen_range(start.timestamp()..=end.timestamp()));
+    let random_datetime: DateTime<Utc> = Utc
+        .timestamp_opt(timestamp, 0)
+        .single()
+        .ok_or_else(|| internal_error(format!("{timestamp} is not a valid timestamp")))?;
+
+    let rendered: String = match format_opt {
+        Some(format) => {
+            let items: Vec<Item> = StrftimeItems::new(&format).collect();
+            if items.contains(&Item::Error) {
+                return Err(unsupported_arg("format", format));
+            }
+            random_datetime
+                .format_with_items(items.into_iter())
+                .to_string()
+        }
+        None => random_datetime.to_rfc3339_opts(SecondsFormat::Secs, true),
+    };
+    let json_value: Value = to_value(rendered)?;
+    quote_if_requested(args, json_value)
+}
+
+/// A stub for [`random_datetime`] when the `time` feature is disabled, which always returns an
+/// error explaining that the feature is not enabled.
+#[cfg(not(feature = "time"))]
+pub fn random_datetime(_args: &HashMap<String, Value>) -> Result<Value> {
+    Err(feature_not_enabled("random_datetime", "time"))
+}
+
+/// A Tera function to generate a random Unix timestamp, such as `1700000000`, for log-style data
+/// which records times as numbers rather than formatted dates.
+///
+/// The `start` and `end` parameters take the inclusive bounds of the range to sample from, in
+/// seconds since the Unix epoch. If `end` is not provided, it defaults to the current time. If
+/// `start` is not provided, it defaults to one year before `end`.
+///
+/// The `unit` parameter takes the unit of the generated timestamp: `"seconds"`, `"millis"`, or
+/// `"nanos"`. It defaults to `"seconds"`. The bounds are always in seconds, and a timestamp in a
+/// finer unit may fall anywhere between them, not only on a whole second.
+///
+/// Unlike [`random_datetime`], this function does not require the `time` feature.
+///
+/// # Example usage
+///
+/// ```edition2021
+/// use tera::{Context, Tera};
+/// use tera_rand::random_timestamp;
+///
+/// let mut tera: Tera = Tera::default();
+/// tera.register_function("random_timestamp", random_timestamp);
+/// let context: Context = Context::new();
+///
+/// // sometime in the past year
+/// let rendered: String = tera
+///     .render_str("{{ random_timestamp() }}", &context)
+///     .unwrap();
+/// // milliseconds within a single day
+/// let rendered: String = tera
+///     .render_str(
+///         r#"{{ random_timestamp(start=1700000000, end=1700086400, unit="millis") }}"#,
+///         &context,
+///     )
+///     .unwrap();
+/// ```
+pub fn random_timestamp(args: &HashMap<String, Value>) -> Result<Value> {
+    let end_opt: Option<i64> = parse_arg(args, "end")?;
+    let start_opt: Option<i64> = parse_arg(args, "start")?;
+    let unit: String = parse_arg(args, "unit")?.unwrap_or_else(|| String::from("seconds"));
+    let units_per_second: i64 = TIMESTAMP_UNITS
+        .iter()
+        .find(|(name, _)| *name == unit)
+        .map(|(_, units_per_second)| *units_per_second)
+        .ok_or_else(|| unsupported_arg("unit", unit))?;
+
+    let end: i64 = match end_opt {
+        Some(end) => end,
+        None => current_unix_timestamp()?,
+    };
+    let start: i64 = match start_opt {
+        Some(start) => start,
+        None => end.saturating_sub(DEFAULT_TIMESTAMP_RANGE_SECS),
+    };
+    if start > end {
+        return Err(empty_range(start.to_string(), end.to_string()));
+    }
+
+    let start: i64 = start
+        .checked_mul(units_per_second)
+        .ok_or_else(|| unsupported_arg("start", start.to_string()))?;
+    let end: i64 = end
+        .checked_mul(units_per_second)
+        .ok_or_else(|| unsupported_arg("end", end.to_string()))?;
+    let timestamp: i64 = with_rng(|rng| rng.gen_range(start..=end));
+    Ok(Value::from(timestamp))
+}
+
+/// A Tera function to generate a random duration, such as for request latencies or cache TTLs.
+///
+/// The `min` and `max` parameters take the inclusive bounds of the range to sample from, as
+/// ISO 8601 durations like `"PT0.5S"`, `"PT15M"`, or `"P1DT12H"`. The duration is sampled uniformly
+/// from this range, to the millisecond. `min` defaults to zero, and `max` is required. As in the
+/// CLI's duration options, a year counts as 365 days and a month as 30 days.
+///
+/// The `format` parameter takes how to render the duration:
+/// - `"iso8601"`, the default, renders an ISO 8601 duration String in days, hours, minutes and
+///   seconds, like `P1DT2H3M4.567S`
+/// - `"seconds"` renders the whole number of seconds, rounded down, as a number
+/// - `"millis"` renders the number of milliseconds as a number
+///
+/// This function requires the `time` feature, which is not enabled by default.
+///
+/// Pass `quote=true` to render an ISO 8601 duration wrapped in JSON quotation marks.
+///
+/// # Example usage
+///
+/// ```edition2021
+/// use tera::{Context, Tera};
+/// use tera_rand::random_duration;
+///
+/// let mut tera: Tera = Tera::default();
+/// tera.register_function("random_duration", random_duration);
+/// let context: Context = Context::new();
+///
+/// let rendered: tera::Result<String> =
+///     tera.render_str(r#"{{ random_duration(max="PT1H") }}"#, &context);
+/// // request latency in milliseconds
+/// let rendered: tera::Result<String> = tera.render_str(
+///     r#"{{ random_duration(min="PT0.005S", max="PT2S", format="millis") }}"#,
+///     &context,
+/// );
+/// ```
+#[cfg(feature = "time")]
+pub fn random_duration(args: &HashMap<String, Value>) -> Result<Value> {
+    let min: u64 = parse_duration_millis_arg(args, "min")?.unwrap_or(0);
+    let max_opt: Option<u64> = parse_duration_millis_arg(args, "max")?;
+    let max: u64 = max_opt.ok_or_else(|| missing_arg("max"))?;
+    if min > max {
+        return Err(empty_range(
+            format_iso8601_duration(min),
+            format_iso8601_duration(max),
+        ));
+    }
+    let format: String = parse_arg(args, "format")?.unwrap_or_else(|| String::from("iso8601"));
+
+    let millis: u64 = with_rng(|rng| rng.gen_range(min..=max));
+    match format.as_str() {
+        "iso8601" => {
+            let json_value: Value = to_value(format_iso8601_duration(millis))?;
+            quote_if_requested(args, json_value)
+        }
+        "seconds" => Ok(Value::from(millis / 1_000)),
+        "millis" => Ok(Value::from(millis)),
+        _ => Err(unsupported_arg("format", format)),
+    }
+}
+
+/// A stub for [`random_duration`] when the `time` feature is disabled, which always returns an
+/// error explaining that the feature is not enabled.
+#[cfg(not(feature = "time"))]
+pub fn random_duration(_args: &HashMap<String, Value>) -> Result<Value> {
+    Err(feature_not_enabled("random_duration", "time"))
+}
+
+// Parse an ISO 8601 duration argument into a number of milliseconds.
+#[cfg(feature = "time")]
+fn parse_duration_millis_arg(
+    args: &HashMap<String, Value>,
+    parameter: &'static str,
+) -> Result<Option<u64>> {
+    let duration_opt: Option<String> = parse_arg(args, parameter)?;
+    duration_opt
+        .map(|duration| {
+            let parsed: iso8601::Duration = duration
+                .parse()
+                .map_err(|source: String| arg_parse_error(parameter, anyhow!(source)))?;
+            let millis: u128 = std::time::Duration::from(parsed).as_millis();
+            u64::try_from(millis).map_err(|_| unsupported_arg(parameter, duration))
+        })
+        .transpose()
+}
+
+// Render a number of milliseconds as an ISO 8601 duration, such as `P1DT2H3M4.567S`. Only days
+// and smaller units are used, since years and months have no fixed length.
+#[cfg(feature = "time")]
+fn format_iso8601_duration(millis: u64) -> String {
+    let days: u64 = millis / 86_400_000;
+    let hours: u64 = millis / 3_600_000 % 24;
+    let minutes: u64 = millis / 60_000 % 60;
+    let seconds: u64 = millis / 1_000 % 60;
+    let sub_second_millis: u64 = millis % 1_000;
+
+    let mut formatted: String = String::from("P");
+    if days > 0 {
+        formatted.push_str(&format!("{days}D"));
+    }
+    if hours > 0 || minutes > 0 || seconds > 0 || sub_second_millis > 0 || days == 0 {
+        formatted.push('T');
+        if hours > 0 {
+            formatted.push_str(&format!("{hours}H"));
+        }
+        if minutes > 0 {
+            formatted.push_str(&format!("{minutes}M"));
+        }
+        if sub_second_millis > 0 {
+            let fraction: String = format!("{sub_second_millis:03}");
+            formatted.push_str(&format!("{seconds}.{}S", fraction.trim_end_matches('0')));
+        } else if seconds > 0 || formatted == "PT" {
+            formatted.push_str(&format!("{seconds}S"));
+        }
+    }
+    formatted
+}
+
+#[cfg(feature = "time")]
+fn parse_datetime_arg(
+    args: &HashMap<String, Value>,
+    parameter: &'static str,
+) -> Result<Option<DateTime<Utc>>> {
+    let datetime_opt: Option<String> = parse_arg(args, parameter)?;
+    datetime_opt
+        .map(|datetime| {
+            DateTime::parse_from_rfc3339(&datetime)
+                .map(|datetime| datetime.with_timezone(&Utc))
+                .map_err(|source| arg_parse_error(parameter, source))
+        })
+        .transpose()
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "time")]
+    use crate::common::tests::test_tera_rand_function;
+    use crate::common::tests::test_tera_rand_function_returns_error;
+    use crate::time::*;
+    use tera::{Context, Tera};
+    use tracing_test::traced_test;
+
+    #[test]
+    #[traced_test]
+    #[cfg(feature = "time")]
+    fn test_random_datetime() {
+        test_tera_rand_function(
+            random_datetime,
+            "random_datetime",
+            r#"{ "some_field": "{{ random_datetime() }}" }"#,
+            r#"^\{ "some_field": "\d{4}-\d{2}-\d{2}T\d{2}:\d{2}:\d{2}Z" }$"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    #[cfg(feature = "time")]
+    fn test_random_datetime_defaults_to_last_30_days() {
+        let before: DateTime<Utc> = DateTime::<Utc>::from(SystemTime::now());
+        let random_datetime: Value = random_datetime(&HashMap::new()).unwrap();
+        let after: DateTime<Utc> = DateTime::<Utc>::from(SystemTime::now());
+
+        let random_datetime: DateTime<Utc> =
+            DateTime::parse_from_rfc3339(random_datetime.as_str().unwrap())
+                .unwrap()
+                .with_timezone(&Utc);
+        assert!(random_datetime >= before - Duration::days(30) - Duration::seconds(1));
+        assert!(random_datetime <= after);
+    }
+
+    #[test]
+    #[traced_test]
+    #[cfg(feature = "time")]
+    fn test_random_datetime_with_start_and_end() {
+        let args: HashMap<String, Value> = HashMap::from([
+            (String::from("start"), Value::from("2023-11-14T22:13:20Z")),
+            // the same range, written with a different offset
+            (
+                String::from("end"),
+                Value::from("2023-11-15T07:13:30+09:00"),
+            ),
+        ]);
+        for _ in 0..100 {
+            let random_datetime: Value = random_datetime(&args).unwrap();
+            let random_datetime: &str = random_datetime.as_str().unwrap();
+            assert!(
+                ("2023-11-14T22:13:20Z"..="2023-11-14T22:13:30Z").contains(&random_datetime),
+                "{random_datetime} is out of range"
+            );
+        }
+    }
+
+    #[test]
+    #[traced_test]
+    #[cfg(feature = "time")]
+    fn test_random_datetime_with_format() {
+        test_tera_rand_function(
+            random_datetime,
+            "random_datetime",
+            r#"{ "some_field": "{{ random_datetime(start="2023-06-01T00:00:00Z", end="2023-06-30T23:59:59Z", format="%d/%m/%Y") }}" }"#,
+            r#"^\{ "some_field": "(0[1-9]|[12]\d|30)/06/2023" }$"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    #[cfg(feature = "time")]
+    fn test_random_datetime_with_invalid_args_returns_error() {
+        for template in [
+            r#"{{ random_datetime(start="yesterday") }}"#,
+            r#"{{ random_datetime(end="2023-13-01T00:00:00Z") }}"#,
+            r#"{{ random_datetime(start="2023-06-02T00:00:00Z", end="2023-06-01T00:00:00Z") }}"#,
+            r#"{{ random_datetime(format="%Q") }}"#,
+        ] {
+            test_tera_rand_function_returns_error(random_datetime, "random_datetime", template);
+        }
+    }
+
+    #[test]
+    #[traced_test]
+    #[cfg(feature = "time")]
+    fn test_random_duration() {
+        test_tera_rand_function(
+            random_duration,
+            "random_duration",
+            r#"{ "some_field": "{{ random_duration(min="PT1M", max="PT2M") }}" }"#,
+            r#"^\{ "some_field": "PT(1M(\d+(\.\d+)?S)?|2M)" }$"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    #[cfg(feature = "time")]
+    fn test_random_duration_with_format() {
+        for (format, start, end) in [("seconds", 90, 120), ("millis", 90_000, 120_000)] {
+            let args: HashMap<String, Value> = HashMap::from([
+                (String::from("min"), Value::from("PT1M30S")),
+                (String::from("max"), Value::from("PT2M")),
+                (String::from("format"), Value::from(format)),
+            ]);
+            for _ in 0..100 {
+                let duration: u64 = random_duration(&args).unwrap().as_u64().unwrap();
+                assert!((start..=end).contains(&duration), "{duration} {format}");
+            }
+        }
+    }
+
+    #[test]
+    #[traced_test]
+    #[cfg(feature = "time")]
+    fn test_format_iso8601_duration() {
+        for (millis, expected) in [
+            (0, "PT0S"),
+            (500, "PT0.5S"),
+            (61_000, "PT1M1S"),
+            (3_600_000, "PT1H"),
+            (86_400_000, "P1D"),
+            (93_784_005, "P1DT2H3M4.005S"),
+        ] {
+            assert_eq!(format_iso8601_duration(millis), expected);
+        }
+    }
+
+    #[test]
+    #[traced_test]
+    #[cfg(feature = "time")]
+    fn test_random_duration_with_invalid_args_returns_error() {
+        for template in [
+            r#"{{ random_duration() }}"#,
+            r#"{{ random_duration(max="15 minutes") }}"#,
+            r#"{{ random_duration(min="PT2M", max="PT1M") }}"#,
+            r#"{{ random_duration(max="PT1M", format="hours") }}"#,
+        ] {
+            test_tera_rand_function_returns_error(random_duration, "random_duration", template);
+        }
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_timestamp_with_start_and_end() {
+        for _ in 0..100 {
+            let rendered: String =
+                render_random_timestamp("{{ random_timestamp(start=1700000000, end=1700000010) }}");
+            let timestamp: i64 = rendered.parse().unwrap();
+            assert!((1_700_000_000..=1_700_000_010).contains(&timestamp));
+        }
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_timestamp_with_unit() {
+        for (unit, units_per_second) in TIMESTAMP_UNITS {
+            let template: String = format!(
+                r#"{{{{ random_timestamp(start=1700000000, end=1700000010, unit="{unit}") }}}}"#
+            );
+            let timestamp: i64 = render_random_timestamp(&template).parse().unwrap();
+            assert!(
+                (1_700_000_000 * units_per_second..=1_700_000_010 * units_per_second)
+                    .contains(&timestamp),
+                "{timestamp} is out of range for {unit}"
+            );
+        }
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_timestamp_defaults_to_past_year() {
+        let before: i64 = current_unix_timestamp().unwrap();
+        let timestamp: i64 = random_timestamp(&HashMap::new()).unwrap().as_i64().unwrap();
+        let after: i64 = current_unix_timestamp().unwrap();
+        assert!(timestamp >= before - DEFAULT_TIMESTAMP_RANGE_SECS && timestamp <= after);
+    }
+
+    #[test]
+    #[traced_test]
+    fn test_random_timestamp_with_invalid_args_returns_error() {
+        for template in [
+            "{{ random_timestamp(start=1700000010, end=1700000000) }}",
+            r#"{{ random_timestamp(unit="minutes") }}"#,
+            r#"{{ random_timestamp(start=9223372036, end=9223372037, unit="nanos") }}"#,
+        ] {
+            test_tera_rand_function_returns_error(random_timestamp, "random_timestamp", template);
+        }
+    }
+
+    fn render_random_timestamp(template: &str) -> String {
+        let mut tera: Tera = Tera::default();
+        tera.register_function("random_timestamp", random_timestamp);
+        tera.render_str(template, &Context::new()).unwrap()
+    }
+
+    #[test]
+    #[traced_test]
+    #[cfg(not(feature = "time"))]
+    fn test_random_duration_without_time_feature_returns_error() {
+        test_tera_rand_function_returns_error(
+            random_duration,
+            "random_duration",
+            r#"{{ random_duration(max="PT1M") }}"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    #[cfg(not(feature = "time"))]
+    fn test_random_datetime_without_time_feature_returns_error() {
+        test_tera_rand_function_returns_error(
+            random_datetime,
+            "random_datetime",
+            "{{ random_datetime() }}",
+        );
+    }
+}
@@ -0,0 +1,97 @@
+#[cfg(feature = "ulid")]
+use crate::common::{current_unix_millis, quote_if_requested};
+#[cfg(not(feature = "ulid"))]
+use crate::error::feature_not_enabled;
+#[cfg(feature = "ulid")]
+use crate::rng::with_rng;
+#[cfg(feature = "ulid")]
+use rand::Rng;
+use std::collections::HashMap;
+#[cfg(feature = "ulid")]
+use tera::to_value;
+use tera::{Result, Value};
+#[cfg(feature = "ulid")]
+use ulid::Ulid;
+
+/// A Tera function to generate a random ULID, a 26-character identifier in Crockford's base32,
+/// such as `01HFV3JZ9X8Q2C4N6B7M5K0D1R`. A ULID starts with the current Unix timestamp in
+/// milliseconds, so ULIDs generated in different milliseconds sort lexicographically in the order
+/// they were generated.
+///
+/// The 80 random bits are drawn from the same random number generator as every other tera-rand
+/// function. Under a fixed seed (see [`set_seed`]), only the timestamp varies between renders.
+///
+/// This function requires the `ulid` feature, which is not enabled by default.
+///
+/// Pass `quote=true` to render the ULID wrapped in JSON quotation marks.
+///
+/// # Example usage
+///
+/// ```edition2021
+/// use tera::{Context, Tera};
+/// use tera_rand::random_ulid;
+///
+/// let mut tera: Tera = Tera::default();
+/// tera.register_function("random_ulid", random_ulid);
+///
+/// let context: Context = Context::new();
+/// let rendered: tera::Result<String> = tera.render_str("{{ random_ulid() }}", &context);
+/// ```
+///
+/// [`set_seed`]: crate::set_seed
+#[cfg(feature = "ulid")]
+pub fn random_ulid(args: &HashMap<String, Value>) -> Result<Value> {
+    let random_bits: u128 = with_rng(|rng| rng.gen());
+    // `from_parts` keeps only the low 80 bits of the random part
+    let random_ulid: Ulid = Ulid::from_parts(current_unix_millis()?, random_bits);
+    let json_value: Value = to_value(random_ulid.to_string())?;
+    quote_if_requested(args, json_value)
+}
+
+/// A stub for [`random_ulid`] when the `ulid` feature is disabled, which always returns an error
+/// explaining that the feature is not enabled.
+#[cfg(not(feature = "ulid"))]
+pub fn random_ulid(_args: &HashMap<String, Value>) -> Result<Value> {
+    Err(feature_not_enabled("random_ulid", "ulid"))
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "ulid")]
+    use crate::common::tests::test_tera_rand_function;
+    #[cfg(not(feature = "ulid"))]
+    use crate::common::tests::test_tera_rand_function_returns_error;
+    use crate::ulid::*;
+    use tracing_test::traced_test;
+
+    #[test]
+    #[traced_test]
+    #[cfg(feature = "ulid")]
+    fn test_random_ulid() {
+        test_tera_rand_function(
+            random_ulid,
+            "random_ulid",
+            r#"{ "some_field": "{{ random_ulid() }}" }"#,
+            r#"^\{ "some_field": "[0-9A-HJKMNP-TV-Z]{26}" }$"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    #[cfg(feature = "ulid")]
+    fn test_random_ulid_starts_with_current_timestamp() {
+        let before: u64 = current_unix_millis().unwrap();
+        let random_ulid: Value = random_ulid(&HashMap::new()).unwrap();
+        let after: u64 = current_unix_millis().unwrap();
+
+        let random_ulid: Ulid = Ulid::from_string(random_ulid.as_str().unwrap()).unwrap();
+        assert!((before..=after).contains(&random_ulid.timestamp_ms()));
+    }
+
+    #[test]
+    #[traced_test]
+    #[cfg(not(feature = "ulid"))]
+    fn test_random_ulid_without_ulid_feature_returns_error() {
+        test_tera_rand_function_returns_error(random_ulid, "random_ulid", "{{ random_ulid() }}");
+    }
+}
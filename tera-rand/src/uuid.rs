@@ -1,8 +1,34 @@
+#[cfg(feature = "uuid")]
+use crate::common::{current_unix_millis, parse_arg, quote_if_requested};
+#[cfg(not(feature = "uuid"))]
+use crate::error::feature_not_enabled;
+#[cfg(feature = "uuid")]
+use crate::error::{arg_parse_error, missing_arg, unsupported_arg};
+#[cfg(feature = "uuid")]
+use crate::rng::with_rng;
 use std::collections::HashMap;
-use tera::{to_value, Result, Value};
-use uuid::Uuid;
+#[cfg(feature = "uuid")]
+use tera::to_value;
+use tera::{Result, Value};
+#[cfg(feature = "uuid")]
+use uuid::{Builder, Uuid};
 
-/// A Tera function to generate a random UUIDv4.
+/// A Tera function to generate a random UUID, which is a UUIDv4 by default.
+///
+/// The random bits are drawn from the same random number generator as every other tera-rand
+/// function, so the UUIDs are reproducible under a fixed seed (see [`set_seed`]).
+///
+/// The `version` parameter takes `4` (the default) for a fully random UUIDv4, or `7` for a
+/// time-ordered UUIDv7, as generated by [`random_uuid_v7`].
+///
+/// The `format` parameter chooses how the UUID is written: `"hyphenated"` (the default) for
+/// `67e55044-10b1-426f-9247-bb680e5fe0c8`, `"simple"` for `67e5504410b1426f9247bb680e5fe0c8`, or
+/// `"urn"` for `urn:uuid:67e55044-10b1-426f-9247-bb680e5fe0c8`.
+///
+/// This function requires the `uuid` feature, which is enabled by default. Without the feature,
+/// calling this function returns an error explaining that the feature is not enabled.
+///
+/// Pass `quote=true` to render the UUID wrapped in JSON quotation marks.
 ///
 /// # Example usage
 ///
@@ -15,17 +41,170 @@ use uuid::Uuid;
 ///
 /// let context: Context = Context::new();
 /// let rendered: String = tera.render_str("{{ random_uuid() }}", &context).unwrap();
+/// // a time-ordered UUID without hyphens
+/// let rendered: String = tera
+///     .render_str(r#"{{ random_uuid(version=7, format="simple") }}"#, &context)
+///     .unwrap();
 /// ```
+///
+/// [`set_seed`]: crate::set_seed
 #[cfg(feature = "uuid")]
+pub fn random_uuid(args: &HashMap<String, Value>) -> Result<Value> {
+    let version: u8 = parse_arg(args, "version")?.unwrap_or(4u8);
+
+    let random_uuid: Uuid = match version {
+        4 => {
+            let mut random_bytes: [u8; 16] = [0; 16];
+            with_rng(|rng| rng.fill_bytes(&mut random_bytes));
+            // set the version nibble to 4 and the variant bits to RFC 4122
+            Builder::from_random_bytes(random_bytes).into_uuid()
+        }
+        7 => gen_uuid_v7(current_unix_millis()?),
+        _ => return Err(unsupported_arg("version", version.to_string())),
+    };
+    format_uuid(args, random_uuid)
+}
+
+/// A stub for [`random_uuid`] when the `uuid` feature is disabled, which always returns an error
+/// explaining that the feature is not enabled. This keeps templates and function registrations
+/// working the same way regardless of features, failing only when a UUID is actually rendered.
+#[cfg(not(feature = "uuid"))]
 pub fn random_uuid(_args: &HashMap<String, Value>) -> Result<Value> {
-    let random_uuid: Uuid = Uuid::new_v4();
-    let json_value: Value = to_value(random_uuid.to_string())?;
-    Ok(json_value)
+    Err(feature_not_enabled("random_uuid", "uuid"))
+}
+
+/// A Tera function to generate a random UUIDv7, which starts with the current Unix timestamp in
+/// milliseconds, so that UUIDs generated in different milliseconds sort in the order they were
+/// generated. This suits database primary keys better than the fully random UUIDv4 of
+/// [`random_uuid`].
+///
+/// The remaining random bits are drawn from the same random number generator as every other
+/// tera-rand function. Under a fixed seed (see [`set_seed`]), only the timestamp varies between
+/// renders.
+///
+/// This is the same as [`random_uuid`] with `version=7`, and it accepts the same `format`
+/// parameter.
+///
+/// This function requires the `uuid` feature, which is enabled by default.
+///
+/// Pass `quote=true` to render the UUID wrapped in JSON quotation marks.
+///
+/// # Example usage
+///
+/// ```edition2021
+/// use tera::{Context, Tera};
+/// use tera_rand::random_uuid_v7;
+///
+/// let mut tera: Tera = Tera::default();
+/// tera.register_function("random_uuid_v7", random_uuid_v7);
+///
+/// let context: Context = Context::new();
+/// let rendered: String = tera.render_str("{{ random_uuid_v7() }}", &context).unwrap();
+/// ```
+///
+/// [`set_seed`]: crate::set_seed
+#[cfg(feature = "uuid")]
+pub fn random_uuid_v7(args: &HashMap<String, Value>) -> Result<Value> {
+    let random_uuid: Uuid = gen_uuid_v7(current_unix_millis()?);
+    format_uuid(args, random_uuid)
+}
+
+/// A stub for [`random_uuid_v7`] when the `uuid` feature is disabled, which always returns an
+/// error explaining that the feature is not enabled.
+#[cfg(not(feature = "uuid"))]
+pub fn random_uuid_v7(_args: &HashMap<String, Value>) -> Result<Value> {
+    Err(feature_not_enabled("random_uuid_v7", "uuid"))
+}
+
+/// A Tera function to generate a name-based UUIDv5, which is derived from a namespace and a name,
+/// such as a stable ID for each hostname.
+///
+/// Despite its name, this function is not random: the same `namespace` and `name` always produce
+/// the same UUID, whether or not a seed is set.
+///
+/// The `namespace` parameter takes either one of the standard namespace names, `"dns"`, `"url"`,
+/// `"oid"`, or `"x500"`, or any UUID string to use as a custom namespace. The `name` parameter
+/// takes the string to derive the UUID from. Both are required.
+///
+/// The `format` parameter takes `"hyphenated"` (the default), `"simple"`, or `"urn"`, as in
+/// [`random_uuid`].
+///
+/// This function requires the `uuid` feature, which is enabled by default.
+///
+/// Pass `quote=true` to render the UUID wrapped in JSON quotation marks.
+///
+/// # Example usage
+///
+/// ```edition2021
+/// use tera::{Context, Tera};
+/// use tera_rand::random_uuid_v5;
+///
+/// let mut tera: Tera = Tera::default();
+/// tera.register_function("random_uuid_v5", random_uuid_v5);
+///
+/// let context: Context = Context::new();
+/// let rendered: String = tera
+///     .render_str(r#"{{ random_uuid_v5(namespace="dns", name="www.example.com") }}"#, &context)
+///     .unwrap();
+/// assert_eq!(rendered, "2ed6657d-e927-568b-95e1-2665a8aea6a2");
+/// ```
+#[cfg(feature = "uuid")]
+pub fn random_uuid_v5(args: &HashMap<String, Value>) -> Result<Value> {
+    let namespace_opt: Option<String> = parse_arg(args, "namespace")?;
+    let namespace: String = namespace_opt.ok_or_else(|| missing_arg("namespace"))?;
+    let name_opt: Option<String> = parse_arg(args, "name")?;
+    let name: String = name_opt.ok_or_else(|| missing_arg("name"))?;
+
+    let namespace: Uuid = match namespace.as_str() {
+        "dns" => Uuid::NAMESPACE_DNS,
+        "url" => Uuid::NAMESPACE_URL,
+        "oid" => Uuid::NAMESPACE_OID,
+        "x500" => Uuid::NAMESPACE_X500,
+        _ => Uuid::parse_str(&namespace).map_err(|source| arg_parse_error("namespace", source))?,
+    };
+    format_uuid(args, Uuid::new_v5(&namespace, name.as_bytes()))
+}
+
+/// A stub for [`random_uuid_v5`] when the `uuid` feature is disabled, which always returns an
+/// error explaining that the feature is not enabled.
+#[cfg(not(feature = "uuid"))]
+pub fn random_uuid_v5(_args: &HashMap<String, Value>) -> Result<Value> {
+    Err(feature_not_enabled("random_uuid_v5", "uuid"))
+}
+
+// Write `uuid` in the representation requested by the `format` parameter, quoting it if requested.
+#[cfg(feature = "uuid")]
+fn format_uuid(args: &HashMap<String, Value>, uuid: Uuid) -> Result<Value> {
+    let format: String = parse_arg(args, "format")?.unwrap_or_else(|| String::from("hyphenated"));
+    let formatted_uuid: String = match format.as_str() {
+        "hyphenated" => uuid.hyphenated().to_string(),
+        "simple" => uuid.simple().to_string(),
+        "urn" => uuid.urn().to_string(),
+        _ => return Err(unsupported_arg("format", format)),
+    };
+    let json_value: Value = to_value(formatted_uuid)?;
+    quote_if_requested(args, json_value)
+}
+
+// Lay out a UUIDv7 as in RFC 9562: a 48-bit big-endian millisecond timestamp, the version nibble,
+// and the variant bits, with every other bit random.
+#[cfg(feature = "uuid")]
+fn gen_uuid_v7(unix_millis: u64) -> Uuid {
+    let mut bytes: [u8; 16] = [0; 16];
+    with_rng(|rng| rng.fill_bytes(&mut bytes[6..]));
+    bytes[..6].copy_from_slice(&unix_millis.to_be_bytes()[2..]);
+    bytes[6] = (bytes[6] & 0x0f) | 0x70;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    Uuid::from_bytes(bytes)
 }
 
 #[cfg(test)]
 mod tests {
+    #[cfg(feature = "uuid")]
     use crate::common::tests::test_tera_rand_function;
+    use crate::common::tests::test_tera_rand_function_returns_error;
+    #[cfg(feature = "uuid")]
+    use crate::rng::{clear_seed, set_seed};
     use crate::uuid::*;
     use tracing_test::traced_test;
 
@@ -40,4 +219,196 @@ mod tests {
             r#"\{ "some_field": "[\da-f]{8}-([\da-f]{4}-){3}[\da-f]{12}" }"#,
         );
     }
+
+    #[test]
+    #[traced_test]
+    #[cfg(feature = "uuid")]
+    fn test_random_uuid_is_reproducible_under_fixed_seed() {
+        let args: HashMap<String, Value> = HashMap::new();
+
+        set_seed(31);
+        let first: Vec<Value> = (0..5).map(|_| random_uuid(&args).unwrap()).collect();
+        set_seed(31);
+        let second: Vec<Value> = (0..5).map(|_| random_uuid(&args).unwrap()).collect();
+        clear_seed();
+
+        assert_eq!(first, second);
+        assert_ne!(first[0], first[1]);
+    }
+
+    #[test]
+    #[traced_test]
+    #[cfg(feature = "uuid")]
+    fn test_random_uuid_has_version_and_variant_bits() {
+        for _ in 0..100 {
+            let random_uuid: Value = random_uuid(&HashMap::new()).unwrap();
+            let random_uuid: Uuid = Uuid::parse_str(random_uuid.as_str().unwrap()).unwrap();
+
+            assert_eq!(random_uuid.get_version_num(), 4);
+            assert_eq!(random_uuid.get_variant(), uuid::Variant::RFC4122);
+            // the version is the 13th hex digit, and the variant is in the 17th
+            let hyphenless: String = random_uuid.simple().to_string();
+            assert_eq!(&hyphenless[12..13], "4");
+            assert!(matches!(&hyphenless[16..17], "8" | "9" | "a" | "b"));
+        }
+    }
+
+    #[test]
+    #[traced_test]
+    #[cfg(feature = "uuid")]
+    fn test_random_uuid_v7() {
+        test_tera_rand_function(
+            random_uuid_v7,
+            "random_uuid_v7",
+            r#"{ "some_field": "{{ random_uuid_v7() }}" }"#,
+            r#"^\{ "some_field": "[\da-f]{8}-[\da-f]{4}-7[\da-f]{3}-[89ab][\da-f]{3}-[\da-f]{12}" }$"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    #[cfg(feature = "uuid")]
+    fn test_random_uuid_with_each_version_and_format() {
+        for (version, version_digit) in [(4, "4"), (7, "7")] {
+            for (format, prefix, hyphen) in [
+                ("hyphenated", "", "-"),
+                ("simple", "", ""),
+                ("urn", "urn:uuid:", "-"),
+            ] {
+                let expected_regex: String = format!(
+                    r#"^\{{ "some_field": "{prefix}[\da-f]{{8}}{hyphen}[\da-f]{{4}}{hyphen}{version_digit}[\da-f]{{3}}{hyphen}[89ab][\da-f]{{3}}{hyphen}[\da-f]{{12}}" }}$"#
+                );
+                test_tera_rand_function(
+                    random_uuid,
+                    "random_uuid",
+                    &format!(
+                        r#"{{ "some_field": "{{{{ random_uuid(version={version}, format="{format}") }}}}" }}"#
+                    ),
+                    &expected_regex,
+                );
+            }
+        }
+    }
+
+    #[test]
+    #[traced_test]
+    #[cfg(feature = "uuid")]
+    fn test_random_uuid_v7_with_format() {
+        test_tera_rand_function(
+            random_uuid_v7,
+            "random_uuid_v7",
+            r#"{ "some_field": "{{ random_uuid_v7(format="urn") }}" }"#,
+            r#"^\{ "some_field": "urn:uuid:[\da-f]{8}-[\da-f]{4}-7[\da-f]{3}-[89ab][\da-f]{3}-[\da-f]{12}" }$"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    #[cfg(feature = "uuid")]
+    fn test_random_uuid_with_invalid_args_returns_error() {
+        for template in [
+            "{{ random_uuid(version=1) }}",
+            "{{ random_uuid(version=5) }}",
+            r#"{{ random_uuid(format="braced") }}"#,
+        ] {
+            test_tera_rand_function_returns_error(random_uuid, "random_uuid", template);
+        }
+    }
+
+    #[test]
+    #[traced_test]
+    #[cfg(feature = "uuid")]
+    fn test_random_uuid_v5_is_deterministic() {
+        for (namespace, name, expected_uuid) in [
+            (
+                "dns",
+                "www.example.com",
+                "2ed6657d-e927-568b-95e1-2665a8aea6a2",
+            ),
+            (
+                "url",
+                "https://example.com/",
+                "dd2c1780-811a-5296-81c5-178a0ef488bc",
+            ),
+            (
+                "6ba7b810-9dad-11d1-80b4-00c04fd430c8",
+                "www.example.com",
+                "2ed6657d-e927-568b-95e1-2665a8aea6a2",
+            ),
+        ] {
+            let args: HashMap<String, Value> = HashMap::from([
+                (String::from("namespace"), Value::from(namespace)),
+                (String::from("name"), Value::from(name)),
+            ]);
+            for _ in 0..3 {
+                assert_eq!(random_uuid_v5(&args).unwrap(), expected_uuid);
+            }
+        }
+    }
+
+    #[test]
+    #[traced_test]
+    #[cfg(feature = "uuid")]
+    fn test_random_uuid_v5_with_format() {
+        test_tera_rand_function(
+            random_uuid_v5,
+            "random_uuid_v5",
+            r#"{ "some_field": "{{ random_uuid_v5(namespace="oid", name="1.3.6.1", format="simple") }}" }"#,
+            r#"^\{ "some_field": "[\da-f]{12}5[\da-f]{3}[89ab][\da-f]{15}" }$"#,
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    #[cfg(feature = "uuid")]
+    fn test_random_uuid_v5_with_invalid_args_returns_error() {
+        for template in [
+            r#"{{ random_uuid_v5(name="www.example.com") }}"#,
+            r#"{{ random_uuid_v5(namespace="dns") }}"#,
+            r#"{{ random_uuid_v5(namespace="domain", name="www.example.com") }}"#,
+        ] {
+            test_tera_rand_function_returns_error(random_uuid_v5, "random_uuid_v5", template);
+        }
+        let args: HashMap<String, Value> = HashMap::from([
+            (String::from("namespace"), Value::from("not-a-uuid")),
+            (String::from("name"), Value::from("www.example.com")),
+        ]);
+        let error: tera::Error = random_uuid_v5(&args).unwrap_err();
+        assert!(
+            error.to_string().contains("Unable to parse argument"),
+            "{error}"
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    #[cfg(feature = "uuid")]
+    fn test_gen_uuid_v7_has_timestamp_version_and_variant() {
+        let unix_millis: u64 = 1_700_000_000_123;
+        let random_uuid: Uuid = gen_uuid_v7(unix_millis);
+
+        assert_eq!(random_uuid.get_version_num(), 7);
+        assert_eq!(random_uuid.get_variant(), uuid::Variant::RFC4122);
+        assert_eq!(&random_uuid.simple().to_string()[..12], "018bcfe5687b");
+        // a later millisecond always sorts after an earlier one
+        assert!(gen_uuid_v7(unix_millis + 1) > gen_uuid_v7(unix_millis));
+    }
+
+    #[test]
+    #[traced_test]
+    #[cfg(not(feature = "uuid"))]
+    fn test_random_uuid_v7_without_uuid_feature_returns_error() {
+        test_tera_rand_function_returns_error(
+            random_uuid_v7,
+            "random_uuid_v7",
+            "{{ random_uuid_v7() }}",
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    #[cfg(not(feature = "uuid"))]
+    fn test_random_uuid_without_uuid_feature_returns_error() {
+        test_tera_rand_function_returns_error(random_uuid, "random_uuid", "{{ random_uuid() }}");
+    }
 }
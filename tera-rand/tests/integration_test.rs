@@ -0,0 +1,34 @@
+use tera::{Context, Tera};
+use tera_rand::{clear_seed, register_all, set_seed};
+use tracing_test::traced_test;
+
+const TEMPLATE: &str = r#"{"hostname": "{{ random_string() }}", "cpu_util": {{ random_uint32(end=100) }}, "load": {{ random_float64(precision=2) }}, "address": "{{ random_ipv4() }}", "region": "{{ random_choice(values=["east", "west", "north", "south"]) }}"}"#;
+
+fn render_with_seed(tera: &mut Tera, seed: u64) -> String {
+    set_seed(seed);
+    let rendered: String = tera.render_str(TEMPLATE, &Context::new()).unwrap();
+    clear_seed();
+    rendered
+}
+
+#[test]
+#[traced_test]
+fn test_same_seed_renders_identical_output() {
+    let mut tera: Tera = Tera::default();
+    register_all(&mut tera);
+
+    let first: String = render_with_seed(&mut tera, 42);
+    let second: String = render_with_seed(&mut tera, 42);
+    assert_eq!(first, second);
+}
+
+#[test]
+#[traced_test]
+fn test_different_seeds_render_different_output() {
+    let mut tera: Tera = Tera::default();
+    register_all(&mut tera);
+
+    let first: String = render_with_seed(&mut tera, 42);
+    let second: String = render_with_seed(&mut tera, 43);
+    assert_ne!(first, second);
+}